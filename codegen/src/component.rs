@@ -28,10 +28,12 @@ pub struct ComponentMeta {
     state_meta: StateMeta,
     /// Events metadata if any events declaration.
     events_meta: EventsMeta,
+    /// Whether the component rerenders on its own.
+    isolated: bool,
 }
 
 impl ComponentMeta {
-    pub fn parse(mut item: ItemStruct) -> ParseResult<ComponentMeta> {
+    pub fn parse(mut item: ItemStruct, isolated: bool) -> ParseResult<ComponentMeta> {
         // Remove `#[component]` attribute.
         Self::filter_out_component_attribute(&mut item);
 
@@ -52,6 +54,7 @@ impl ComponentMeta {
             props_meta,
             state_meta,
             events_meta,
+            isolated,
         })
     }
 
@@ -232,6 +235,13 @@ impl ComponentMeta {
         let update_body = self.impl_fn_update_body();
         let refresh_state_body = self.impl_fn_refresh_state_body();
         let status_body = self.impl_fn_status_body();
+        let isolated = if self.isolated {
+            Some(quote! {
+                const ISOLATED: bool = true;
+            })
+        } else {
+            None
+        };
 
         quote! {
             impl Component for #ident {
@@ -239,6 +249,8 @@ impl ComponentMeta {
                 type State = #state_type;
                 type Events = #events_type;
 
+                #isolated
+
                 fn init(
                     __props__: Self::Props,
                     __events__: Self::Events,
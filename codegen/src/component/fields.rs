@@ -139,6 +139,12 @@ impl ComponentField {
                     "Cannot have multiple `#[state]` attributes.",
                 ))?;
             }
+            if state_attrs.is_empty() && field.ident.as_ref().map_or(false, |i| i == "key") {
+                Err(Error::new(
+                    field.ident.span(),
+                    "`key` is reserved by html! for keyed reconciliation and cannot be a prop.",
+                ))?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,97 @@
+//! The errors which point at more than one span, like a closing tag not
+//! matching its opening tag.
+//!
+//! They are parse errors like any other, but their notes are kept aside till
+//! the macro fails, when they are emitted along with the error as a
+//! `proc_macro::Diagnostic` with the `nightly-diagnostics` feature. Without it,
+//! only the error itself is emitted.
+
+use proc_macro2::{Span, TokenStream};
+use std::cell::RefCell;
+use syn::parse::{Error, Parse, Result as ParseResult};
+
+thread_local! {
+    /// The diagnostic of the error created last.
+    static LAST: RefCell<Option<Diagnostic>> = RefCell::new(None);
+}
+
+/// An error along with its notes & help.
+pub struct Diagnostic {
+    span: Span,
+    message: String,
+    notes: Vec<(Span, String)>,
+    help: Option<(Span, String)>,
+}
+
+impl Diagnostic {
+    /// An error at the span.
+    pub fn error(span: Span, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            span,
+            message: message.into(),
+            notes: vec![],
+            help: None,
+        }
+    }
+
+    /// Adds a note pointing at another span.
+    pub fn span_note(mut self, span: Span, note: impl Into<String>) -> Diagnostic {
+        self.notes.push((span, note.into()));
+        self
+    }
+
+    /// Adds a help, e.g. the corrected code, pointing at the span.
+    pub fn span_help(mut self, span: Span, help: impl Into<String>) -> Diagnostic {
+        self.help = Some((span, help.into()));
+        self
+    }
+
+    /// Turns it into a parse error, keeping its notes aside to be emitted if
+    /// the macro fails with it.
+    pub fn into_error(self) -> Error {
+        let error = Error::new(self.span, &self.message);
+        LAST.with(|last| *last.borrow_mut() = Some(self));
+        error
+    }
+
+    #[cfg(feature = "nightly-diagnostics")]
+    fn emit(self, _: Error) -> TokenStream {
+        use proc_macro::Level;
+        use quote::quote;
+
+        let mut diagnostic =
+            proc_macro::Diagnostic::spanned(self.span.unwrap(), Level::Error, self.message);
+        for (span, note) in self.notes {
+            diagnostic = diagnostic.span_note(span.unwrap(), note);
+        }
+        if let Some((span, help)) = self.help {
+            diagnostic = diagnostic.span_help(span.unwrap(), help);
+        }
+        diagnostic.emit();
+        // Stands in for the expression, which has failed already.
+        quote! { unreachable!() }
+    }
+
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    fn emit(self, error: Error) -> TokenStream {
+        error.to_compile_error()
+    }
+}
+
+/// Parses the input of a macro, forgetting the diagnostics of the macros
+/// expanded before.
+pub fn parse<T: Parse>(input: proc_macro::TokenStream) -> ParseResult<T> {
+    LAST.with(|last| last.borrow_mut().take());
+    syn::parse(input)
+}
+
+/// Expands an expression macro which failed with the error, along with the
+/// notes of the error if any.
+pub fn to_compile_error(error: Error) -> TokenStream {
+    let diagnostic = LAST.with(|last| last.borrow_mut().take());
+    match diagnostic {
+        // The diagnostic may be of an error which was recovered from.
+        Some(diagnostic) if diagnostic.message == error.to_string() => diagnostic.emit(error),
+        _ => error.to_compile_error(),
+    }
+}
@@ -1,5 +1,9 @@
 //! The pseudo grammar for the html! macro.
 //!
+//! TEMPLATE -> SELF_CLOSING ROOT
+//!
+//! SELF_CLOSING -> #![self_closing(TAGNAMES)] SELF_CLOSING | EPS
+//!
 //! ROOT -> ITEM ITEM
 //!
 //! ITEM -> ELEMENT ITEM | EXPR_BLOCK ITEM | TEXT ITEM | EPS
@@ -27,17 +31,62 @@
 //!
 //! N.B. EPS is Epsilon and IDENT & EXPR are Rust constructs.
 
-use self::element::{HtmlElement, KeyAttribute};
-use proc_macro2::TokenStream;
+use self::element::{HtmlElement, KeyAttribute, TagName};
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse::{Parse, ParseStream, Result as ParseResult},
-    token, Block as RustExpressionBlock, LitStr, Token,
+    braced, bracketed, parenthesized,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    punctuated::Punctuated,
+    token, Block as RustExpressionBlock, Expr, Ident, LitStr, Token,
 };
 
 mod element;
 mod kw;
 
+/// The input of the html! macro, i.e. the markup led by the declarations of
+/// the custom tags which are self-closing within it, like
+/// `#![self_closing(my-icon)]`.
+pub struct HtmlTemplate {
+    pub root: HtmlRoot,
+}
+
+impl Parse for HtmlTemplate {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let mut self_closing = vec![];
+        while input.peek(Token![#]) && input.peek2(Token![!]) {
+            input.parse::<Token![#]>()?;
+            input.parse::<Token![!]>()?;
+            let content;
+            bracketed!(content in input);
+            let name: Ident = content.parse()?;
+            if name != "self_closing" {
+                return Err(Error::new(
+                    name.span(),
+                    "Only the custom self-closing tags can be declared like \
+                     `#![self_closing(my-icon)]`.",
+                ));
+            }
+            let tags;
+            parenthesized!(tags in content);
+            for tag in tags.call(Punctuated::<TagName, Token![,]>::parse_terminated)? {
+                match tag {
+                    TagName::Tag { name, .. } => self_closing.push(name),
+                    other => {
+                        return Err(Error::new(
+                            other.span(),
+                            "Only the elements can be declared self-closing.",
+                        ))
+                    }
+                }
+            }
+        }
+
+        let root = kw::with_custom_self_closing(self_closing, || input.parse())?;
+        Ok(HtmlTemplate { root })
+    }
+}
+
 pub struct HtmlRoot {
     pub items: Vec<HtmlItems>,
     pub flat_len: usize,
@@ -123,6 +172,38 @@ impl HtmlRoot {
     }
 }
 
+impl HtmlRoot {
+    /// Checks that the markup can be constructed only once, i.e. it has
+    /// nothing which is bound to a component.
+    pub fn check_static(&self) -> ParseResult<()> {
+        for items in self.items.iter() {
+            let items = match items {
+                HtmlItems::Keyed(ref items) | HtmlItems::Unkeyed(ref items) => items,
+            };
+            for item in items {
+                if let HtmlItem::Element(ref element) = item {
+                    element.check_static()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Expands the markup to be constructed once and cached in a thread-local.
+    pub fn expand_static(&self) -> TokenStream {
+        let expanded = self.expand();
+        quote! {
+            {
+                thread_local! {
+                    static MARKUP: std::rc::Rc<ruukh::vdom::vstatic::StaticMarkup> =
+                        std::rc::Rc::new(ruukh::vdom::vstatic::StaticMarkup::new(#expanded));
+                }
+                ruukh::vdom::VNode::from(MARKUP.with(ruukh::vdom::vstatic::VStatic::new))
+            }
+        }
+    }
+}
+
 pub enum HtmlItems {
     Keyed(Vec<HtmlItem>),
     Unkeyed(Vec<HtmlItem>),
@@ -167,7 +248,7 @@ impl Parse for HtmlItem {
         if lookahead1.peek(Token![<]) {
             Ok(HtmlItem::Element(Box::new(input.parse()?)))
         } else if lookahead1.peek(token::Brace) {
-            Ok(HtmlItem::ExpressionBlock(input.parse()?))
+            Ok(HtmlItem::ExpressionBlock(parse_expression_block(input)?))
         } else if lookahead1.peek(LitStr) {
             Ok(HtmlItem::Text(input.parse()?))
         } else {
@@ -216,6 +297,47 @@ impl HtmlItem {
     }
 }
 
+/// Parses the `{ .. }` block of a child, which may contain statements as
+/// well.
+fn parse_expression_block(input: ParseStream<'_>) -> ParseResult<RustExpressionBlock> {
+    let content;
+    let brace_token = braced!(content in input);
+    let stmts = content
+        .call(RustExpressionBlock::parse_within)
+        .map_err(|err| malformed_interpolation(brace_token.span, &err))?;
+    Ok(RustExpressionBlock { brace_token, stmts })
+}
+
+/// Parses the `{ EXPR }` value of an attribute.
+pub fn parse_braced_expr(input: ParseStream<'_>) -> ParseResult<(token::Brace, Expr)> {
+    let content;
+    let brace = braced!(content in input);
+    let value = content.parse().and_then(|value| {
+        if content.is_empty() {
+            Ok(value)
+        } else {
+            Err(content.error("unexpected tokens after the expression"))
+        }
+    });
+    match value {
+        Ok(value) => Ok((brace, value)),
+        Err(err) => Err(malformed_interpolation(brace.span, &err)),
+    }
+}
+
+/// The error on a malformed `{ .. }` interpolation, which points at its
+/// braces instead of somewhere within them.
+fn malformed_interpolation(brace: Span, err: &Error) -> Error {
+    Error::new(
+        brace,
+        format!(
+            "Malformed interpolation, expected a Rust expression within the braces like \
+             `{{ value }}`: {}.",
+            err
+        ),
+    )
+}
+
 pub struct Text {
     pub content: String,
 }
@@ -242,6 +364,32 @@ impl Parse for Text {
 mod test {
     use super::*;
 
+    #[test]
+    fn should_parse_declared_custom_self_closing_tags() {
+        let template: HtmlTemplate = syn::parse_str(
+            r#"
+            #![self_closing(my-icon, x-spacer)]
+            <div>
+                <my-icon name={"star"}>
+                <x-spacer>
+                "Starred"
+            </div>
+        "#,
+        )
+        .unwrap();
+        assert_eq!(template.root.flat_len, 1);
+
+        let undeclared: ParseResult<HtmlRoot> = syn::parse_str(r#"<div><my-icon></div>"#);
+        assert!(undeclared.is_err());
+    }
+
+    #[test]
+    fn should_not_parse_components_declared_self_closing() {
+        let template: ParseResult<HtmlTemplate> =
+            syn::parse_str(r#"#![self_closing(Icon)] <Icon></Icon>"#);
+        assert!(template.is_err());
+    }
+
     #[test]
     fn should_parse_html() {
         let _: HtmlRoot = syn::parse_str(
@@ -268,6 +416,32 @@ mod test {
         ).unwrap();
     }
 
+    #[test]
+    fn should_point_at_the_braces_of_malformed_interpolation() {
+        let err = syn::parse_str::<HtmlRoot>(r#"<div>{ let }</div>"#)
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .starts_with("Malformed interpolation, expected a Rust expression"));
+
+        let err = syn::parse_str::<HtmlRoot>(r#"<div class={ "big" "red" }></div>"#)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Malformed interpolation, expected a Rust expression within the braces like \
+             `{ value }`: unexpected tokens after the expression."
+        );
+
+        let err = syn::parse_str::<HtmlRoot>(r#"<div class={}></div>"#)
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .starts_with("Malformed interpolation, expected a Rust expression"));
+    }
+
     #[test]
     fn should_parse_text() {
         let text: Text =
@@ -1,15 +1,19 @@
 use super::kw;
-use super::HtmlRoot;
-use crate::suffix::{EVENT_SUFFIX, PROPS_SUFFIX};
+use super::{parse_braced_expr, HtmlRoot};
+use crate::{
+    diagnostic::Diagnostic,
+    suffix::{EVENT_SUFFIX, PROPS_SUFFIX},
+};
 use heck::{CamelCase, KebabCase, SnakeCase};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
     braced,
+    ext::IdentExt,
     parse::{Error, Parse, ParseStream, Result as ParseResult},
     punctuated::Punctuated,
     spanned::Spanned,
-    token, Token, {Expr, Ident},
+    token, Token, {Expr, Ident, Pat},
 };
 
 pub enum HtmlElement {
@@ -29,10 +33,13 @@ impl Parse for HtmlElement {
 
 impl HtmlElement {
     pub fn expand(&self) -> TokenStream {
-        match self {
-            HtmlElement::Normal(ref normal) => normal.expand(),
-            HtmlElement::SelfClosing(ref self_closing) => self_closing.expand(),
-        }
+        let (expanded, for_each) = match self {
+            HtmlElement::Normal(ref normal) => (normal.expand(), &normal.opening_tag.for_each),
+            HtmlElement::SelfClosing(ref self_closing) => {
+                (self_closing.expand(), &self_closing.tag.for_each)
+            }
+        };
+        ForAttribute::expand_on(for_each.as_ref(), expanded)
     }
 
     pub fn key(&self) -> Option<&KeyAttribute> {
@@ -41,6 +48,46 @@ impl HtmlElement {
             HtmlElement::SelfClosing(ref el) => el.key(),
         }
     }
+
+    /// Checks that the element along with its children has nothing which is
+    /// bound to a component, so that it can be constructed only once.
+    pub fn check_static(&self) -> ParseResult<()> {
+        let (tag_name, node_ref, event_attributes) = match self {
+            HtmlElement::Normal(ref el) => (
+                &el.opening_tag.tag_name,
+                &el.opening_tag.node_ref,
+                &el.opening_tag.event_attributes,
+            ),
+            HtmlElement::SelfClosing(ref el) => {
+                (&el.tag.tag_name, &el.tag.node_ref, &el.tag.event_attributes)
+            }
+        };
+        match tag_name {
+            TagName::Tag { .. } | TagName::Fragment { .. } => {}
+            _ => {
+                return Err(Error::new(
+                    tag_name.span(),
+                    "Components & portals are not allowed in html_static!.",
+                ))
+            }
+        }
+        if let Some(ref node_ref) = node_ref {
+            return Err(Error::new(
+                node_ref.ref_token.span,
+                "`ref` is not allowed in html_static!.",
+            ));
+        }
+        if let Some(attr) = event_attributes.first() {
+            return Err(Error::new(
+                attr.key.span,
+                "Event listeners are not allowed in html_static!.",
+            ));
+        }
+        match self {
+            HtmlElement::Normal(ref el) => el.child.check_static(),
+            HtmlElement::SelfClosing(_) => Ok(()),
+        }
+    }
 }
 
 pub struct NormalHtmlElement {
@@ -62,19 +109,38 @@ impl Parse for NormalHtmlElement {
             ));
         }
 
+        if opening_tag.inner_html.is_some() && child.flat_len != 0 {
+            return Err(Error::new(
+                closing_tag.tag_name.span(),
+                "An element with `inner-html` cannot have children.",
+            ));
+        }
+
         let not_same = match (&opening_tag.tag_name, &closing_tag.tag_name) {
             (TagName::Tag { name: ref op, .. }, TagName::Tag { name: ref cl, .. }) => op != cl,
-            (TagName::Component { ident: ref op }, TagName::Component { ident: ref cl }) => {
-                op != cl
-            }
+            (
+                TagName::Component {
+                    ident: ref op,
+                    memo: op_memo,
+                },
+                TagName::Component {
+                    ident: ref cl,
+                    memo: cl_memo,
+                },
+            ) => op != cl || op_memo != cl_memo,
+            (TagName::Portal { .. }, TagName::Portal { .. }) => false,
+            (TagName::Fragment { .. }, TagName::Fragment { .. }) => false,
             _ => true,
         };
 
         if not_same {
-            return Err(Error::new(
+            return Err(Diagnostic::error(
                 closing_tag.tag_name.span(),
                 "Opening and closing tag must be same.",
-            ));
+            )
+            .span_note(opening_tag.tag_name.span(), "opened here")
+            .span_note(closing_tag.tag_name.span(), "but closed here")
+            .into_error());
         }
 
         Ok(NormalHtmlElement {
@@ -122,6 +188,11 @@ pub struct OpeningTag {
     pub lt: Token![<],
     pub tag_name: TagName,
     pub key: Option<KeyAttribute>,
+    pub node_ref: Option<RefAttribute>,
+    pub for_each: Option<ForAttribute>,
+    /// The reserved `inner-html={..}` attribute which sets the raw HTML of an
+    /// element.
+    pub inner_html: Option<HtmlAttribute>,
     pub prop_attributes: Vec<HtmlAttribute>,
     pub event_attributes: Vec<HtmlAttribute>,
     pub gt: Token![>],
@@ -130,23 +201,126 @@ pub struct OpeningTag {
 impl Parse for OpeningTag {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
         let lt = input.parse()?;
-        let tag_name = input.parse()?;
+        let tag_name: TagName = input.parse()?;
         let mut key = None;
+        let mut node_ref = None;
+        let mut for_each = None;
 
         let mut attributes: Vec<HtmlAttribute> = vec![];
         while !input.peek(Token![>]) {
-            if input.peek(kw::key) {
-                key = Some(input.parse()?);
+            if kw::is_key(input) {
+                KeyAttribute::parse_once(input, &mut key)?;
+            } else if kw::is_ref(input) {
+                RefAttribute::parse_once(input, &mut node_ref)?;
+            } else if kw::is_for(input) {
+                ForAttribute::parse_once(input, &mut for_each)?;
             } else {
                 attributes.push(input.parse()?);
             }
         }
 
         let gt = input.parse()?;
+        ForAttribute::take_key(&mut for_each, &mut key);
 
         let (mut prop_attributes, mut event_attributes) = attributes
             .into_iter()
             .partition::<Vec<_>, _>(|attr| attr.at.is_none());
+        desugar_bind(&tag_name, &mut prop_attributes, &mut event_attributes)?;
+
+        if tag_name.is_component() {
+            if let Some(ref node_ref) = node_ref {
+                return Err(Error::new(
+                    node_ref.ref_token.span,
+                    "`ref` is only allowed on elements, not on components.",
+                ));
+            }
+            if let Some(attr) = prop_attributes.iter().find(|attr| attr.key.directive.is_some()) {
+                return Err(Error::new(
+                    attr.key.span,
+                    "Directives are only allowed on elements, not on components.",
+                ));
+            }
+            if let Some(attr) = prop_attributes.iter().find(|attr| attr.key.namespace.is_some()) {
+                return Err(Error::new(
+                    attr.key.span,
+                    "Namespaced attributes are only allowed on elements, not on components.",
+                ));
+            }
+            if let Some(attr) = event_attributes.iter().find(|attr| !attr.modifiers.is_empty()) {
+                return Err(Error::new(
+                    attr.modifiers[0].span(),
+                    "Event modifiers are only allowed on elements, not on components.",
+                ));
+            }
+        }
+
+        if let TagName::Portal { ref ident } = tag_name {
+            if let Some(ref node_ref) = node_ref {
+                return Err(Error::new(
+                    node_ref.ref_token.span,
+                    "`ref` is not allowed on a `Portal`.",
+                ));
+            }
+            if let Some(attr) = event_attributes.first() {
+                return Err(Error::new(
+                    attr.key.span,
+                    "Events are not allowed on a `Portal`.",
+                ));
+            }
+            if let Some(attr) = prop_attributes.iter().find(|attr| attr.key.name != "target") {
+                return Err(Error::new(
+                    attr.key.span,
+                    "Only the `target` attribute is allowed on a `Portal`.",
+                ));
+            }
+            match prop_attributes.len() {
+                0 => {
+                    return Err(Error::new(
+                        ident.span(),
+                        "A `Portal` requires a `target={..}` attribute.",
+                    ))
+                }
+                1 => {}
+                _ => {
+                    return Err(Error::new(
+                        prop_attributes[1].key.span,
+                        "Duplicate `target` attribute found.",
+                    ))
+                }
+            }
+        }
+
+        if let TagName::Fragment { .. } = tag_name {
+            if let Some(ref node_ref) = node_ref {
+                return Err(Error::new(
+                    node_ref.ref_token.span,
+                    "`ref` is not allowed on a `Fragment`.",
+                ));
+            }
+            if let Some(attr) = prop_attributes.iter().chain(event_attributes.iter()).next() {
+                return Err(Error::new(
+                    attr.key.span,
+                    "Only `ruukh:for` and `key` are allowed on a `Fragment`.",
+                ));
+            }
+        }
+
+        let mut inner_html = None;
+        if let TagName::Tag { .. } = tag_name {
+            while let Some(index) = prop_attributes
+                .iter()
+                .position(|attr| attr.key.is_inner_html())
+            {
+                let attr = prop_attributes.remove(index);
+                if inner_html.is_some() {
+                    return Err(Error::new(
+                        attr.key.span,
+                        "`inner-html` is already specified on this tag.",
+                    ));
+                }
+                inner_html = Some(attr);
+            }
+        }
 
         prop_attributes.sort_by(|l, r| l.key.name.cmp(&r.key.name));
         event_attributes.sort_by(|l, r| l.key.name.cmp(&r.key.name));
@@ -155,6 +329,9 @@ impl Parse for OpeningTag {
             lt,
             tag_name,
             key,
+            node_ref,
+            for_each,
+            inner_html,
             prop_attributes,
             event_attributes,
             gt,
@@ -166,27 +343,45 @@ impl OpeningTag {
     fn expand_with(&self, child: &TokenStream) -> TokenStream {
         match self.tag_name {
             TagName::Tag { ref name, .. } => {
-                let prop_attributes: Vec<_> = self
-                    .prop_attributes
-                    .iter()
-                    .map(|p| p.expand_as_prop_attribute().unwrap())
-                    .collect();
+                let prop_attributes = expand_element_prop_attributes(&self.prop_attributes);
                 let event_attributes: Vec<_> = self
                     .event_attributes
                     .iter()
                     .map(|e| e.expand_as_event_attribute().unwrap())
                     .collect();
 
-                quote! {
-                    ruukh::vdom::velement::VElement::new(
-                        #name,
-                        vec![#(#prop_attributes),*],
-                        vec![#(#event_attributes),*],
-                        #child
-                    )
-                }
+                let element = if self.tag_name.is_namespace_boundary() {
+                    quote! {
+                        ruukh::vdom::velement::VElement::namespaced(
+                            ruukh::vdom::velement::SVG_NAMESPACE,
+                            #name,
+                            vec![#(#prop_attributes),*],
+                            vec![#(#event_attributes),*],
+                            #child
+                        )
+                    }
+                } else {
+                    quote! {
+                        ruukh::vdom::velement::VElement::new(
+                            #name,
+                            vec![#(#prop_attributes),*],
+                            vec![#(#event_attributes),*],
+                            #child
+                        )
+                    }
+                };
+                let element = match self.inner_html {
+                    Some(ref inner_html) => {
+                        let value = &inner_html.value;
+                        quote! {
+                            #element.dangerous_inner_html(#value)
+                        }
+                    }
+                    None => element,
+                };
+                RefAttribute::expand_on(self.node_ref.as_ref(), element)
             }
-            TagName::Component { ref ident } => {
+            TagName::Component { ref ident, memo } => {
                 let prop_attributes: Vec<_> = self
                     .prop_attributes
                     .iter()
@@ -201,14 +396,26 @@ impl OpeningTag {
 
                 let props_ident = Ident::new(&format!("{}{}", ident, PROPS_SUFFIX), ident.span());
                 let event_ident = Ident::new(&format!("{}{}", ident, EVENT_SUFFIX), ident.span());
+                let constructor = if memo {
+                    Ident::new("memo", ident.span())
+                } else {
+                    Ident::new("new", ident.span())
+                };
                 let span = ident.span();
                 quote_spanned!{span=>
-                    ruukh::vdom::vcomponent::VComponent::new::<#ident>(
+                    ruukh::vdom::vcomponent::VComponent::#constructor::<#ident>(
                         #props_ident!(#(#prop_attributes),*),
                         #event_ident!(#(#event_attributes),*),
                     )
                 }
             }
+            TagName::Portal { .. } => {
+                let target = &self.prop_attributes[0].value;
+                quote! {
+                    ruukh::vdom::vportal::VPortal::new(#target, #child)
+                }
+            }
+            TagName::Fragment { .. } => child.clone(),
         }
     }
 }
@@ -235,6 +442,8 @@ pub struct SelfClosingTag {
     pub lt: Token![<],
     pub tag_name: TagName,
     pub key: Option<KeyAttribute>,
+    pub node_ref: Option<RefAttribute>,
+    pub for_each: Option<ForAttribute>,
     pub prop_attributes: Vec<HtmlAttribute>,
     pub event_attributes: Vec<HtmlAttribute>,
     pub slash: Option<Token![/]>,
@@ -246,11 +455,17 @@ impl Parse for SelfClosingTag {
         let lt = input.parse()?;
         let tag_name = input.parse()?;
         let mut key = None;
+        let mut node_ref = None;
+        let mut for_each = None;
 
         let mut attributes: Vec<HtmlAttribute> = vec![];
         while !input.peek(Token![/]) && !input.peek(Token![>]) {
-            if input.peek(kw::key) {
-                key = Some(input.parse()?);
+            if kw::is_key(input) {
+                KeyAttribute::parse_once(input, &mut key)?;
+            } else if kw::is_ref(input) {
+                RefAttribute::parse_once(input, &mut node_ref)?;
+            } else if kw::is_for(input) {
+                ForAttribute::parse_once(input, &mut for_each)?;
             } else {
                 attributes.push(input.parse()?);
             }
@@ -258,14 +473,19 @@ impl Parse for SelfClosingTag {
 
         let slash = input.parse()?;
         let gt = input.parse()?;
+        ForAttribute::take_key(&mut for_each, &mut key);
 
-        let (prop_attributes, event_attributes) =
-            attributes.into_iter().partition(|attr| attr.at.is_none());
+        let (mut prop_attributes, mut event_attributes) = attributes
+            .into_iter()
+            .partition::<Vec<_>, _>(|attr| attr.at.is_none());
+        desugar_bind(&tag_name, &mut prop_attributes, &mut event_attributes)?;
 
         Ok(SelfClosingTag {
             lt,
             tag_name,
             key,
+            node_ref,
+            for_each,
             prop_attributes,
             event_attributes,
             slash,
@@ -278,24 +498,33 @@ impl SelfClosingTag {
     fn expand(&self) -> TokenStream {
         match self.tag_name {
             TagName::Tag { ref name, .. } => {
-                let prop_attributes: Vec<_> = self
-                    .prop_attributes
-                    .iter()
-                    .map(|p| p.expand_as_prop_attribute().unwrap())
-                    .collect();
+                let prop_attributes = expand_element_prop_attributes(&self.prop_attributes);
                 let event_attributes: Vec<_> = self
                     .event_attributes
                     .iter()
                     .map(|e| e.expand_as_event_attribute().unwrap())
                     .collect();
 
-                quote! {
-                    ruukh::vdom::velement::VElement::childless(
-                        #name,
-                        vec![#(#prop_attributes),*],
-                        vec![#(#event_attributes),*]
-                    )
-                }
+                let element = if self.tag_name.is_namespace_boundary() {
+                    quote! {
+                        ruukh::vdom::velement::VElement::namespaced(
+                            ruukh::vdom::velement::SVG_NAMESPACE,
+                            #name,
+                            vec![#(#prop_attributes),*],
+                            vec![#(#event_attributes),*],
+                            ruukh::vdom::VNode::None
+                        )
+                    }
+                } else {
+                    quote! {
+                        ruukh::vdom::velement::VElement::childless(
+                            #name,
+                            vec![#(#prop_attributes),*],
+                            vec![#(#event_attributes),*]
+                        )
+                    }
+                };
+                RefAttribute::expand_on(self.node_ref.as_ref(), element)
             }
             _ => unreachable!("The spec specified self-closing tags are the only ones allowed."),
         }
@@ -313,9 +542,7 @@ impl Parse for KeyAttribute {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
         let key = input.parse()?;
         let eq = input.parse()?;
-        let content;
-        let brace = braced!(content in input);
-        let value = content.parse()?;
+        let (brace, value) = parse_braced_expr(input)?;
         Ok(KeyAttribute {
             key,
             eq,
@@ -326,6 +553,20 @@ impl Parse for KeyAttribute {
 }
 
 impl KeyAttribute {
+    /// Parses the key attribute into `key` unless it was already parsed
+    /// before on the same tag.
+    fn parse_once(input: ParseStream<'_>, key: &mut Option<KeyAttribute>) -> ParseResult<()> {
+        let parsed: KeyAttribute = input.parse()?;
+        if key.is_some() {
+            return Err(Error::new(
+                parsed.key.span,
+                "`key` is already specified on this tag.",
+            ));
+        }
+        *key = Some(parsed);
+        Ok(())
+    }
+
     pub fn expand(&self) -> TokenStream {
         let value = &self.value;
         quote! {
@@ -334,9 +575,177 @@ impl KeyAttribute {
     }
 }
 
+pub struct RefAttribute {
+    pub ref_token: Token![ref],
+    pub eq: Token![=],
+    pub brace: token::Brace,
+    pub value: Expr,
+}
+
+impl Parse for RefAttribute {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let ref_token = input.parse()?;
+        let eq = input.parse()?;
+        let (brace, value) = parse_braced_expr(input)?;
+        Ok(RefAttribute {
+            ref_token,
+            eq,
+            brace,
+            value,
+        })
+    }
+}
+
+impl RefAttribute {
+    /// Parses the ref attribute into `node_ref` unless it was already parsed
+    /// before on the same tag.
+    fn parse_once(input: ParseStream<'_>, node_ref: &mut Option<RefAttribute>) -> ParseResult<()> {
+        let parsed: RefAttribute = input.parse()?;
+        if node_ref.is_some() {
+            return Err(Error::new(
+                parsed.ref_token.span,
+                "`ref` is already specified on this tag.",
+            ));
+        }
+        *node_ref = Some(parsed);
+        Ok(())
+    }
+
+    /// Sets the node ref, if any, on the expanded element.
+    fn expand_on(node_ref: Option<&RefAttribute>, element: TokenStream) -> TokenStream {
+        match node_ref {
+            Some(node_ref) => {
+                let value = &node_ref.value;
+                quote! {
+                    #element.with_node_ref(#value)
+                }
+            }
+            None => element,
+        }
+    }
+}
+
+/// The reserved `ruukh:for={item in items}` attribute which repeats the
+/// element for every item of the iterator.
+pub struct ForAttribute {
+    pub ruukh: kw::ruukh,
+    pub colon: Token![:],
+    pub for_token: Token![for],
+    pub eq: Token![=],
+    pub brace: token::Brace,
+    pub pat: Pat,
+    pub in_token: Token![in],
+    pub iter: Expr,
+    /// The `key={..}` of the tag, which keys every item instead of the whole
+    /// list.
+    pub key: Option<KeyAttribute>,
+}
+
+impl Parse for ForAttribute {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let ruukh = input.parse()?;
+        let colon = input.parse()?;
+        let for_token = input.parse()?;
+        let eq = input.parse()?;
+        let content;
+        let brace = braced!(content in input);
+        let parsed = content.parse::<Pat>().and_then(|pat| {
+            let in_token = content.parse()?;
+            let iter = content.parse()?;
+            if content.is_empty() {
+                Ok((pat, in_token, iter))
+            } else {
+                Err(content.error("unexpected tokens after the iterator"))
+            }
+        });
+        let (pat, in_token, iter) = parsed.map_err(|err| {
+            Error::new(
+                brace.span,
+                format!(
+                    "Malformed `ruukh:for`, expected a pattern and an iterator within the \
+                     braces like `{{ item in items }}`: {}.",
+                    err
+                ),
+            )
+        })?;
+        Ok(ForAttribute {
+            ruukh,
+            colon,
+            for_token,
+            eq,
+            brace,
+            pat,
+            in_token,
+            iter,
+            key: None,
+        })
+    }
+}
+
+impl ForAttribute {
+    /// Parses the for attribute into `for_each` unless it was already parsed
+    /// before on the same tag.
+    fn parse_once(input: ParseStream<'_>, for_each: &mut Option<ForAttribute>) -> ParseResult<()> {
+        let parsed: ForAttribute = input.parse()?;
+        if for_each.is_some() {
+            return Err(Error::new(
+                parsed.ruukh.span,
+                "`ruukh:for` is already specified on this tag.",
+            ));
+        }
+        *for_each = Some(parsed);
+        Ok(())
+    }
+
+    /// Moves the key of the tag onto the for attribute, if any, as it keys
+    /// the items of the list.
+    fn take_key(for_each: &mut Option<ForAttribute>, key: &mut Option<KeyAttribute>) {
+        if let Some(ref mut for_each) = for_each {
+            for_each.key = key.take();
+        }
+    }
+
+    /// Repeats the expanded element for every item, in a keyed list if the
+    /// items are keyed so that they are reordered along with their state.
+    fn expand_on(for_each: Option<&ForAttribute>, element: TokenStream) -> TokenStream {
+        let for_each = match for_each {
+            Some(for_each) => for_each,
+            None => return element,
+        };
+        let pat = &for_each.pat;
+        let iter = &for_each.iter;
+        match for_each.key {
+            Some(ref key) => {
+                let key_expanded = key.expand();
+                quote! {
+                    ruukh::vdom::VNode::from(ruukh::vdom::vlist::VList::from(
+                        std::iter::IntoIterator::into_iter(#iter)
+                            .map(|#pat| (#key_expanded, ruukh::vdom::VNode::from(#element)))
+                            .collect::<ruukh::reexports::IndexMap<
+                                _,
+                                _,
+                                ruukh::reexports::FnvBuildHasher
+                            >>()
+                    ))
+                }
+            }
+            None => quote! {
+                ruukh::vdom::VNode::from(ruukh::vdom::vlist::VList::from(
+                    std::iter::IntoIterator::into_iter(#iter)
+                        .map(|#pat| ruukh::vdom::VNode::from(#element))
+                        .collect::<Vec<_>>()
+                ))
+            },
+        }
+    }
+}
+
 pub struct HtmlAttribute {
     pub at: Option<Token![@]>,
     pub key: AttributeName,
+    /// The modifiers of an event listener like `prevent` in
+    /// `@click.prevent={..}`.
+    pub modifiers: Vec<Ident>,
     pub eq: Token![=],
     pub brace: token::Brace,
     pub value: Expr,
@@ -344,15 +753,48 @@ pub struct HtmlAttribute {
 
 impl Parse for HtmlAttribute {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        let at = input.parse()?;
-        let key = input.parse()?;
+        let at: Option<Token![@]> = input.parse()?;
+        let key: AttributeName = input.parse()?;
+        if at.is_some() && key.directive.is_some() {
+            return Err(Error::new(
+                key.span,
+                "Directives cannot be used as event listeners.",
+            ));
+        }
+        if at.is_some() && key.namespace.is_some() {
+            return Err(Error::new(
+                key.span,
+                "Namespaced attributes cannot be used as event listeners.",
+            ));
+        }
+        let mut modifiers: Vec<Ident> = vec![];
+        while at.is_some() && input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            // `async` is a keyword, so parse it as any ident.
+            let modifier = input.call(Ident::parse_any)?;
+            if !EVENT_MODIFIERS.contains(&modifier.to_string().as_str()) {
+                return Err(Error::new(
+                    modifier.span(),
+                    &format!(
+                        "Unknown event modifier, only {} are allowed.",
+                        EVENT_MODIFIERS.join(", ")
+                    ),
+                ));
+            }
+            if modifiers.contains(&modifier) {
+                return Err(Error::new(
+                    modifier.span(),
+                    "The event modifier is already specified.",
+                ));
+            }
+            modifiers.push(modifier);
+        }
         let eq = input.parse()?;
-        let content;
-        let brace = braced!(content in input);
-        let value = content.parse()?;
+        let (brace, value) = parse_braced_expr(input)?;
         Ok(HtmlAttribute {
             at,
             key,
+            modifiers,
             eq,
             brace,
             value,
@@ -368,6 +810,19 @@ impl HtmlAttribute {
         let key = &self.key.name;
         let value = &self.value;
 
+        if let Some(ref namespace) = self.key.namespace {
+            let namespace = Ident::new(
+                &format!("{}_NAMESPACE", namespace.to_uppercase()),
+                Span::call_site(),
+            );
+            return Some(quote! {
+                ruukh::vdom::velement::Attribute::namespaced(
+                    ruukh::vdom::velement::#namespace,
+                    #key,
+                    #value
+                )
+            });
+        }
         Some(quote! {
             ruukh::vdom::velement::Attribute::new(#key, #value)
         })
@@ -375,11 +830,41 @@ impl HtmlAttribute {
 
     fn expand_as_event_attribute(&self) -> Option<TokenStream> {
         self.at?;
-        let key = &self.key.name;
+        let key = event_name(&self.key.name);
         let value = &self.value;
+        let is_async = self.modifiers.iter().any(|modifier| modifier == "async");
+        let modifiers = self
+            .modifiers
+            .iter()
+            .filter(|modifier| *modifier != "async")
+            .map(|modifier| {
+                let method = match modifier.to_string().as_str() {
+                    "prevent" => Ident::new("prevent_default", modifier.span()),
+                    "stop" => Ident::new("stop_propagation", modifier.span()),
+                    _ => unreachable!("The modifiers are validated while parsing."),
+                };
+                quote!(.#method())
+            });
+
+        let event_type = Ident::new(event_type(key), Span::call_site());
+        let listener = if is_async {
+            quote! {
+                ruukh::vdom::velement::EventListener::new_async::<ruukh::web_api::#event_type, _>(
+                    #key,
+                    Box::new(#value)
+                )
+            }
+        } else {
+            quote! {
+                ruukh::vdom::velement::EventListener::new::<ruukh::web_api::#event_type>(
+                    #key,
+                    Box::new(#value)
+                )
+            }
+        };
 
         Some(quote! {
-            ruukh::vdom::velement::EventListener::new(#key, Box::new(#value))
+            #listener#(#modifiers)*
         })
     }
 
@@ -393,9 +878,160 @@ impl HtmlAttribute {
     }
 }
 
+/// Replaces the `@bind={self.field}` shorthand of a form control with a
+/// `value={..}` attribute and a listener which sets the state field to the
+/// value entered, i.e. `@input` on an `<input>` or a `<textarea>` and
+/// `@change` on a `<select>`.
+fn desugar_bind(
+    tag_name: &TagName,
+    prop_attributes: &mut Vec<HtmlAttribute>,
+    event_attributes: &mut Vec<HtmlAttribute>,
+) -> ParseResult<()> {
+    let index = match event_attributes.iter().position(|attr| attr.key.name == "bind") {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+    let bind = event_attributes.remove(index);
+    if let Some(duplicate) = event_attributes.iter().find(|attr| attr.key.name == "bind") {
+        return Err(Error::new(
+            duplicate.key.span,
+            "`@bind` is already specified on this tag.",
+        ));
+    }
+    if let Some(modifier) = bind.modifiers.first() {
+        return Err(Error::new(
+            modifier.span(),
+            "Event modifiers are not allowed on `@bind`.",
+        ));
+    }
+    let event = match tag_name {
+        TagName::Tag { ref name, .. } if name == "input" || name == "textarea" => "input",
+        TagName::Tag { ref name, .. } if name == "select" => "change",
+        _ => {
+            return Err(Error::new(
+                bind.key.span,
+                "`@bind` is only allowed on `<input>`, `<textarea>` and `<select>`.",
+            ))
+        }
+    };
+    let is_self = |base: &Expr| quote!(#base).to_string() == "self";
+    let field = match bind.value {
+        Expr::Field(ref field) if is_self(&field.base) => &field.member,
+        _ => {
+            return Err(Error::new(
+                bind.value.span(),
+                "`@bind` expects a state field of the component like `@bind={self.text}`.",
+            ))
+        }
+    };
+    if let Some(attr) = prop_attributes.iter().find(|attr| attr.key.name == "value") {
+        return Err(Error::new(
+            attr.key.span,
+            "The `value` is already bound by `@bind` on this tag.",
+        ));
+    }
+    if let Some(attr) = event_attributes.iter().find(|attr| attr.key.name == event) {
+        return Err(Error::new(
+            attr.key.span,
+            format!("The `@{}` is already bound by `@bind` on this tag.", event),
+        ));
+    }
+
+    let bound = &bind.value;
+    let event_type = Ident::new(event_type(event), Span::call_site());
+    prop_attributes.push(HtmlAttribute {
+        at: None,
+        key: AttributeName {
+            name: "value".to_string(),
+            directive: None,
+            namespace: None,
+            span: bind.key.span,
+        },
+        modifiers: vec![],
+        eq: bind.eq,
+        brace: bind.brace,
+        value: syn::parse_quote!(std::clone::Clone::clone(&#bound)),
+    });
+    // `set_state` messages the app only when the field actually changed.
+    event_attributes.push(HtmlAttribute {
+        at: bind.at,
+        key: AttributeName {
+            name: event.to_string(),
+            directive: None,
+            namespace: None,
+            span: bind.key.span,
+        },
+        modifiers: vec![],
+        eq: bind.eq,
+        brace: bind.brace,
+        value: syn::parse_quote! {
+            |__this: &Self, __event: ruukh::web_api::#event_type| {
+                if let Some(__value) = ruukh::web_api::EventExt::target_value(&__event) {
+                    ruukh::component::SetState::set_state(__this, |__state| {
+                        __state.#field = __value.clone();
+                    });
+                }
+            }
+        },
+    });
+    Ok(())
+}
+
+/// Expands the prop attributes of an element. The directives like
+/// `class:name` or `style:property` are folded into their own attribute so
+/// that all of them are diffed together.
+fn expand_element_prop_attributes(attributes: &[HtmlAttribute]) -> Vec<TokenStream> {
+    let (directives, rest): (Vec<_>, Vec<_>) = attributes
+        .iter()
+        .partition(|attr| attr.key.directive.is_some());
+
+    let mut expanded = vec![];
+    let mut folded = vec![];
+    for directive in DIRECTIVES {
+        let applied: Vec<_> = directives
+            .iter()
+            .filter(|attr| attr.key.is_directive(directive))
+            .map(|attr| {
+                let method = Ident::new(&format!("with_{}", directive), Span::call_site());
+                let name = &attr.key.name;
+                let value = &attr.value;
+                quote!(.#method(#name, #value))
+            })
+            .collect();
+        if applied.is_empty() {
+            continue;
+        }
+
+        let base = match rest.iter().find(|attr| attr.key.name == *directive) {
+            Some(attr) => {
+                let value = &attr.value;
+                quote!(ruukh::vdom::velement::AttributeValue::from(#value))
+            }
+            None => quote!(ruukh::vdom::velement::AttributeValue::None),
+        };
+        expanded.push(quote! {
+            ruukh::vdom::velement::Attribute::new(#directive, #base #(#applied)*)
+        });
+        folded.push(*directive);
+    }
+
+    for attr in rest {
+        if !folded.contains(&attr.key.name.as_str()) {
+            expanded.push(attr.expand_as_prop_attribute().unwrap());
+        }
+    }
+    expanded
+}
+
 pub enum TagName {
     Tag { name: String, span: Span },
-    Component { ident: Ident },
+    /// A component, which is memoized when written as `Memo<Component>`.
+    Component { ident: Ident, memo: bool },
+    /// The built-in `Portal` pseudo-component.
+    Portal { ident: Ident },
+    /// The built-in `Fragment` pseudo-component, which groups its children
+    /// without a wrapper element.
+    Fragment { ident: Ident },
 }
 
 impl TagName {
@@ -406,10 +1042,21 @@ impl TagName {
         }
     }
 
-    fn span(&self) -> Span {
+    /// Whether the tag starts a new namespace, i.e. the `<svg>` whose
+    /// descendants are in the SVG namespace.
+    fn is_namespace_boundary(&self) -> bool {
+        match self {
+            TagName::Tag { ref name, .. } => name == "svg",
+            _ => false,
+        }
+    }
+
+    pub fn span(&self) -> Span {
         match self {
             TagName::Tag { ref span, .. } => span.clone(),
-            TagName::Component { ref ident } => ident.span(),
+            TagName::Component { ref ident, .. }
+            | TagName::Portal { ref ident }
+            | TagName::Fragment { ref ident } => ident.span(),
         }
     }
 }
@@ -425,8 +1072,31 @@ impl Parse for TagName {
             if idents.len() != 1 {
                 return Err(Error::new(span, "no dashes in a component tag allowed."));
             }
+            if ident == "Memo" {
+                input.parse::<Token![<]>()?;
+                let ident: Ident = input.parse()?;
+                if ident != ident.to_string().to_camel_case() {
+                    return Err(Error::new(
+                        ident.span(),
+                        "Only components can be memoized like `Memo<MyComponent>`.",
+                    ));
+                }
+                input.parse::<Token![>]>()?;
+                return Ok(TagName::Component { ident, memo: true });
+            }
+            if ident == "Portal" {
+                return Ok(TagName::Portal {
+                    ident: idents.swap_remove(0),
+                });
+            }
+            if ident == "Fragment" {
+                return Ok(TagName::Fragment {
+                    ident: idents.swap_remove(0),
+                });
+            }
             return Ok(TagName::Component {
                 ident: idents.swap_remove(0),
+                memo: false,
             });
         }
 
@@ -438,10 +1108,12 @@ impl Parse for TagName {
 
         let kebab_tag_name = tag_name.to_kebab_case();
         if tag_name != kebab_tag_name {
-            return Err(Error::new(
+            return Err(Diagnostic::error(
                 span,
-                &format!("tag name in kebab case only like {}.", kebab_tag_name),
-            ));
+                format!("tag name in kebab case only like {}.", kebab_tag_name),
+            )
+            .span_help(span, format!("write it as `{}`", kebab_tag_name))
+            .into_error());
         }
 
         Ok(TagName::Tag {
@@ -451,8 +1123,68 @@ impl Parse for TagName {
     }
 }
 
+/// The attribute names which are allowed to be used as a directive like
+/// `class:name` or `style:property`.
+const DIRECTIVES: &[&str] = &["class", "style"];
+
+/// The name of the event the browsers dispatch for the listener name, which
+/// is the same but for the aliases like `doubleclick` for `dblclick`.
+fn event_name(name: &str) -> &str {
+    match name {
+        "doubleclick" => "dblclick",
+        _ => name,
+    }
+}
+
+/// The type of the event the browsers dispatch for the event name, from the
+/// ones in `ruukh::web_api`.
+fn event_type(name: &str) -> &'static str {
+    match event_name(name) {
+        "click" | "dblclick" | "auxclick" | "contextmenu" | "mousedown" | "mouseup"
+        | "mousemove" | "mouseover" | "mouseout" | "mouseenter" | "mouseleave" => "MouseEvent",
+        "keydown" | "keyup" | "keypress" => "KeyboardEvent",
+        "input" | "beforeinput" => "InputEvent",
+        "focus" | "blur" | "focusin" | "focusout" => "FocusEvent",
+        "wheel" => "WheelEvent",
+        "drag" | "dragstart" | "dragend" | "dragenter" | "dragover" | "dragleave" | "drop" => {
+            "DragEvent"
+        }
+        "pointerdown" | "pointermove" | "pointerup" | "pointercancel" | "pointerover"
+        | "pointerout" | "pointerenter" | "pointerleave" | "gotpointercapture"
+        | "lostpointercapture" => "PointerEvent",
+        "touchstart" | "touchmove" | "touchend" | "touchcancel" => "TouchEvent",
+        "submit" => "SubmitEvent",
+        "scroll" => "ScrollEvent",
+        _ => "Event",
+    }
+}
+
+/// The modifiers allowed on an event listener like `@click.prevent`.
+const EVENT_MODIFIERS: &[&str] = &["prevent", "stop", "async"];
+
+/// The prefixes of the namespaced attributes like `xlink:href`.
+const NAMESPACES: &[&str] = &["xlink", "xml"];
+
 pub struct AttributeName {
     name: String,
+    /// The directive of the attribute, if any. i.e. `class` in `class:active`,
+    /// where the name is `active`.
+    directive: Option<String>,
+    /// The namespace prefix of the attribute, if any. i.e. `xlink` in
+    /// `xlink:href`, where the name is `xlink:href` itself.
+    namespace: Option<String>,
+    span: Span,
+}
+
+impl AttributeName {
+    fn is_directive(&self, directive: &str) -> bool {
+        self.directive.as_ref().map_or(false, |d| d == directive)
+    }
+
+    /// Whether it is the reserved `inner-html` attribute.
+    fn is_inner_html(&self) -> bool {
+        self.name == "inner-html" && self.directive.is_none() && self.namespace.is_none()
+    }
 }
 
 impl Parse for AttributeName {
@@ -467,13 +1199,64 @@ impl Parse for AttributeName {
 
         let kebab_name = name.to_kebab_case();
         if name != kebab_name {
-            return Err(Error::new(
+            return Err(Diagnostic::error(
                 span,
-                &format!("attribute name in kebab case only like {}.", kebab_name),
-            ));
+                format!("attribute name in kebab case only like {}.", kebab_name),
+            )
+            .span_help(span, format!("write it as `{}`", kebab_name))
+            .into_error());
+        }
+
+        if input.peek(Token![:]) && !input.peek(Token![::]) && NAMESPACES.contains(&name.as_str())
+        {
+            input.parse::<Token![:]>()?;
+            let local: Ident = input.parse()?;
+            return Ok(AttributeName {
+                name: format!("{}:{}", name, local),
+                directive: None,
+                namespace: Some(name),
+                span,
+            });
         }
 
-        Ok(AttributeName { name })
+        if input.peek(Token![:]) && !input.peek(Token![::]) {
+            if !DIRECTIVES.contains(&name.as_str()) {
+                return Err(Error::new(
+                    span,
+                    &format!("`{}` is not a directive.", name),
+                ));
+            }
+            input.parse::<Token![:]>()?;
+            // CSS custom properties are prefixed with `--`.
+            let prefix = if name == "style" && input.peek(Token![-]) && input.peek2(Token![-]) {
+                input.parse::<Token![-]>()?;
+                input.parse::<Token![-]>()?;
+                "--"
+            } else {
+                ""
+            };
+            let idents = input.call(Punctuated::<Ident, Token![-]>::parse_separated_nonempty)?;
+            let arg = prefix.to_string()
+                + &idents
+                    .into_iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join("-");
+
+            return Ok(AttributeName {
+                name: arg,
+                directive: Some(name),
+                namespace: None,
+                span,
+            });
+        }
+
+        Ok(AttributeName {
+            name,
+            directive: None,
+            namespace: None,
+            span,
+        })
     }
 }
 
@@ -527,24 +1310,327 @@ mod test {
         assert!(attr.at.is_none());
     }
 
+    #[test]
+    fn should_parse_attribute_with_if_and_match_values() {
+        let attr: HtmlAttribute =
+            syn::parse_str(r#"aria-expanded={if open { "true" } else { "false" }}"#).unwrap();
+        assert!(match attr.value {
+            Expr::If(_) => true,
+            _ => false,
+        });
+
+        let attr: HtmlAttribute = syn::parse_str(
+            r#"class={match size { Size::Small => "sm", Size::Large => "lg" }}"#,
+        )
+        .unwrap();
+        assert!(match attr.value {
+            Expr::Match(_) => true,
+            _ => false,
+        });
+    }
+
     #[test]
     fn should_parse_event_attribute() {
         let attr: HtmlAttribute = syn::parse_str(r#"@input={fn_name}"#).unwrap();
         assert!(attr.at.is_some());
     }
 
+    #[test]
+    fn should_parse_event_attribute_with_modifiers() {
+        let attr: HtmlAttribute = syn::parse_str(r#"@click.prevent.stop={fn_name}"#).unwrap();
+        assert_eq!(attr.modifiers.len(), 2);
+    }
+
+    #[test]
+    fn should_parse_async_event_modifier() {
+        let attr: HtmlAttribute = syn::parse_str(r#"@click.async={Self::save}"#).unwrap();
+        assert_eq!(attr.modifiers[0], "async");
+    }
+
+    #[test]
+    fn should_not_parse_unknown_event_modifier() {
+        let attr: ParseResult<HtmlAttribute> = syn::parse_str(r#"@click.once={fn_name}"#);
+        assert!(attr.is_err());
+    }
+
+    #[test]
+    fn should_not_parse_event_modifiers_on_component() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r#"<Button @click.stop={fn_name}>"#);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_infer_event_type_from_name() {
+        assert_eq!(event_type("click"), "MouseEvent");
+        assert_eq!(event_type("keydown"), "KeyboardEvent");
+        assert_eq!(event_type("scroll"), "ScrollEvent");
+        assert_eq!(event_type("drop"), "DragEvent");
+        assert_eq!(event_type("pointerdown"), "PointerEvent");
+        assert_eq!(event_type("touchmove"), "TouchEvent");
+        assert_eq!(event_type("load"), "Event");
+        assert_eq!(event_type("doubleclick"), "MouseEvent");
+        assert_eq!(event_name("doubleclick"), "dblclick");
+    }
+
+    #[test]
+    fn should_parse_key_attribute() {
+        let tag: OpeningTag = syn::parse_str(r#"<div key={1} class={"active"}>"#).unwrap();
+        assert!(tag.key.is_some());
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
+    #[test]
+    fn should_parse_key_attribute_on_self_closing_tag() {
+        let tag: SelfClosingTag = syn::parse_str(r#"<input key={"name"}>"#).unwrap();
+        assert!(tag.key.is_some());
+    }
+
+    #[test]
+    fn should_parse_for_attribute_along_with_its_key() {
+        let tag: OpeningTag =
+            syn::parse_str(r#"<Counter ruukh:for={id in ids.iter()} key={*id} id={*id}>"#).unwrap();
+        let for_each = tag.for_each.unwrap();
+        assert!(for_each.key.is_some());
+        assert!(tag.key.is_none());
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
+    #[test]
+    fn should_not_parse_malformed_for_attribute() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str("<li ruukh:for={items}>");
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_parse_dashed_attribute_starting_with_key_as_normal() {
+        let tag: OpeningTag = syn::parse_str(r#"<div key-path={"a.b"}>"#).unwrap();
+        assert!(tag.key.is_none());
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
+    #[test]
+    fn should_not_parse_duplicate_key_attributes() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str("<div key={1} key={2}>");
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_parse_class_directive() {
+        let attr: HtmlAttribute = syn::parse_str(r#"class:is-active={true}"#).unwrap();
+        assert!(attr.key.is_directive("class"));
+        assert_eq!(attr.key.name, "is-active");
+    }
+
+    #[test]
+    fn should_parse_class_directives_along_with_class() {
+        let tag: OpeningTag =
+            syn::parse_str(r#"<div class={"btn"} class:active={true} class:hidden={false}>"#)
+                .unwrap();
+        assert_eq!(tag.prop_attributes.len(), 3);
+    }
+
+    #[test]
+    fn should_parse_style_directive() {
+        let attr: HtmlAttribute = syn::parse_str(r#"style:background-color={"red"}"#).unwrap();
+        assert!(attr.key.is_directive("style"));
+        assert_eq!(attr.key.name, "background-color");
+    }
+
+    #[test]
+    fn should_parse_style_directive_with_custom_property() {
+        let attr: HtmlAttribute = syn::parse_str(r#"style:--main-color={"red"}"#).unwrap();
+        assert!(attr.key.is_directive("style"));
+        assert_eq!(attr.key.name, "--main-color");
+    }
+
+    #[test]
+    fn should_not_parse_unknown_directive() {
+        let attr: ParseResult<HtmlAttribute> = syn::parse_str(r#"id:main={true}"#);
+        assert!(attr.is_err());
+    }
+
+    #[test]
+    fn should_parse_namespaced_attribute() {
+        let attr: HtmlAttribute = syn::parse_str(r##"xlink:href={"#icon"}"##).unwrap();
+        assert_eq!(attr.key.name, "xlink:href");
+        assert_eq!(attr.key.namespace, Some("xlink".to_string()));
+        assert!(attr.key.directive.is_none());
+    }
+
+    #[test]
+    fn should_not_parse_namespaced_attribute_on_component() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r##"<Icon xlink:href={"#icon"}>"##);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_treat_svg_as_namespace_boundary() {
+        let svg: TagName = syn::parse_str("svg").unwrap();
+        let path: TagName = syn::parse_str("path").unwrap();
+        assert!(svg.is_namespace_boundary());
+        assert!(!path.is_namespace_boundary());
+    }
+
+    #[test]
+    fn should_not_parse_class_directive_on_component() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r#"<Button class:active={true}>"#);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_parse_ref_attribute() {
+        let tag: SelfClosingTag = syn::parse_str(r#"<input ref={input_ref} name={"a"}>"#).unwrap();
+        assert!(tag.node_ref.is_some());
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
+    #[test]
+    fn should_not_parse_ref_attribute_on_component() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r#"<Button ref={button_ref}>"#);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_parse_inner_html_attribute() {
+        let tag: OpeningTag = syn::parse_str(r#"<div inner-html={markdown} class={"md"}>"#).unwrap();
+        assert!(tag.inner_html.is_some());
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
+    #[test]
+    fn should_not_parse_inner_html_along_with_children() {
+        let el: ParseResult<NormalHtmlElement> =
+            syn::parse_str(r#"<div inner-html={markdown}>"Hello"</div>"#);
+        assert!(el.is_err());
+    }
+
+    #[test]
+    fn should_check_static_element() {
+        let el: HtmlElement = syn::parse_str(r#"<footer class={"dim"}>"Ruukh"</footer>"#).unwrap();
+        assert!(el.check_static().is_ok());
+    }
+
+    #[test]
+    fn should_not_check_static_element_with_event_listener() {
+        let el: HtmlElement =
+            syn::parse_str(r#"<footer><a @click={fn_name}>"Top"</a></footer>"#).unwrap();
+        assert!(el.check_static().is_err());
+    }
+
+    #[test]
+    fn should_parse_portal() {
+        let el: NormalHtmlElement =
+            syn::parse_str(r#"<Portal target={el}><span>"Tooltip"</span></Portal>"#).unwrap();
+        assert!(match el.opening_tag.tag_name {
+            TagName::Portal { .. } => true,
+            _ => false,
+        });
+        assert_eq!(el.child.flat_len, 1);
+    }
+
+    #[test]
+    fn should_not_parse_portal_without_target() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r#"<Portal>"#);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_not_parse_portal_with_other_attributes() {
+        let tag: ParseResult<OpeningTag> =
+            syn::parse_str(r#"<Portal target={el} class={"modal"}>"#);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_parse_fragment_repeated_by_for() {
+        let el: HtmlElement = syn::parse_str(
+            r#"<Fragment ruukh:for={term in terms} key={term.id}>
+                <dt>{ term.name }</dt>
+                <dd>{ term.definition }</dd>
+            </Fragment>"#,
+        )
+        .unwrap();
+        let el = match el {
+            HtmlElement::Normal(el) => el,
+            _ => panic!("Expected a normal element."),
+        };
+        assert!(match el.opening_tag.tag_name {
+            TagName::Fragment { .. } => true,
+            _ => false,
+        });
+        assert!(el.opening_tag.for_each.unwrap().key.is_some());
+        assert_eq!(el.child.flat_len, 2);
+    }
+
+    #[test]
+    fn should_not_parse_fragment_with_attributes() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r#"<Fragment class={"terms"}>"#);
+        assert!(tag.is_err());
+    }
+
+    #[test]
+    fn should_desugar_bind_into_value_and_listener() {
+        let tag: SelfClosingTag = syn::parse_str(r#"<input @bind={self.text}/>"#).unwrap();
+        assert_eq!(tag.prop_attributes.len(), 1);
+        assert_eq!(tag.prop_attributes[0].key.name, "value");
+        assert_eq!(tag.event_attributes.len(), 1);
+        assert_eq!(tag.event_attributes[0].key.name, "input");
+
+        let tag: OpeningTag = syn::parse_str(r#"<select @bind={self.choice}>"#).unwrap();
+        assert_eq!(tag.event_attributes[0].key.name, "change");
+    }
+
+    #[test]
+    fn should_not_parse_bind_on_other_tags_or_values() {
+        let tag: ParseResult<OpeningTag> = syn::parse_str(r#"<div @bind={self.text}>"#);
+        assert!(tag.is_err());
+
+        let tag: ParseResult<SelfClosingTag> = syn::parse_str(r#"<input @bind={text}/>"#);
+        assert!(tag.is_err());
+
+        let tag: ParseResult<SelfClosingTag> =
+            syn::parse_str(r#"<input value={"Ruukh"} @bind={self.text}/>"#);
+        assert!(tag.is_err());
+    }
+
     #[test]
     fn should_parse_single_tag_name() {
         let parsed: TagName = syn::parse_str("Identifier").unwrap();
-        if let TagName::Component { ident } = parsed {
+        if let TagName::Component { ident, .. } = parsed {
             assert_eq!(ident, "Identifier");
         }
     }
 
+    #[test]
+    fn should_parse_memoized_tag_name() {
+        let parsed: TagName = syn::parse_str("Memo<Button>").unwrap();
+        match parsed {
+            TagName::Component { ident, memo } => {
+                assert_eq!(ident, "Button");
+                assert!(memo);
+            }
+            _ => panic!("Expected a component tag name."),
+        }
+    }
+
+    #[test]
+    fn should_parse_memoized_component_element() {
+        let el: NormalHtmlElement =
+            syn::parse_str(r#"<Memo<Button> disabled={true}></Memo<Button>>"#).unwrap();
+        assert_eq!(el.opening_tag.prop_attributes.len(), 1);
+    }
+
+    #[test]
+    fn should_not_parse_mismatched_memoized_closing_tag() {
+        let el: ParseResult<NormalHtmlElement> =
+            syn::parse_str(r#"<Memo<Button>></Button>"#);
+        assert!(el.is_err());
+    }
+
     #[test]
     fn should_parse_dashed_tag_name() {
         let parsed: TagName = syn::parse_str("first-second-third").unwrap();
-        if let TagName::Component { ident } = parsed {
+        if let TagName::Component { ident, .. } = parsed {
             assert_eq!(ident, "first-second-third");
         }
     }
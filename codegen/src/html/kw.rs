@@ -1,7 +1,40 @@
 //! Custom keywords used in the parser.
+use super::element::TagName;
+use std::cell::RefCell;
 use syn::{custom_keyword, parse::ParseStream, Token};
 
+thread_local! {
+    /// The custom tags declared to be self-closing in the template being
+    /// parsed.
+    static CUSTOM_SELF_CLOSING: RefCell<Vec<String>> = RefCell::new(vec![]);
+}
+
+/// Treats the custom tags as self-closing while running `f`.
+pub fn with_custom_self_closing<T>(tags: Vec<String>, f: impl FnOnce() -> T) -> T {
+    let previous = CUSTOM_SELF_CLOSING.with(|current| current.replace(tags));
+    let result = f();
+    CUSTOM_SELF_CLOSING.with(|current| current.replace(previous));
+    result
+}
+
 custom_keyword!(key);
+custom_keyword!(ruukh);
+
+/// Whether the next attribute is the reserved `key={..}` attribute and not a
+/// dashed attribute which starts with `key`.
+pub fn is_key(inp: ParseStream<'_>) -> bool {
+    inp.peek(key) && inp.peek2(Token![=])
+}
+
+/// Whether the next attribute is the reserved `ref={..}` attribute.
+pub fn is_ref(inp: ParseStream<'_>) -> bool {
+    inp.peek(Token![ref]) && inp.peek2(Token![=])
+}
+
+/// Whether the next attribute is the reserved `ruukh:for={..}` attribute.
+pub fn is_for(inp: ParseStream<'_>) -> bool {
+    inp.peek(ruukh) && inp.peek2(Token![:]) && inp.peek3(Token![for])
+}
 
 macro_rules! custom_keywords {
     ($($ident:ident),*) => {
@@ -26,5 +59,24 @@ pub fn is_self_closing(inp: ParseStream<'_>) -> bool {
         && (is_self_closing!(
             inp is
             [area, base, br, col, embed, hr, img, input, link, meta, param, source, track, wbr]
-        ))
+        ) || is_custom_self_closing(inp))
+}
+
+/// Whether the next tag is one of the custom tags declared to be
+/// self-closing.
+fn is_custom_self_closing(inp: ParseStream<'_>) -> bool {
+    CUSTOM_SELF_CLOSING.with(|tags| {
+        let tags = tags.borrow();
+        if tags.is_empty() {
+            return false;
+        }
+        let fork = inp.fork();
+        if fork.parse::<Token![<]>().is_err() {
+            return false;
+        }
+        match fork.parse() {
+            Ok(TagName::Tag { ref name, .. }) => tags.contains(name),
+            _ => false,
+        }
+    })
 }
@@ -1,17 +1,19 @@
 #![recursion_limit = "256"]
 #![cfg_attr(feature = "cargo-clippy", feature(tool_lints))]
 #![cfg_attr(feature = "cargo-clippy", warn(clippy::all))]
+#![cfg_attr(feature = "nightly-diagnostics", feature(proc_macro_diagnostic))]
 //! The crate which removes most of the boilerplate from Ruukh apps.
 //!
-//! This lib defines `#[component]`, `#[derive(Lifecycle)]` and `html!` macros.
+//! This lib defines `#[component]`, `#[derive(Lifecycle)]`, `html!` and
+//! `html_static!` macros.
 extern crate proc_macro;
 
-use crate::{component::ComponentMeta, html::HtmlRoot};
-use proc_macro2::Span;
+use crate::{component::ComponentMeta, html::HtmlTemplate};
 use quote::quote;
-use syn::{parse::Error, parse_macro_input, spanned::Spanned, DeriveInput, Item};
+use syn::{parse::Error, parse_macro_input, spanned::Spanned, DeriveInput, Ident, Item};
 
 mod component;
+mod diagnostic;
 mod html;
 mod suffix;
 
@@ -78,6 +80,19 @@ pub fn derive_lifecycle(input: proc_macro::TokenStream) -> proc_macro::TokenStre
 /// state field. If a `#[state]` or `#[state(default)]` is specified then the
 /// `Default` value of the field is used. If you want to provide a more
 /// specific value, then pass it by using `#[state(default = val)]` attribute.
+///
+/// A component which updates on its own cadence, like a clock, may be
+/// declared as `#[component(isolated)]`. Its state changes, along with the
+/// ones of its descendants, rerender only itself instead of walking the whole
+/// app.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component(isolated)]
+/// struct Clock {
+///     #[state]
+///     time: String,
+/// }
+/// ```
 #[proc_macro_attribute]
 #[cfg_attr(
     feature = "cargo-clippy",
@@ -87,18 +102,24 @@ pub fn component(
     metadata: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    if !metadata.is_empty() {
-        return Error::new(
-            Span::call_site(),
-            "`#[component]` does not support attribute arguments.",
-        ).to_compile_error()
-        .into();
-    }
+    let isolated = if metadata.is_empty() {
+        false
+    } else {
+        let arg = parse_macro_input!(metadata as Ident);
+        if arg != "isolated" {
+            return Error::new(
+                arg.span(),
+                "`#[component]` only supports the `isolated` argument.",
+            ).to_compile_error()
+            .into();
+        }
+        true
+    };
 
     let input = parse_macro_input!(input as Item);
 
     let expanded = match input {
-        Item::Struct(struct_) => ComponentMeta::parse(struct_)
+        Item::Struct(struct_) => ComponentMeta::parse(struct_, isolated)
             .map(|s| s.expand())
             .unwrap_or_else(|e| e.to_compile_error()),
         _ => {
@@ -111,6 +132,10 @@ pub fn component(
 
 /// `html!` macro to parse `vue`-inspired syntax to generate Markup.
 ///
+/// It always expands to a `VNode`, i.e. the `Markup`, whatever the markup is,
+/// so it can be used as any expression, e.g. in a `match` arm or as an
+/// argument.
+///
 /// The basics of using html! macro:
 ///
 /// ## Text
@@ -142,6 +167,37 @@ pub fn component(
 /// }
 /// ```
 ///
+/// ## Event listeners
+/// Event listeners are prefixed with `@`. The `.prevent` & `.stop` modifiers
+/// call `preventDefault()` & `stopPropagation()` on the event before the
+/// listener is invoked.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <a href={"/about"} @click.prevent={navigate}>"About"</a>
+/// }
+/// ```
+///
+/// The `.async` modifier spawns the future returned by the listener, with
+/// the `async-events` feature of `ruukh`.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <button @click.async={Self::save}>"Save"</button>
+/// }
+/// ```
+///
+/// ## Two-way binding
+/// `@bind={self.field}` on an `<input>`, a `<textarea>` or a `<select>` sets
+/// its value to the state field, and sets the state field to the value the
+/// user enters on `input`, or on `change` for a `<select>`.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <input @bind={self.query}>
+/// }
+/// ```
+///
 /// ## Component tags
 /// ```ignore,compile_fail
 /// html! {
@@ -149,6 +205,16 @@ pub fn component(
 /// }
 /// ```
 ///
+/// ## Memoized component tags
+/// A memoized component is not updated at all when its props are equal to the
+/// previous ones. Its props type needs to implement `Clone` & `PartialEq`.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <Memo<MyComponent> prop={value}></Memo<MyComponent>>
+/// }
+/// ```
+///
 /// ## List of tags
 /// ```ignore,compile_fail
 /// html! {
@@ -173,8 +239,113 @@ pub fn component(
 ///     "There are "{ count }" people."
 /// }
 /// ```
+///
+/// ## Lists
+/// The reserved `ruukh:for` attribute repeats a tag for every item of an
+/// iterator. With a `key`, every item is keyed by it, so that the components
+/// keep their state when the items are reordered.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <ul>
+///         <TodoItem ruukh:for={todo in self.todos.iter()} key={todo.id} todo={todo.clone()}>
+///         </TodoItem>
+///     </ul>
+/// }
+/// ```
+///
+/// ## Fragments
+/// The built-in `Fragment` groups its children without a wrapper element, so
+/// that `ruukh:for` repeats all of them for every item. A `key` on it keys
+/// the whole group.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <dl>
+///         <Fragment ruukh:for={term in self.terms.iter()} key={term.id}>
+///             <dt>{ term.name.clone() }</dt>
+///             <dd>{ term.definition.clone() }</dd>
+///         </Fragment>
+///     </dl>
+/// }
+/// ```
+///
+/// ## Portals
+/// The built-in `Portal` renders its children into the `target` element
+/// instead.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <Portal target={modal_root}>
+///         <div class={"modal"}></div>
+///     </Portal>
+/// }
+/// ```
+///
+/// ## SVG
+/// An `<svg>` along with its descendants is created in the SVG namespace. The
+/// `xlink:*` and `xml:*` attributes are set within their namespaces.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <svg width={"24"} height={"24"}>
+///         <image xlink:href={"icon.png"}></image>
+///     </svg>
+/// }
+/// ```
+///
+/// ## Raw HTML
+/// The reserved `inner-html` attribute sets the raw HTML as the content of an
+/// element, which then cannot have any children. The HTML is not sanitized.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <article inner-html={rendered_markdown}></article>
+/// }
+/// ```
+///
+/// ## Custom self-closing tags
+/// Only the void elements of HTML like `<input>` are written without a
+/// closing tag. Any custom elements to be written so are declared at the
+/// start of the template.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     #![self_closing(my-icon, x-spacer)]
+///     <button><my-icon name={"save"}><x-spacer>"Save"</button>
+/// }
+/// ```
 #[proc_macro]
 pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let parsed = parse_macro_input!(input as HtmlRoot);
-    parsed.expand().into()
+    match diagnostic::parse::<HtmlTemplate>(input) {
+        Ok(parsed) => parsed.root.expand().into(),
+        Err(err) => diagnostic::to_compile_error(err).into(),
+    }
+}
+
+/// `html_static!` macro to construct a subtree which never changes only once.
+///
+/// The markup is constructed lazily on its first use and cached per thread,
+/// so every render shares the same markup and patching it does nothing. As it
+/// is constructed only once, the expressions in it cannot use the local
+/// variables and it cannot have event listeners, refs, components or portals.
+///
+/// ```ignore,compile_fail
+/// html! {
+///     <main>{ self.content() }</main>
+///     { html_static! {
+///         <footer>"Made with Ruukh"</footer>
+///     } }
+/// }
+/// ```
+#[proc_macro]
+pub fn html_static(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = match diagnostic::parse::<HtmlTemplate>(input) {
+        Ok(parsed) => parsed.root,
+        Err(err) => return diagnostic::to_compile_error(err).into(),
+    };
+    if let Err(err) = parsed.check_static() {
+        return err.to_compile_error().into();
+    }
+    parsed.expand_static().into()
 }
@@ -2,7 +2,7 @@
 
 use ruukh::prelude::*;
 use wasm_bindgen::prelude::*;
-use web_sys::Event;
+use ruukh::web_api::MouseEvent;
 
 #[component]
 #[derive(Lifecycle)]
@@ -36,7 +36,7 @@ impl MainApp {
         }
     }
 
-    fn toggle(&self, _: Event) {
+    fn toggle(&self, _: MouseEvent) {
         self.set_state(|state| {
             state.toggle = !state.toggle;
         });
@@ -46,7 +46,7 @@ impl MainApp {
 #[component]
 #[derive(Lifecycle)]
 #[events(
-    fn click(&self, event: Event);
+    fn click(&self, event: MouseEvent);
 )]
 struct Button {
     disabled: Option<bool>,
@@ -1,8 +1,8 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 
 use ruukh::prelude::*;
-use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{Event, HtmlInputElement};
+use ruukh::web_api::InputEvent;
+use wasm_bindgen::prelude::*;
 
 #[component]
 #[derive(Lifecycle)]
@@ -31,11 +31,10 @@ impl Render for MainApp {
 }
 
 impl MainApp {
-    fn on_input(&self, ev: Event) {
-        let ev_target = ev.current_target().unwrap();
-        let input_el: &HtmlInputElement = ev_target.unchecked_ref();
+    fn on_input(&self, ev: InputEvent) {
+        let value = ev.target_value().unwrap_or_default();
         self.set_state(|state| {
-            state.input = input_el.value();
+            state.input = value.clone();
         });
     }
 }
@@ -1,13 +1,14 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 
-use ruukh::prelude::*;
-use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::window;
+use ruukh::{prelude::*, web_api::Interval};
+use wasm_bindgen::prelude::*;
 
 #[component]
 struct MainApp {
     #[state]
     seconds: i32,
+    #[state]
+    ticker: Option<Interval>,
 }
 
 impl Lifecycle for MainApp {
@@ -15,21 +16,14 @@ impl Lifecycle for MainApp {
         // Get a setter to mutate the state within the closure.
         let setter = self.state_setter();
 
-        let closure = Closure::wrap(Box::new(move || {
+        let ticker = Interval::new(1000, move || {
             setter.set_state(|state| {
                 state.seconds += 1;
             });
-        }) as Box<Fn()>);
-
-        window()
-            .unwrap()
-            .set_interval_with_callback_and_timeout_and_arguments_0(
-                closure.as_ref().unchecked_ref(),
-                1000,
-            ).unwrap();
+        });
 
-        // Forget the closure so that it lives for the lifetime of the program.
-        closure.forget();
+        // Keep the interval in the state, so that it is cleared along with it.
+        self.set_state(|state| state.ticker = Some(ticker.clone()));
     }
 }
 
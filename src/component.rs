@@ -60,7 +60,8 @@
 //! Note: Docs on component macros are located
 //! [here](../../ruukh_codegen/index.html).
 
-use crate::{Markup, MessageSender, Shared};
+use crate::{DebouncedSender, Markup, MessageSender, Shared, ThrottledSender};
+use std::{any::Any, error::Error, fmt, mem};
 
 /// Trait to define a component. You do not need to implement this trait. Auto
 /// implement this trait by using `#[component]` on a component struct (which
@@ -105,6 +106,16 @@ pub trait Component: 'static {
     /// name with `State`.
     type State: Default;
 
+    /// Whether the component rerenders only itself on its own state changes,
+    /// instead of the whole app.
+    ///
+    /// ## Internals
+    ///
+    /// It is set by `#[component(isolated)]`. An isolated component gets its
+    /// own message channel, which is passed down to its descendants too, so
+    /// their state changes rerender it apart from the render loop of the app.
+    const ISOLATED: bool = false;
+
     /// Creates a new component with the props, events and state passed to it.
     ///
     /// ## Internals
@@ -133,6 +144,18 @@ pub trait Component: 'static {
 
     /// Get the status of the component.
     fn status(&self) -> Option<&Shared<Status<Self::State>>>;
+
+    /// Rerenders the component on the next frame, even though none of its
+    /// state changed. Use it when the component renders some external data,
+    /// e.g. a shared cache, which has changed.
+    ///
+    /// It does nothing on a component without any props or state, as there
+    /// is no status to route the request through.
+    fn force_update(&self) {
+        if let Some(status) = self.status() {
+            status.borrow_mut().force_update();
+        }
+    }
 }
 
 /// Trait to allow mutatation of a component state.
@@ -203,6 +226,8 @@ pub struct Status<T> {
     state: T,
     state_dirty: bool,
     props_dirty: bool,
+    /// Whether the component is to be rerendered regardless of its state
+    update_forced: bool,
     rx_sender: MessageSender,
 }
 
@@ -213,6 +238,7 @@ impl<T> Status<T> {
             state,
             state_dirty: false,
             props_dirty: false,
+            update_forced: false,
             rx_sender,
         }
     }
@@ -251,6 +277,30 @@ impl<T> Status<T> {
     pub fn do_react(&self) {
         self.rx_sender.do_react();
     }
+
+    /// Sends a request to the App to rerender the component, even though its
+    /// state did not change.
+    pub fn force_update(&mut self) {
+        self.update_forced = true;
+        self.rx_sender.do_react();
+    }
+
+    /// Whether the update was forced since last checked.
+    pub(crate) fn take_update_forced(&mut self) -> bool {
+        mem::replace(&mut self.update_forced, false)
+    }
+
+    /// Gets a sender which requests the App to react only once the state
+    /// changes stop for `ms` milliseconds.
+    pub fn debounced(&self, ms: u32) -> DebouncedSender {
+        self.rx_sender.debounced(ms)
+    }
+
+    /// Gets a sender which requests the App to react at most once every `ms`
+    /// milliseconds.
+    pub fn throttled(&self, ms: u32) -> ThrottledSender {
+        self.rx_sender.throttled(ms)
+    }
 }
 
 /// The lifecycle of a stateful component.
@@ -261,23 +311,156 @@ pub trait Lifecycle: Component {
     /// Invoked when the component is first created.
     fn created(&self) {}
 
+    /// Invoked before the component is updated with newer props to decide
+    /// whether it needs to be rerendered.
+    ///
+    /// The newer props are stored into the component regardless, but when it
+    /// returns `false` the component is not rerendered for those props. The
+    /// default implementation always rerenders.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn should_update(&self, new_props: &Self::Props) -> bool {
+    ///     self.count != new_props.count
+    /// }
+    /// ```
+    #[allow(unused_variables)]
+    fn should_update(&self, new_props: &Self::Props) -> bool {
+        true
+    }
+
     /// Invoked when the component props are updated.
+    ///
+    /// It is invoked with the older props after the component is rerendered
+    /// with the newer ones, so the DOM already reflects the newer props.
     #[allow(unused_variables)]
     fn updated(&self, old_props: Self::Props) {}
 
     /// Invoked when the component is mounted onto the DOM tree.
+    ///
+    /// It is invoked only once, after the component as well as all of its
+    /// nested components are rendered and attached to the DOM.
     fn mounted(&self) {}
 
+    /// Invoked before the component is removed from the DOM tree.
+    ///
+    /// It is invoked on a parent before its nested components, so it is the
+    /// place to clean up any listeners, timers or connections opened in
+    /// `mounted`.
+    fn before_destroy(&self) {}
+
     /// Invoked when the component is removed from the DOM tree.
     fn destroyed(&self) {}
+
+    /// Invoked when any of the nested components fails to render, which makes
+    /// the component an error boundary.
+    ///
+    /// The subtree which failed is removed and the returned markup is rendered
+    /// in its place, until the component is rerendered. The default
+    /// implementation returns the error back, so that it is propagated to the
+    /// nearest ancestor which handles it. Set a state here to render something
+    /// else on the next render.
+    ///
+    /// The error is a [RenderError](struct.RenderError.html) when a
+    /// [TryRender](trait.TryRender.html) component failed. On the targets other
+    /// than wasm32, the panics of the nested components are caught as well, in
+    /// which case it is the value the panic was raised with. The panics abort
+    /// on wasm32, so only the render errors are handled there.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn on_error(&self, error: Box<dyn Any + Send>) -> Result<Markup<Self>, Box<dyn Any + Send>> {
+    ///     Ok(html! {
+    ///         "Something went wrong."
+    ///     })
+    /// }
+    /// ```
+    fn on_error(&self, error: Box<dyn Any + Send>) -> Result<Markup<Self>, Box<dyn Any + Send>>
+    where
+        Self: Sized,
+    {
+        Err(error)
+    }
 }
 
 /// Trait to render a view for the component.
 pub trait Render: Lifecycle + Sized {
     /// Render a markup for the component by using the html! macro.
     fn render(&self) -> Markup<Self>;
+
+    /// Render a markup for the component which may fail. The framework always
+    /// renders a component through this method.
+    ///
+    /// The default implementation never fails. Implement
+    /// [TryRender](trait.TryRender.html) instead of `Render` for a fallible
+    /// render.
+    fn try_render(&self) -> Result<Markup<Self>, RenderError> {
+        Ok(self.render())
+    }
+}
+
+/// Trait to render a view for the component which may fail.
+///
+/// When the render fails, the error is handled by the nearest error boundary
+/// i.e. an ancestor which handles it in its `Lifecycle::on_error`, where the
+/// error is passed as a `RenderError`. The components implementing this trait
+/// implement `Render` automatically.
+///
+/// # Example
+/// ```ignore
+/// impl TryRender for Profile {
+///     fn render(&self) -> Result<Markup<Self>, RenderError> {
+///         let user = self.user.as_ref().ok_or_else(|| RenderError::new("No user found."))?;
+///         Ok(html! {
+///             { &user.name }
+///         })
+///     }
+/// }
+/// ```
+pub trait TryRender: Lifecycle + Sized {
+    /// Render a markup for the component by using the html! macro, or an error
+    /// if it cannot be rendered.
+    fn render(&self) -> Result<Markup<Self>, RenderError>;
+}
+
+impl<T: TryRender> Render for T {
+    fn render(&self) -> Markup<Self> {
+        TryRender::render(self).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_render(&self) -> Result<Markup<Self>, RenderError> {
+        TryRender::render(self)
+    }
+}
+
+/// An error which occurred while rendering a component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderError {
+    message: String,
+}
+
+impl RenderError {
+    /// Creates a new render error with the given message.
+    pub fn new(message: impl Into<String>) -> RenderError {
+        RenderError {
+            message: message.into(),
+        }
+    }
+
+    /// The message of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
 }
 
+impl Error for RenderError {}
+
 /// Trait to convert from a event props to a events type.
 ///
 /// Used to convert a (render) contextual events type to a wrapped one.
@@ -336,6 +519,13 @@ impl Lifecycle for RootParent {
         )
     }
 
+    fn should_update(&self, _: &Self::Props) -> bool {
+        unreachable!(
+            "It is a void component to be used as a render context for a root \
+             component. Not to be used as a component itself."
+        )
+    }
+
     fn updated(&self, _: Self::Props) {
         unreachable!(
             "It is a void component to be used as a render context for a root \
@@ -350,12 +540,26 @@ impl Lifecycle for RootParent {
         )
     }
 
+    fn before_destroy(&self) {
+        unreachable!(
+            "It is a void component to be used as a render context for a root \
+             component. Not to be used as a component itself."
+        )
+    }
+
     fn destroyed(&self) {
         unreachable!(
             "It is a void component to be used as a render context for a root \
              component. Not to be used as a component itself."
         )
     }
+
+    fn on_error(&self, _: Box<dyn Any + Send>) -> Result<Markup<Self>, Box<dyn Any + Send>> {
+        unreachable!(
+            "It is a void component to be used as a render context for a root \
+             component. Not to be used as a component itself."
+        )
+    }
 }
 
 impl Render for RootParent {
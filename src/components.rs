@@ -0,0 +1,13 @@
+//! The ready-made components of Ruukh.
+//!
+//! They are defined in their own modules and gathered here, so that an app
+//! finds all of them in one place.
+//!
+//! # Example
+//! ```ignore
+//! use ruukh::components::{VirtualList, VirtualListProps};
+//! ```
+
+#[cfg(feature = "drop-zone")]
+pub use crate::drop_zone::{DropZone, DropZoneProps, DropZoneState, FileHandle};
+pub use crate::virtual_list::{VirtualList, VirtualListProps, VirtualListState};
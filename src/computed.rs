@@ -0,0 +1,310 @@
+//! A value derived from some shared values, which is cached until any of them
+//! changes, much like the computed properties of Vue.
+//!
+//! Create a [Computed](struct.Computed.html) with the
+//! [computed!](../macro.computed.html) macro, naming the shared values it
+//! depends upon.
+//!
+//! # Example
+//! ```ignore
+//! let items: Shared<Vec<Item>> = Rc::new(RefCell::new(vec![]));
+//! let filter: Shared<String> = Rc::new(RefCell::new(String::new()));
+//!
+//! let visible = computed!(|deps: (items, filter)| {
+//!     items
+//!         .iter()
+//!         .filter(|item| item.name.contains(filter.as_str()))
+//!         .count()
+//! });
+//!
+//! // Filtered only once, until either of the items or the filter changes.
+//! assert_eq!(visible.get(), visible.get());
+//! ```
+
+use crate::Shared;
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    rc::Rc,
+};
+
+/// A shared value which counts the times it is mutably borrowed, so that a
+/// `Computed` depending upon it only compares the counts instead of the
+/// values.
+///
+/// Unlike a `Shared` dependency, its value is cloned only when recomputing and
+/// is not required to be `PartialEq`. Any mutable borrow counts as a change,
+/// even if the value is left as it was.
+///
+/// # Example
+/// ```ignore
+/// let items = Tracked::new(vec![]);
+/// let count = computed!(|deps: (items)| items.len());
+///
+/// items.borrow_mut().push(Item::new("Milk"));
+/// assert_eq!(count.get(), 1);
+/// ```
+pub struct Tracked<T>(Rc<TrackedValue<T>>);
+
+/// The value of a `Tracked` which is shared by its clones.
+struct TrackedValue<T> {
+    value: RefCell<T>,
+    version: Cell<u64>,
+}
+
+impl<T> Tracked<T> {
+    /// Creates a shared value to be tracked.
+    pub fn new(value: T) -> Tracked<T> {
+        Tracked(Rc::new(TrackedValue {
+            value: RefCell::new(value),
+            version: Cell::new(0),
+        }))
+    }
+
+    /// Borrows the value.
+    pub fn borrow(&self) -> Ref<T> {
+        self.0.value.borrow()
+    }
+
+    /// Borrows the value mutably, counting it as a change.
+    pub fn borrow_mut(&self) -> RefMut<T> {
+        let value = self.0.value.borrow_mut();
+        self.0.version.set(self.0.version.get().wrapping_add(1));
+        value
+    }
+
+    /// The number of times the value has been mutably borrowed.
+    pub fn version(&self) -> u64 {
+        self.0.version.get()
+    }
+}
+
+impl<T> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        Tracked(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for Tracked<T> {
+    /// Trackeds are equal only when they are the clones of the same Tracked.
+    fn eq(&self, other: &Tracked<T>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A shared value a `Computed` depends upon.
+pub trait Dependency {
+    /// The value it is computed with.
+    type Value;
+    /// What is compared to tell whether the value has changed since it was
+    /// last computed.
+    type Version: PartialEq;
+
+    /// Gets the current version of the value.
+    fn version(&self) -> Self::Version;
+
+    /// Gets a copy of the current value.
+    fn value(&self) -> Self::Value;
+}
+
+/// A `Shared` value is its own version, so it is cloned & compared on every
+/// read.
+impl<T: Clone + PartialEq> Dependency for Shared<T> {
+    type Value = T;
+    type Version = T;
+
+    fn version(&self) -> T {
+        self.borrow().clone()
+    }
+
+    fn value(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+impl<T: Clone> Dependency for Tracked<T> {
+    type Value = T;
+    type Version = u64;
+
+    fn version(&self) -> u64 {
+        Tracked::version(self)
+    }
+
+    fn value(&self) -> T {
+        self.borrow().clone()
+    }
+}
+
+/// The shared values a `Computed` depends upon.
+///
+/// It is implemented for the tuples of up to four `Shared` or `Tracked`
+/// values.
+pub trait Dependencies {
+    /// The values of the dependencies the value is computed with.
+    type Values;
+    /// The versions of the dependencies, as seen when last computed.
+    type Versions: PartialEq;
+
+    /// Gets the current versions of the dependencies.
+    fn versions(&self) -> Self::Versions;
+
+    /// Gets a copy of the current values of the dependencies.
+    fn values(&self) -> Self::Values;
+}
+
+macro_rules! dependencies {
+    ($($t:ident: $i:tt),*) => {
+        impl<$($t: Dependency),*> Dependencies for ($($t,)*) {
+            type Values = ($($t::Value,)*);
+            type Versions = ($($t::Version,)*);
+
+            fn versions(&self) -> Self::Versions {
+                ($(self.$i.version(),)*)
+            }
+
+            fn values(&self) -> Self::Values {
+                ($(self.$i.value(),)*)
+            }
+        }
+    };
+}
+
+dependencies!(A: 0);
+dependencies!(A: 0, B: 1);
+dependencies!(A: 0, B: 1, C: 2);
+dependencies!(A: 0, B: 1, C: 2, D: 3);
+
+/// A value computed from its dependencies, which is recomputed only when they
+/// change.
+///
+/// The versions of the dependencies are compared with the ones it was last
+/// computed with. A `Shared` dependency is compared by its value, so it is
+/// required to be `Clone` and `PartialEq`, whereas a `Tracked` one is compared
+/// by its version alone.
+pub struct Computed<D: Dependencies, T> {
+    deps: D,
+    compute: Box<dyn Fn(&D::Values) -> T>,
+    /// The last computed value along with the versions of the dependencies it
+    /// was computed with
+    cache: RefCell<Option<(D::Versions, T)>>,
+}
+
+impl<D: Dependencies, T: Clone> Computed<D, T> {
+    /// Creates a value computed from the dependencies. It is not computed
+    /// until it is first read.
+    pub fn new(deps: D, compute: impl Fn(&D::Values) -> T + 'static) -> Computed<D, T> {
+        Computed {
+            deps,
+            compute: Box::new(compute),
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Gets the cached value, recomputing it when any of the dependencies has
+    /// changed since.
+    pub fn get(&self) -> T {
+        let versions = self.deps.versions();
+        if let Some((ref seen, ref value)) = *self.cache.borrow() {
+            if *seen == versions {
+                return value.clone();
+            }
+        }
+        let value = (self.compute)(&self.deps.values());
+        *self.cache.borrow_mut() = Some((versions, value.clone()));
+        value
+    }
+}
+
+/// Creates a [Computed](computed/struct.Computed.html) value from the named
+/// shared values, which are captured by cloning them.
+///
+/// Within the expression, the names refer to the values the shared values
+/// hold.
+///
+/// # Example
+/// ```ignore
+/// let total = computed!(|deps: (price, quantity)| price * quantity);
+/// ```
+#[macro_export]
+macro_rules! computed {
+    (|deps: ($($dep:ident),+ $(,)*)| $body:expr) => {
+        $crate::computed::Computed::new(
+            ($($dep.clone(),)+),
+            move |&($(ref $dep,)+)| $body,
+        )
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_compute_from_the_dependencies() {
+        let price = Rc::new(RefCell::new(3));
+        let quantity = Rc::new(RefCell::new(4));
+        let total = computed!(|deps: (price, quantity)| price * quantity);
+
+        assert_eq!(total.get(), 12);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_recompute_only_when_a_dependency_changes() {
+        let price = Rc::new(RefCell::new(3));
+        let quantity = Rc::new(RefCell::new(4));
+        let computes = Rc::new(Cell::new(0));
+        let computes_clone = computes.clone();
+        let total = Computed::new(
+            (price.clone(), quantity.clone()),
+            move |&(price, quantity): &(i32, i32)| {
+                computes_clone.set(computes_clone.get() + 1);
+                price * quantity
+            },
+        );
+
+        total.get();
+        total.get();
+        assert_eq!(computes.get(), 1);
+
+        *quantity.borrow_mut() = 5;
+        assert_eq!(total.get(), 15);
+        assert_eq!(computes.get(), 2);
+
+        // Set to the same value, so nothing has changed.
+        *price.borrow_mut() = 3;
+        total.get();
+        assert_eq!(computes.get(), 2);
+    }
+
+    /// Counts the times it is cloned.
+    struct Counted(Rc<Cell<u32>>);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            Counted(self.0.clone())
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_compare_the_versions_of_tracked_dependencies() {
+        let clones = Rc::new(Cell::new(0));
+        let items = Tracked::new(Counted(clones.clone()));
+        let computes = Rc::new(Cell::new(0));
+        let computes_clone = computes.clone();
+        let count = Computed::new((items.clone(),), move |_: &(Counted,)| {
+            computes_clone.set(computes_clone.get() + 1);
+            computes_clone.get()
+        });
+
+        count.get();
+        count.get();
+        assert_eq!(computes.get(), 1);
+        // Cloned only to be computed with.
+        assert_eq!(clones.get(), 1);
+
+        items.borrow_mut();
+        assert_eq!(count.get(), 2);
+        assert_eq!(clones.get(), 2);
+    }
+}
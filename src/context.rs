@@ -0,0 +1,284 @@
+//! Contexts to pass values down the component tree without passing them
+//! through the props of every component in between.
+//!
+//! An ancestor component provides a value with
+//! [provide_context](fn.provide_context.html) in its `render` and any of its
+//! descendants get it with [consume_context](fn.consume_context.html). When
+//! the provided value changes, only the components which consumed it are
+//! rerendered.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for ThemeProvider {
+//!     fn render(&self) -> Markup<Self> {
+//!         provide_context(Theme::Dark);
+//!         html! {
+//!             <Toolbar></Toolbar>
+//!         }
+//!     }
+//! }
+//!
+//! impl Render for ThemedButton {
+//!     fn render(&self) -> Markup<Self> {
+//!         let theme = consume_context::<Theme>().unwrap_or_default();
+//!         html! {
+//!             <button class={theme.class_name()}>"Click"</button>
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The services used throughout the app, like an API client, are provided
+//! to all the components with [App::provide](../struct.App.html#method.provide)
+//! instead.
+//!
+//! ## Internals
+//!
+//! Every component has its own scope of contexts. While a component is being
+//! walked, its scope is pushed on top of a stack of scopes of its ancestors,
+//! so that the contexts are looked up from the nearest ancestor.
+
+use crate::{expect::ExpectMsg, MessageSender, Shared};
+use fnv::FnvBuildHasher;
+use std::{
+    any::{Any, TypeId},
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+thread_local! {
+    /// The scopes of the components which are being walked currently.
+    static SCOPES: RefCell<Vec<Shared<Scope>>> = RefCell::new(vec![]);
+    /// The version to be given to the next changed context value.
+    static NEXT_VERSION: Cell<usize> = Cell::new(0);
+}
+
+/// The contexts provided and consumed by a component.
+#[derive(Default)]
+pub(crate) struct Scope {
+    /// The values provided along with their versions.
+    provided: HashMap<TypeId, (Rc<dyn Any>, usize), FnvBuildHasher>,
+    /// The versions of the values consumed.
+    consumed: HashMap<TypeId, usize, FnvBuildHasher>,
+    /// The values kept across the renders by the functions called in
+    /// `render`, e.g. `use_media_query`, by their keys.
+    kept: HashMap<String, Rc<dyn Any>, FnvBuildHasher>,
+    /// Whether any of the kept values has asked to rerender the component.
+    rerender: bool,
+    /// Messages the app to rerender, once the component is walked.
+    sender: Option<MessageSender>,
+}
+
+impl Scope {
+    /// Provides a value in the scope, unless it equals the one provided
+    /// before.
+    pub(crate) fn provide<T: Clone + PartialEq + 'static>(&mut self, value: T) {
+        let type_id = TypeId::of::<T>();
+        let unchanged = self
+            .provided
+            .get(&type_id)
+            .and_then(|(old, _)| old.downcast_ref::<T>())
+            .map_or(false, |old| *old == value);
+        if !unchanged {
+            let version = NEXT_VERSION.with(|next| {
+                let version = next.get();
+                next.set(version + 1);
+                version
+            });
+            self.provided.insert(type_id, (Rc::new(value), version));
+        }
+    }
+
+    /// Forgets the consumed contexts, as they are consumed again on render,
+    /// along with the asked rerender.
+    pub(crate) fn clear_consumed(&mut self) {
+        self.consumed.clear();
+        self.rerender = false;
+    }
+
+    /// Sets the sender which messages the app to rerender the component.
+    pub(crate) fn set_sender(&mut self, sender: MessageSender) {
+        self.sender = Some(sender);
+    }
+}
+
+/// Provides a value to all the descendants of the component which is being
+/// rendered. Must be called from within `render` or the lifecycle hooks.
+///
+/// Providing a value equal to the one provided before does not rerender the
+/// consumers.
+pub fn provide_context<T: Clone + PartialEq + 'static>(value: T) {
+    SCOPES.with(|scopes| {
+        let scopes = scopes.borrow();
+        scopes
+            .last()
+            .expect_msg("Contexts can only be provided while rendering a component.")
+            .borrow_mut()
+            .provide(value);
+    });
+}
+
+/// Gets the value provided by the nearest ancestor, if any. Must be called
+/// from within `render` or the lifecycle hooks.
+///
+/// The component is rerendered whenever the consumed value changes.
+pub fn consume_context<T: Clone + 'static>() -> Option<T> {
+    SCOPES.with(|scopes| {
+        let scopes = scopes.borrow();
+        let type_id = TypeId::of::<T>();
+        let (value, version) = lookup(&scopes, type_id)?;
+
+        if let Some(current) = scopes.last() {
+            current.borrow_mut().consumed.insert(type_id, version);
+        }
+        value.downcast_ref::<T>().cloned()
+    })
+}
+
+/// Gets the value kept by the key in the scope of the component which is
+/// being rendered, creating it with `init` when it is not kept yet. The value
+/// is given a callback to rerender the component, e.g. on an event.
+///
+/// Nothing is kept when the component is not walked by an app, e.g. when it is
+/// rendered to a string.
+pub(crate) fn keep<T: 'static>(
+    key: &str,
+    init: impl FnOnce(Rc<dyn Fn()>) -> T,
+) -> Option<Rc<T>> {
+    let scope = SCOPES.with(|scopes| scopes.borrow().last().cloned())?;
+    if scope.borrow().sender.is_none() {
+        return None;
+    }
+    let kept = scope.borrow().kept.get(key).cloned();
+    let kept = match kept.and_then(|kept| kept.downcast::<T>().ok()) {
+        Some(kept) => kept,
+        None => {
+            // The value is owned by the scope, so it holds the scope weakly.
+            let weak = Rc::downgrade(&scope);
+            let value = Rc::new(init(Rc::new(move || {
+                if let Some(scope) = weak.upgrade() {
+                    let mut scope = scope.borrow_mut();
+                    scope.rerender = true;
+                    if let Some(ref sender) = scope.sender {
+                        sender.do_react();
+                    }
+                }
+            })));
+            scope.borrow_mut().kept.insert(key.to_string(), value.clone());
+            value
+        }
+    };
+    Some(kept)
+}
+
+/// Enters the scope of a component which is about to be walked.
+pub(crate) fn enter(scope: Shared<Scope>) {
+    SCOPES.with(|scopes| scopes.borrow_mut().push(scope));
+}
+
+/// Exits the scope of the component which was walked.
+pub(crate) fn exit() {
+    SCOPES.with(|scopes| {
+        scopes.borrow_mut().pop();
+    });
+}
+
+/// The number of scopes entered currently.
+pub(crate) fn depth() -> usize {
+    SCOPES.with(|scopes| scopes.borrow().len())
+}
+
+/// The scopes entered currently, from the outermost one.
+pub(crate) fn scopes() -> Vec<Shared<Scope>> {
+    SCOPES.with(|scopes| scopes.borrow().clone())
+}
+
+/// Exits the scopes entered after the given depth. The scopes are not exited
+/// when a panic unwinds through the components, so they are exited when the
+/// panic is caught.
+pub(crate) fn unwind(depth: usize) {
+    SCOPES.with(|scopes| scopes.borrow_mut().truncate(depth));
+}
+
+/// Whether any of the contexts consumed in the scope have changed since, or
+/// any of its kept values has asked to rerender.
+pub(crate) fn is_stale(scope: &Shared<Scope>) -> bool {
+    SCOPES.with(|scopes| {
+        let scopes = scopes.borrow();
+        let scope = scope.borrow();
+        scope.rerender
+            || scope.consumed.iter().any(|(type_id, version)| {
+                lookup(&scopes, *type_id).map(|(_, current)| current) != Some(*version)
+            })
+    })
+}
+
+/// Looks up the nearest provided value from the innermost scope.
+fn lookup(scopes: &[Shared<Scope>], type_id: TypeId) -> Option<(Rc<dyn Any>, usize)> {
+    scopes.iter().rev().find_map(|scope| {
+        scope
+            .borrow()
+            .provided
+            .get(&type_id)
+            .map(|(value, version)| (value.clone(), *version))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_consume_context_from_the_nearest_ancestor() {
+        let root = Rc::new(RefCell::new(Scope::default()));
+        let parent = Rc::new(RefCell::new(Scope::default()));
+        let child = Rc::new(RefCell::new(Scope::default()));
+
+        enter(root.clone());
+        provide_context(String::from("light"));
+        enter(parent.clone());
+        provide_context(String::from("dark"));
+        enter(child.clone());
+
+        assert_eq!(consume_context::<String>(), Some("dark".to_string()));
+        assert_eq!(consume_context::<i32>(), None);
+
+        exit();
+        exit();
+        exit();
+    }
+
+    #[wasm_bindgen_test]
+    fn should_be_stale_only_when_the_consumed_context_changes() {
+        let parent = Rc::new(RefCell::new(Scope::default()));
+        let child = Rc::new(RefCell::new(Scope::default()));
+
+        enter(parent.clone());
+        provide_context(String::from("dark"));
+        enter(child.clone());
+        consume_context::<String>();
+        exit();
+
+        assert!(!is_stale(&child));
+
+        provide_context(String::from("dark"));
+        assert!(!is_stale(&child));
+
+        provide_context(String::from("light"));
+        assert!(is_stale(&child));
+
+        exit();
+    }
+
+    #[wasm_bindgen_test]
+    fn should_unwind_to_the_given_depth() {
+        let depth = depth();
+        enter(Rc::new(RefCell::new(Scope::default())));
+        enter(Rc::new(RefCell::new(Scope::default())));
+
+        unwind(depth);
+        assert_eq!(super::depth(), depth);
+    }
+}
@@ -1,7 +1,31 @@
 use crate::{component::Render, MessageSender, Shared};
+use std::cell::Cell;
 use wasm_bindgen::prelude::JsValue;
 use web_sys::Node;
 
+thread_local! {
+    /// Whether the nodes of the VDOM being removed are already detached from
+    /// their parent, so that only the VDOM itself is cleaned up.
+    static DETACHED: Cell<bool> = Cell::new(false);
+}
+
+/// Removes the VDOM while running `f` as if its nodes are already detached,
+/// or not.
+pub(crate) fn with_detached<T>(detached: bool, f: impl FnOnce() -> T) -> T {
+    let previous = DETACHED.with(|current| current.replace(detached));
+    let result = f();
+    DETACHED.with(|current| current.set(previous));
+    result
+}
+
+/// Removes the node from the parent, unless it is already detached.
+pub(crate) fn remove_child(parent: &Node, node: &Node) -> Result<(), JsValue> {
+    if !DETACHED.with(Cell::get) {
+        parent.remove_child(node)?;
+    }
+    Ok(())
+}
+
 /// Trait to patch the DOM to reflect the VDOM structure.
 pub(crate) trait DOMPatch
 where
@@ -0,0 +1,365 @@
+//! An area the user drops the files onto, e.g. to upload them.
+//!
+//! The [DropZone](struct.DropZone.html) tracks whether the files are being
+//! dragged over it and provides it as a
+//! [DropZoneState](struct.DropZoneState.html) context, so that its children
+//! style themselves accordingly. The dropped files are handed over as the
+//! [FileHandle](struct.FileHandle.html)s.
+//!
+//! Requires the `drop-zone` feature.
+//!
+//! # Example
+//! ```ignore
+//! let zone = VComponent::new::<DropZone>(
+//!     DropZoneProps {
+//!         on_drop: Box::new(|files| {
+//!             for file in files {
+//!                 spawn_local(file.read_as_data_url().map(show_preview).map_err(|_| ()));
+//!             }
+//!         }),
+//!         accept: Some("image/*".to_string()),
+//!         children: Rc::new(|| html! {
+//!             <DropHint></DropHint>
+//!         }),
+//!     },
+//!     (),
+//! );
+//! ```
+
+use crate::{
+    component::{Component, Lifecycle, Render, Status},
+    context::provide_context,
+    vdom::velement::{Attribute, ClassList, EventListener, VElement},
+    web_api::DragEvent,
+    Markup, Shared,
+};
+use futures::Future;
+use js_sys::Promise;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{File, FileReader};
+
+/// The props of a [DropZone](struct.DropZone.html).
+pub struct DropZoneProps {
+    /// Receives the dropped files which are accepted.
+    pub on_drop: Box<dyn Fn(Vec<FileHandle>)>,
+    /// The types of the files accepted, in the syntax of the `accept`
+    /// attribute of an `<input type="file">`, e.g. `"image/*,.pdf"`. All of
+    /// them are accepted when `None`.
+    pub accept: Option<String>,
+    /// Renders the children of the zone.
+    pub children: Rc<dyn Fn() -> Markup<DropZone>>,
+}
+
+/// The state of a [DropZone](struct.DropZone.html), which is also provided
+/// to its children as a context.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DropZoneState {
+    /// Whether the files are being dragged over the zone.
+    pub is_dragging_over: bool,
+    /// Whether any of the files last dropped were rejected.
+    pub has_rejected: bool,
+}
+
+/// An area which receives the dropped files.
+///
+/// It is rendered as a `div` with the `ruukh-drop-zone` class, along with the
+/// `dragging-over` class while the files are dragged over it and the
+/// `rejected` class once any of the dropped files are rejected.
+pub struct DropZone {
+    props: DropZoneProps,
+    state: DropZoneState,
+    /// The number of the elements within the zone the drag has entered, as
+    /// the drag leaves the zone only when it leaves all of them
+    entered: Cell<u32>,
+    status: Shared<Status<DropZoneState>>,
+}
+
+impl DropZone {
+    /// Sets the state and rerenders the zone, if it has changed.
+    fn set_state(&self, mutator: impl FnOnce(&mut DropZoneState)) {
+        let mut status = self.status.borrow_mut();
+        let old = *status.state_as_ref();
+        mutator(status.state_as_mut());
+        if *status.state_as_ref() != old {
+            status.set_state_dirty(true);
+            status.do_react();
+        }
+    }
+
+    fn on_drag_enter(&self, event: DragEvent) {
+        event.prevent_default();
+        self.entered.set(self.entered.get() + 1);
+        self.set_state(|state| state.is_dragging_over = true);
+    }
+
+    fn on_drag_over(&self, event: DragEvent) {
+        // Allows the files to be dropped.
+        event.prevent_default();
+    }
+
+    fn on_drag_leave(&self, _: DragEvent) {
+        self.entered.set(self.entered.get().saturating_sub(1));
+        if self.entered.get() == 0 {
+            self.set_state(|state| state.is_dragging_over = false);
+        }
+    }
+
+    fn on_drop(&self, event: DragEvent) {
+        // Stops the browser from opening the files.
+        event.prevent_default();
+        self.entered.set(0);
+
+        let files = event.data_transfer().and_then(|data| data.files());
+        let mut accepted = vec![];
+        let mut has_rejected = false;
+        if let Some(files) = files {
+            for index in 0..files.length() {
+                if let Some(file) = files.get(index) {
+                    let file = FileHandle(file);
+                    if file.is_accepted(self.props.accept.as_ref().map(String::as_str)) {
+                        accepted.push(file);
+                    } else {
+                        has_rejected = true;
+                    }
+                }
+            }
+        }
+
+        self.set_state(|state| {
+            state.is_dragging_over = false;
+            state.has_rejected = has_rejected;
+        });
+        if !accepted.is_empty() {
+            (self.props.on_drop)(accepted);
+        }
+    }
+}
+
+impl Component for DropZone {
+    type Props = DropZoneProps;
+    type Events = ();
+    type State = DropZoneState;
+
+    fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+        DropZone {
+            props,
+            state: *status.state_as_ref(),
+            entered: Cell::new(0),
+            status: Rc::new(RefCell::new(status)),
+        }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        // The closures cannot be compared, so always rerender.
+        self.status.borrow_mut().set_props_dirty(true);
+        Some(std::mem::replace(&mut self.props, props))
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        let state = *self.status.borrow().state_as_ref();
+        let changed = state != self.state;
+        self.state = state;
+        changed
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        Some(&self.status)
+    }
+}
+
+impl Lifecycle for DropZone {}
+
+impl Render for DropZone {
+    fn render(&self) -> Markup<Self> {
+        provide_context(self.state);
+        let class = ClassList::new()
+            .add("ruukh-drop-zone")
+            .add_if("dragging-over", self.state.is_dragging_over)
+            .add_if("rejected", self.state.has_rejected);
+        VElement::new(
+            "div",
+            vec![Attribute::new("class", class)],
+            vec![
+                EventListener::new(
+                    "dragenter",
+                    Box::new(|this: &DropZone, event| this.on_drag_enter(event)),
+                ),
+                EventListener::new(
+                    "dragover",
+                    Box::new(|this: &DropZone, event| this.on_drag_over(event)),
+                ),
+                EventListener::new(
+                    "dragleave",
+                    Box::new(|this: &DropZone, event| this.on_drag_leave(event)),
+                ),
+                EventListener::new("drop", Box::new(|this: &DropZone, event| this.on_drop(event))),
+            ],
+            (self.props.children)(),
+        )
+        .into()
+    }
+}
+
+/// A file dropped onto a [DropZone](struct.DropZone.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileHandle(File);
+
+impl FileHandle {
+    /// The name of the file, without its path.
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> f64 {
+        self.0.size()
+    }
+
+    /// The MIME type of the file, e.g. `image/png`. It is empty when the
+    /// browser cannot tell.
+    pub fn mime_type(&self) -> String {
+        self.0.type_()
+    }
+
+    /// The underlying file, e.g. to upload it with a `FormData`.
+    pub fn file(&self) -> &File {
+        &self.0
+    }
+
+    /// Reads the content of the file as a `data:` URL, e.g. to preview an
+    /// image with an `<img src={..}>`.
+    pub fn read_as_data_url(&self) -> impl Future<Item = String, Error = JsValue> {
+        let file = self.0.clone();
+        let read = Promise::new(&mut |resolve, reject| {
+            let reader = match FileReader::new() {
+                Ok(reader) => reader,
+                Err(err) => {
+                    let _ = reject.call1(&JsValue::NULL, &err);
+                    return;
+                }
+            };
+            let on_load = {
+                let reader = reader.clone();
+                Closure::once_into_js(move || {
+                    let url = reader.result().unwrap_or(JsValue::UNDEFINED);
+                    let _ = resolve.call1(&JsValue::NULL, &url);
+                })
+            };
+            let on_error = {
+                let reject = reject.clone();
+                Closure::once_into_js(move |event: JsValue| {
+                    let _ = reject.call1(&JsValue::NULL, &event);
+                })
+            };
+            reader.set_onload(Some(on_load.unchecked_ref()));
+            reader.set_onerror(Some(on_error.unchecked_ref()));
+            if let Err(err) = reader.read_as_data_url(&file) {
+                let _ = reject.call1(&JsValue::NULL, &err);
+            }
+        });
+        JsFuture::from(read).map(|url| url.as_string().unwrap_or_default())
+    }
+
+    /// Whether the file is of any of the types accepted, in the syntax of the
+    /// `accept` attribute.
+    fn is_accepted(&self, accept: Option<&str>) -> bool {
+        accept.map_or(true, |accept| accepts(accept, &self.name(), &self.mime_type()))
+    }
+}
+
+/// Whether a file of the name and the MIME type is of any of the types
+/// accepted, i.e. the extensions like `.pdf`, the MIME types like
+/// `image/png` or the wildcards like `image/*`.
+fn accepts(accept: &str, name: &str, mime_type: &str) -> bool {
+    let name = name.to_lowercase();
+    let mime_type = mime_type.to_lowercase();
+    accept
+        .split(',')
+        .map(|kind| kind.trim().to_lowercase())
+        .filter(|kind| !kind.is_empty())
+        .any(|kind| {
+            if kind.starts_with('.') {
+                name.ends_with(&kind)
+            } else if kind.ends_with("/*") {
+                mime_type.starts_with(&kind[..kind.len() - 1])
+            } else {
+                mime_type == kind
+            }
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        dom::DOMPatch,
+        vdom::{test::container, vcomponent::VComponent, vtext::VText, VNode},
+    };
+    use wasm_bindgen_test::*;
+    use web_sys::{DataTransfer, DragEventInit};
+
+    #[wasm_bindgen_test]
+    fn should_accept_the_files_by_their_types() {
+        assert!(accepts("image/*", "cat.PNG", "image/png"));
+        assert!(accepts("image/png, .pdf", "report.PDF", ""));
+        assert!(!accepts("image/*", "report.pdf", "application/pdf"));
+        assert!(!accepts("image/png", "cat.jpg", "image/jpeg"));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_hand_over_only_the_accepted_files() {
+        let dropped = Rc::new(RefCell::new(vec![]));
+        let dropped_clone = dropped.clone();
+        let mut zone: VNode<()> = VComponent::new::<DropZone>(
+            DropZoneProps {
+                on_drop: Box::new(move |files| {
+                    dropped_clone
+                        .borrow_mut()
+                        .extend(files.iter().map(FileHandle::name))
+                }),
+                accept: Some("text/plain".to_string()),
+                children: Rc::new(|| VText::text("Drop here").into()),
+            },
+            (),
+        )
+        .into();
+        let div = container();
+        zone.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        let data = DataTransfer::new().unwrap();
+        let text = File::new_with_str_sequence_and_options(
+            &js_sys::Array::of1(&JsValue::from_str("Hello")),
+            "hello.txt",
+            web_sys::FilePropertyBag::new().type_("text/plain"),
+        )
+        .unwrap();
+        let image = File::new_with_str_sequence_and_options(
+            &js_sys::Array::new(),
+            "cat.png",
+            web_sys::FilePropertyBag::new().type_("image/png"),
+        )
+        .unwrap();
+        data.items().add_with_file(&text).unwrap();
+        data.items().add_with_file(&image).unwrap();
+        let mut init = DragEventInit::new();
+        init.cancelable(true).data_transfer(Some(&data));
+        let event = DragEvent::new_with_event_init_dict("drop", &init).unwrap();
+        div.first_child().unwrap().dispatch_event(&event).unwrap();
+
+        assert_eq!(*dropped.borrow(), vec!["hello.txt".to_string()]);
+        assert!(event.default_prevented());
+    }
+}
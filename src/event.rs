@@ -0,0 +1,221 @@
+//! Dispatching DOM events from the components to interop with the non-Ruukh
+//! code listening on the DOM, and listening to the ones it dispatches.
+
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{CustomEvent, CustomEventInit, Event, EventTarget};
+
+/// Dispatches a bubbling `CustomEvent` of the given name on the target.
+///
+/// Returns `false` if any of the listeners called `preventDefault` on it.
+pub fn dispatch_event(target: &EventTarget, name: &str) -> Result<bool, JsValue> {
+    let mut init = CustomEventInit::new();
+    init.bubbles(true);
+    let event = CustomEvent::new_with_event_init_dict(name, &init)?;
+    target.dispatch_event(&event)
+}
+
+/// Dispatches a bubbling `CustomEvent` of the given name on the target with
+/// the `detail` serialized to a JS value, so that the listeners get structured
+/// data.
+///
+/// Returns `false` if any of the listeners called `preventDefault` on it.
+///
+/// Requires the `serialize` feature.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Serialize)]
+/// struct Selected {
+///     id: u32,
+/// }
+///
+/// let el = self.list_ref.get().unwrap();
+/// dispatch_custom_event(el.as_ref(), "selected", &Selected { id: 5 })?;
+/// ```
+#[cfg(feature = "serialize")]
+pub fn dispatch_custom_event<T: serde::Serialize>(
+    target: &EventTarget,
+    name: &str,
+    detail: &T,
+) -> Result<bool, JsValue> {
+    let detail = serde_wasm_bindgen::to_value(detail)?;
+    let mut init = CustomEventInit::new();
+    init.bubbles(true);
+    init.detail(&detail);
+    let event = CustomEvent::new_with_event_init_dict(name, &init)?;
+    target.dispatch_event(&event)
+}
+
+/// Dispatches a `CustomEvent` of the given name on the target with the
+/// `detail`, which may be any JS value.
+///
+/// A `composed` event propagates out of the shadow DOM the target is in, if
+/// it also `bubbles`. Returns `false` if any of the listeners called
+/// `preventDefault` on it.
+///
+/// # Example
+/// ```ignore
+/// let el = self.root_ref.get().unwrap();
+/// event::dispatch(el.as_ref(), "value-changed", &JsValue::from(42), true, true)?;
+/// ```
+pub fn dispatch(
+    target: &EventTarget,
+    name: &str,
+    detail: &JsValue,
+    bubbles: bool,
+    composed: bool,
+) -> Result<bool, JsValue> {
+    let mut init = CustomEventInit::new();
+    init.bubbles(bubbles).composed(composed).detail(detail);
+    let event = CustomEvent::new_with_event_init_dict(name, &init)?;
+    target.dispatch_event(&event)
+}
+
+/// Listens to the `CustomEvent`s of the given name on the target, e.g. the
+/// ones dispatched by a web component, with the handler receiving their
+/// `detail`. The events which are not a `CustomEvent` are received with an
+/// `undefined` detail.
+///
+/// It stops listening once the returned listener is dropped along with all
+/// of its clones, so keep it in the state of the component.
+///
+/// # Example
+/// ```ignore
+/// let el = self.picker_ref.get().unwrap();
+/// let listener = event::listen(el.as_ref(), "color-picked", move |detail| {
+///     setter.set_state(|state| state.color = detail.as_string());
+/// })?;
+/// ```
+pub fn listen(
+    target: &EventTarget,
+    name: &str,
+    handler: impl Fn(JsValue) + 'static,
+) -> Result<CustomEventListener, JsValue> {
+    let listener: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |event: Event| {
+        handler(
+            event
+                .dyn_into::<CustomEvent>()
+                .map_or(JsValue::UNDEFINED, |event| event.detail()),
+        )
+    }));
+    target.add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())?;
+    Ok(CustomEventListener(Rc::new(Listening {
+        target: target.clone(),
+        name: name.to_string(),
+        listener,
+    })))
+}
+
+/// A listener of the custom events made by `listen`, which stops listening
+/// once it is dropped along with all of its clones.
+#[derive(Clone)]
+pub struct CustomEventListener(Rc<Listening>);
+
+/// The listening of the `CustomEventListener` which is shared by its clones.
+struct Listening {
+    target: EventTarget,
+    name: String,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl PartialEq for CustomEventListener {
+    /// CustomEventListeners are equal only when they are the clones of the
+    /// same CustomEventListener.
+    fn eq(&self, other: &CustomEventListener) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        let _ = self.target.remove_event_listener_with_callback(
+            &self.name,
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vdom::test::container;
+    use std::cell::RefCell;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_dispatch_event_to_external_listener() {
+        let div = container();
+        let received = Rc::new(RefCell::new(false));
+        let cloned = received.clone();
+        let _listener =
+            listen(div.as_ref(), "saved", move |_| *cloned.borrow_mut() = true).unwrap();
+
+        dispatch_event(div.as_ref(), "saved").expect("To dispatch event");
+
+        assert!(*received.borrow());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[wasm_bindgen_test]
+    fn should_dispatch_custom_event_with_serialized_detail() {
+        #[derive(serde::Serialize)]
+        struct Selected {
+            id: u32,
+            name: String,
+        }
+
+        let div = container();
+        let received = Rc::new(RefCell::new(None));
+        let cloned = received.clone();
+        let _listener = listen(div.as_ref(), "selected", move |detail| {
+            let name = js_sys::Reflect::get(&detail, &JsValue::from_str("name")).unwrap();
+            *cloned.borrow_mut() = name.as_string();
+        })
+        .unwrap();
+
+        dispatch_custom_event(
+            div.as_ref(),
+            "selected",
+            &Selected {
+                id: 5,
+                name: "Ruukh".to_string(),
+            },
+        )
+        .expect("To dispatch event");
+
+        assert_eq!(*received.borrow(), Some("Ruukh".to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_dispatch_the_detail_bubbling_only_if_asked() {
+        let parent = container();
+        let child = container();
+        parent.append_child(&child).unwrap();
+        let received = Rc::new(RefCell::new(vec![]));
+        let cloned = received.clone();
+        let _listener = listen(parent.as_ref(), "picked", move |detail| {
+            cloned.borrow_mut().push(detail.as_f64())
+        })
+        .unwrap();
+
+        dispatch(child.as_ref(), "picked", &JsValue::from(1), false, false).unwrap();
+        dispatch(child.as_ref(), "picked", &JsValue::from(2), true, true).unwrap();
+
+        assert_eq!(*received.borrow(), vec![Some(2.0)]);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_stop_listening_once_dropped() {
+        let div = container();
+        let received = Rc::new(RefCell::new(0));
+        let cloned = received.clone();
+        let listener = listen(div.as_ref(), "saved", move |_| *cloned.borrow_mut() += 1).unwrap();
+
+        dispatch_event(div.as_ref(), "saved").unwrap();
+        drop(listener);
+        dispatch_event(div.as_ref(), "saved").unwrap();
+
+        assert_eq!(*received.borrow(), 1);
+    }
+}
@@ -0,0 +1,61 @@
+//! Unwrapping that can be stripped of its messages.
+//!
+//! The panic messages and the formatting machinery that comes along with them
+//! add a good chunk to the wasm binary. With the `minimal` feature on, a
+//! failed unwrap aborts without a message instead.
+
+/// Unwraps a value or fails with a message that only exists in non-minimal
+/// builds.
+pub(crate) trait ExpectMsg<T> {
+    /// Unwraps the value, failing with `msg` if there is none.
+    fn expect_msg(self, msg: &'static str) -> T;
+}
+
+impl<T> ExpectMsg<T> for Option<T> {
+    #[inline]
+    fn expect_msg(self, msg: &'static str) -> T {
+        match self {
+            Some(value) => value,
+            None => fail(msg),
+        }
+    }
+}
+
+impl<T, E> ExpectMsg<T> for Result<T, E> {
+    #[inline]
+    fn expect_msg(self, msg: &'static str) -> T {
+        match self {
+            Ok(value) => value,
+            Err(_) => fail(msg),
+        }
+    }
+}
+
+/// Fails with the given message.
+#[cfg(not(feature = "minimal"))]
+#[cold]
+#[inline(never)]
+pub(crate) fn fail(msg: &'static str) -> ! {
+    panic!("{}", msg)
+}
+
+/// Fails without any message, so that neither the message nor the panic
+/// formatting ends up in the binary.
+#[cfg(feature = "minimal")]
+#[cold]
+#[inline(always)]
+pub(crate) fn fail(_: &'static str) -> ! {
+    std::process::abort()
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExpectMsg;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_unwrap_present_values() {
+        assert_eq!(Some(5).expect_msg("To have a value"), 5);
+        assert_eq!(Ok::<_, ()>("ok").expect_msg("To be ok"), "ok");
+    }
+}
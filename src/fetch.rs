@@ -0,0 +1,461 @@
+//! Fetching resources over the network which can be cancelled.
+//!
+//! Keep the [AbortController](struct.AbortController.html) of the in-flight
+//! fetches in the component, so that they are aborted once the component is
+//! destroyed along with it.
+//!
+//! Requires the `fetch` feature.
+//!
+//! # Example
+//! ```ignore
+//! let controller = AbortController::new()?;
+//! let response = fetch("/api/users", Some(&controller.signal()));
+//! spawn_local(response.map(|response| ...).map_err(|err| ...));
+//!
+//! // Aborts the fetch, its future fails with an `AbortError`.
+//! controller.abort();
+//! ```
+//!
+//! With the `serialize` feature, a [FetchHandle](struct.FetchHandle.html)
+//! takes care of all of it, fetching the JSON of a
+//! [FetchRequest](struct.FetchRequest.html) into a
+//! [FetchState](enum.FetchState.html) of the component.
+
+use crate::expect::ExpectMsg;
+use futures::Future;
+#[cfg(feature = "serialize")]
+use std::{
+    cell::RefCell,
+    marker::PhantomData,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{prelude::JsValue, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, AbortSignal, Headers, RequestInit, Response};
+
+/// A controller to abort the fetches it signals. It also aborts them when it
+/// is dropped.
+pub struct AbortController(web_sys::AbortController);
+
+impl AbortController {
+    /// Creates a new abort controller.
+    pub fn new() -> Result<AbortController, JsValue> {
+        Ok(AbortController(web_sys::AbortController::new()?))
+    }
+
+    /// The signal to be passed to the fetches which are to be aborted by this
+    /// controller.
+    pub fn signal(&self) -> AbortSignal {
+        self.0.signal()
+    }
+
+    /// Aborts all the fetches signaled by this controller.
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+}
+
+impl Drop for AbortController {
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+/// A request of a resource along with its method, headers & body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+impl FetchRequest {
+    /// Create a request with the method to the url.
+    pub fn new(method: &str, url: impl Into<String>) -> FetchRequest {
+        FetchRequest {
+            method: method.to_string(),
+            url: url.into(),
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    /// Create a `GET` request to the url.
+    pub fn get(url: impl Into<String>) -> FetchRequest {
+        FetchRequest::new("GET", url)
+    }
+
+    /// Create a `POST` request to the url.
+    pub fn post(url: impl Into<String>) -> FetchRequest {
+        FetchRequest::new("POST", url)
+    }
+
+    /// Appends a header to the request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the body of the request.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+}
+
+/// Fetches the resource at the url, which is aborted once the signal is.
+///
+/// The future fails with an `AbortError` when aborted.
+pub fn fetch(
+    url: &str,
+    signal: Option<&AbortSignal>,
+) -> impl Future<Item = Response, Error = JsValue> {
+    fetch_request(&FetchRequest::get(url), signal)
+}
+
+/// Fetches the resource of the request, which is aborted once the signal is.
+///
+/// The future fails with an `AbortError` when aborted.
+pub fn fetch_request(
+    request: &FetchRequest,
+    signal: Option<&AbortSignal>,
+) -> impl Future<Item = Response, Error = JsValue> {
+    let promise = request_init(request, signal)
+        .map(|init| {
+            window()
+                .expect_msg("Could not find the window to fetch with.")
+                .fetch_with_str_and_init(&request.url, &init)
+        });
+    futures::future::result(promise)
+        .and_then(JsFuture::from)
+        .and_then(|response| response.dyn_into::<Response>())
+}
+
+/// Builds the init of the request.
+fn request_init(
+    request: &FetchRequest,
+    signal: Option<&AbortSignal>,
+) -> Result<RequestInit, JsValue> {
+    let mut init = RequestInit::new();
+    init.method(&request.method).signal(signal);
+    if !request.headers.is_empty() {
+        let headers = Headers::new()?;
+        for (name, value) in request.headers.iter() {
+            headers.append(name, value)?;
+        }
+        init.headers(&headers);
+    }
+    if let Some(ref body) = request.body {
+        init.body(Some(&JsValue::from_str(body)));
+    }
+    Ok(init)
+}
+
+/// The state of a resource fetched by a `FetchHandle`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchState<T> {
+    /// The fetch is in-flight
+    Loading,
+    /// The fetched resource
+    Ok(T),
+    /// The fetch failed
+    Err(FetchError),
+}
+
+impl<T> Default for FetchState<T> {
+    fn default() -> Self {
+        FetchState::Loading
+    }
+}
+
+/// The reason a fetch by a `FetchHandle` failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchError {
+    /// The request could not be sent, e.g. when offline.
+    Network(String),
+    /// The response has a non-2xx status.
+    Status(u16),
+    /// The response is not the JSON expected.
+    Parse(String),
+}
+
+#[cfg(feature = "serialize")]
+impl FetchError {
+    /// Creates a network error out of a JS error.
+    fn network(err: JsValue) -> FetchError {
+        FetchError::Network(error_message(err))
+    }
+
+    /// Creates a parse error out of a JS error.
+    fn parse(err: JsValue) -> FetchError {
+        FetchError::Parse(error_message(err))
+    }
+}
+
+/// Gets the message of a JS error.
+#[cfg(feature = "serialize")]
+fn error_message(err: JsValue) -> String {
+    match err.dyn_into::<js_sys::Error>() {
+        Ok(err) => String::from(err.message()),
+        Err(err) => err.as_string().unwrap_or_default(),
+    }
+}
+
+/// Fetches the JSON of a request whenever its dependencies change, aborting
+/// the fetch in-flight, if any. The fetch is also aborted when the handle is
+/// dropped along with all of its clones.
+///
+/// Keep it in the state of the component along with the `FetchState`, so that
+/// the component is rerendered as the state changes.
+///
+/// Requires the `serialize` feature.
+///
+/// # Example
+/// ```ignore
+/// #[component]
+/// struct UserList {
+///     #[prop]
+///     page: u32,
+///     #[state]
+///     users: FetchState<Vec<User>>,
+///     #[state]
+///     fetcher: FetchHandle<Vec<User>, u32>,
+/// }
+///
+/// impl Lifecycle for UserList {
+///     fn mounted(&self) {
+///         self.fetch_users();
+///     }
+///
+///     fn updated(&self, _: Self::Props) {
+///         self.fetch_users();
+///     }
+/// }
+///
+/// impl UserList {
+///     fn fetch_users(&self) {
+///         let setter = self.state_setter();
+///         let request = FetchRequest::get(format!("/api/users?page={}", self.page));
+///         self.fetcher.use_fetch(request, self.page, move |users| {
+///             setter.set_state(|state| state.users = users.clone());
+///         });
+///     }
+/// }
+/// ```
+#[cfg(feature = "serialize")]
+pub struct FetchHandle<T, D>(Rc<RefCell<Fetching<D>>>, PhantomData<T>);
+
+/// The fetch of a `FetchHandle` which is shared by its clones.
+#[cfg(feature = "serialize")]
+struct Fetching<D> {
+    /// The dependencies of the last fetch
+    deps: Option<D>,
+    /// The number of fetches made, to ignore the stale ones
+    generation: usize,
+    /// The controller of the fetch in-flight
+    controller: Option<AbortController>,
+}
+
+#[cfg(feature = "serialize")]
+impl<T, D> FetchHandle<T, D> {
+    /// Creates a handle which is yet to fetch anything.
+    pub fn new() -> FetchHandle<T, D> {
+        FetchHandle(
+            Rc::new(RefCell::new(Fetching {
+                deps: None,
+                generation: 0,
+                controller: None,
+            })),
+            PhantomData,
+        )
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<T, D> FetchHandle<T, D>
+where
+    T: for<'de> serde::Deserialize<'de> + 'static,
+    D: PartialEq,
+{
+    /// Fetches the request if the dependencies changed since the last fetch,
+    /// aborting the last one if it is still in-flight.
+    ///
+    /// The `on_state` is invoked with `FetchState::Loading` right away and
+    /// then with the result once it is fetched, unless another fetch is made
+    /// or the handle is dropped before that.
+    pub fn use_fetch(
+        &self,
+        request: FetchRequest,
+        deps: D,
+        on_state: impl Fn(FetchState<T>) + 'static,
+    ) {
+        let (generation, signal) = {
+            let mut fetching = self.0.borrow_mut();
+            if fetching.deps.as_ref() == Some(&deps) {
+                return;
+            }
+            let controller = match AbortController::new() {
+                Ok(controller) => controller,
+                Err(err) => {
+                    on_state(FetchState::Err(FetchError::network(err)));
+                    return;
+                }
+            };
+            let signal = controller.signal();
+            fetching.deps = Some(deps);
+            fetching.generation += 1;
+            // Drops the last controller, aborting its fetch.
+            fetching.controller = Some(controller);
+            (fetching.generation, signal)
+        };
+        on_state(FetchState::Loading);
+
+        let fetching: Weak<RefCell<Fetching<D>>> = Rc::downgrade(&self.0);
+        let fetched = fetch_request(&request, Some(&signal))
+            .map_err(FetchError::network)
+            .and_then(|response| {
+                if response.ok() {
+                    response.json().map_err(FetchError::parse)
+                } else {
+                    Err(FetchError::Status(response.status()))
+                }
+            })
+            .and_then(|json| JsFuture::from(json).map_err(FetchError::parse))
+            .and_then(|json| {
+                serde_wasm_bindgen::from_value(json)
+                    .map_err(|err| FetchError::Parse(err.to_string()))
+            })
+            .then(move |result| {
+                let is_current = fetching
+                    .upgrade()
+                    .map_or(false, |fetching| fetching.borrow().generation == generation);
+                if is_current {
+                    on_state(match result {
+                        Ok(value) => FetchState::Ok(value),
+                        Err(err) => FetchState::Err(err),
+                    });
+                }
+                Ok(())
+            });
+        wasm_bindgen_futures::spawn_local(fetched);
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<T, D> Default for FetchHandle<T, D> {
+    fn default() -> Self {
+        FetchHandle::new()
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<T, D> Clone for FetchHandle<T, D> {
+    fn clone(&self) -> Self {
+        FetchHandle(self.0.clone(), PhantomData)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<T, D> PartialEq for FetchHandle<T, D> {
+    /// FetchHandles are equal only when they are the clones of the same
+    /// FetchHandle.
+    fn eq(&self, other: &FetchHandle<T, D>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use js_sys::Reflect;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_build_the_init_of_the_request() {
+        let request = FetchRequest::post("/api/users")
+            .header("Content-Type", "application/json")
+            .body(r#"{"name":"Ruukh"}"#);
+        let init = request_init(&request, None).unwrap();
+
+        let get = |key: &str| Reflect::get(&init, &JsValue::from_str(key)).unwrap();
+        assert_eq!(get("method").as_string().unwrap(), "POST");
+        assert_eq!(get("body").as_string().unwrap(), r#"{"name":"Ruukh"}"#);
+        let headers: Headers = get("headers").dyn_into().unwrap();
+        assert_eq!(
+            headers.get("content-type").unwrap().unwrap(),
+            "application/json"
+        );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[wasm_bindgen_test(async)]
+    fn should_fetch_only_when_the_deps_change() -> impl Future<Item = (), Error = JsValue> {
+        use futures::sync::oneshot;
+
+        let (done, fetched) = oneshot::channel();
+        let done = Rc::new(RefCell::new(Some(done)));
+        let states = Rc::new(RefCell::new(vec![]));
+        let handle = FetchHandle::<Vec<u32>, u32>::new();
+        let on_state = |states: Rc<RefCell<Vec<FetchState<Vec<u32>>>>>| {
+            let done = done.clone();
+            move |state: FetchState<Vec<u32>>| {
+                let is_loading = state == FetchState::Loading;
+                states.borrow_mut().push(state);
+                if !is_loading {
+                    done.borrow_mut().take().unwrap().send(()).unwrap();
+                }
+            }
+        };
+
+        let first_states = Rc::new(RefCell::new(vec![]));
+        handle.use_fetch(
+            FetchRequest::get("/not-found"),
+            1,
+            on_state(first_states.clone()),
+        );
+        handle.use_fetch(FetchRequest::get("/not-found"), 1, |_| {
+            panic!("Fetched for the same deps")
+        });
+        handle.use_fetch(
+            FetchRequest::get("/not-found"),
+            2,
+            on_state(states.clone()),
+        );
+
+        fetched.map_err(|_| JsValue::NULL).map(move |_| {
+            assert_eq!(*first_states.borrow(), vec![FetchState::Loading]);
+            assert_eq!(
+                *states.borrow(),
+                vec![FetchState::Loading, FetchState::Err(FetchError::Status(404))]
+            );
+            drop(handle);
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_fail_with_abort_error_when_aborted() -> impl Future<Item = (), Error = JsValue> {
+        let controller = AbortController::new().unwrap();
+        let response = fetch("/", Some(&controller.signal()));
+        controller.abort();
+
+        response.then(|result| {
+            let err = result.err().expect("To be aborted");
+            let name = Reflect::get(&err, &JsValue::from_str("name")).unwrap();
+            assert_eq!(name.as_string().unwrap(), "AbortError");
+            Ok(())
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_abort_when_the_controller_is_dropped() -> impl Future<Item = (), Error = JsValue> {
+        let controller = AbortController::new().unwrap();
+        let response = fetch("/", Some(&controller.signal()));
+        drop(controller);
+
+        response.then(|result| {
+            assert!(result.is_err());
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,327 @@
+//! The state of a form along with the validation of its fields and whether
+//! the user has visited them yet.
+//!
+//! Keep a [Form](struct.Form.html) in the state of the component, attach it
+//! to the component once it is created so that it is rerendered on every
+//! change, then bind its fields to the inputs.
+//!
+//! # Example
+//! ```ignore
+//! #[derive(Clone, Default)]
+//! struct Login {
+//!     email: String,
+//!     password: String,
+//! }
+//!
+//! impl FormFields for Login {
+//!     fn names() -> &'static [&'static str] {
+//!         &["email", "password"]
+//!     }
+//!
+//!     fn value(&self, name: &str) -> Option<String> {
+//!         match name {
+//!             "email" => Some(self.email.clone()),
+//!             "password" => Some(self.password.clone()),
+//!             _ => None,
+//!         }
+//!     }
+//!
+//!     fn set_value(&mut self, name: &str, value: String) {
+//!         match name {
+//!             "email" => self.email = value,
+//!             "password" => self.password = value,
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//!
+//! #[component]
+//! struct LoginForm {
+//!     #[state(default = Form::new(Login::default()).validator(|login: &Login, name| {
+//!         match name {
+//!             "email" if !login.email.contains('@') => Some("Invalid email.".to_string()),
+//!             _ => None,
+//!         }
+//!     }))]
+//!     form: Form<Login>,
+//! }
+//!
+//! impl Lifecycle for LoginForm {
+//!     fn created(&self) {
+//!         self.form.attach_to(self.status().unwrap());
+//!     }
+//! }
+//!
+//! impl Render for LoginForm {
+//!     fn render(&self) -> Markup<Self> {
+//!         let email = self.form.field("email");
+//!         html! {
+//!             <input
+//!                 value={email.value.clone()}
+//!                 @input={move |_, event: InputEvent| email.set(event.target_value().unwrap())}
+//!                 @blur={move |_, _: FocusEvent| email.on_blur()}
+//!             >
+//!             <button @click={|this: &Self, _: MouseEvent| this.form.submit(log_in)}>
+//!                 "Log in"
+//!             </button>
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{component::Status, Shared};
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    rc::{Rc, Weak},
+};
+
+/// The values of the fields of a form, which are read and written by the
+/// names of the fields.
+pub trait FormFields: Clone + 'static {
+    /// The names of all of the fields.
+    fn names() -> &'static [&'static str];
+
+    /// Gets the value of the field, if there is one by the name.
+    fn value(&self, name: &str) -> Option<String>;
+
+    /// Sets the value of the field, if there is one by the name.
+    fn set_value(&mut self, name: &str, value: String);
+}
+
+/// Validates a field of the form given its name, returning the error if it
+/// is invalid.
+type Validator<T> = Box<dyn Fn(&T, &str) -> Option<String>>;
+
+/// The values of the fields of a form, along with their validation and
+/// whether the user has visited them.
+///
+/// Clones share the same state, and are equal only to each other.
+pub struct Form<T: FormFields>(Shared<FormState<T>>);
+
+/// The state of a `Form` which is shared by its clones.
+struct FormState<T> {
+    values: T,
+    /// The names of the fields the user has visited
+    touched: HashSet<String>,
+    validator: Option<Validator<T>>,
+    /// Rerenders the component the form is attached to
+    notify: Option<Box<dyn Fn()>>,
+}
+
+/// A field of a form, to be bound to an input.
+pub struct FieldBinding<T: FormFields> {
+    form: Form<T>,
+    name: String,
+    /// The current value of the field.
+    pub value: String,
+    /// The error of the field, only once the user has visited it.
+    pub error: Option<String>,
+    /// Whether the user has visited the field.
+    pub touched: bool,
+}
+
+impl<T: FormFields> Form<T> {
+    /// Creates a form with the initial values of its fields, all of which are
+    /// valid and untouched.
+    pub fn new(initial: T) -> Form<T> {
+        Form(Rc::new(RefCell::new(FormState {
+            values: initial,
+            touched: HashSet::new(),
+            validator: None,
+            notify: None,
+        })))
+    }
+
+    /// Validates the fields with the function, which returns the error of
+    /// the named field if it is invalid.
+    pub fn validator(self, validator: impl Fn(&T, &str) -> Option<String> + 'static) -> Form<T> {
+        self.0.borrow_mut().validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Rerenders the component on every change of the form. The form does
+    /// not keep the component alive.
+    pub fn attach_to<S: 'static>(&self, status: &Shared<Status<S>>) {
+        let status = Rc::downgrade(status);
+        self.0.borrow_mut().notify = Some(Box::new(move || {
+            if let Some(status) = Weak::upgrade(&status) {
+                status.borrow_mut().force_update();
+            }
+        }));
+    }
+
+    /// Gets the current values of the fields.
+    pub fn values(&self) -> T {
+        self.0.borrow().values.clone()
+    }
+
+    /// Gets the binding of the field by the name.
+    pub fn field(&self, name: &str) -> FieldBinding<T> {
+        let state = self.0.borrow();
+        let touched = state.touched.contains(name);
+        FieldBinding {
+            form: self.clone(),
+            name: name.to_string(),
+            value: state.values.value(name).unwrap_or_default(),
+            error: if touched { state.error(name) } else { None },
+            touched,
+        }
+    }
+
+    /// Whether all of the fields are valid, visited or not.
+    pub fn is_valid(&self) -> bool {
+        let state = self.0.borrow();
+        T::names().iter().all(|name| state.error(name).is_none())
+    }
+
+    /// Marks all of the fields as visited, so that their errors are shown,
+    /// and calls the handler with the values only if all of them are valid.
+    pub fn submit(&self, handler: impl FnOnce(T)) {
+        self.mutate(|state| {
+            state
+                .touched
+                .extend(T::names().iter().map(|name| name.to_string()))
+        });
+        if self.is_valid() {
+            handler(self.values());
+        }
+    }
+
+    /// Mutates the state and rerenders the attached component.
+    fn mutate(&self, mutator: impl FnOnce(&mut FormState<T>)) {
+        mutator(&mut self.0.borrow_mut());
+        let state = self.0.borrow();
+        if let Some(ref notify) = state.notify {
+            notify();
+        }
+    }
+}
+
+impl<T> FormState<T> {
+    /// The error of the field, if it is invalid.
+    fn error(&self, name: &str) -> Option<String> {
+        self.validator
+            .as_ref()
+            .and_then(|validator| validator(&self.values, name))
+    }
+}
+
+impl<T: FormFields> FieldBinding<T> {
+    /// Sets the value of the field, as the user enters it.
+    pub fn set(&self, value: String) {
+        self.form
+            .mutate(|state| state.values.set_value(&self.name, value));
+    }
+
+    /// Marks the field as visited, once the user leaves it.
+    pub fn on_blur(&self) {
+        self.form.mutate(|state| {
+            state.touched.insert(self.name.clone());
+        });
+    }
+}
+
+impl<T: FormFields> Clone for Form<T> {
+    fn clone(&self) -> Form<T> {
+        Form(self.0.clone())
+    }
+}
+
+impl<T: FormFields> PartialEq for Form<T> {
+    /// Forms are equal only when they are the clones of the same Form.
+    fn eq(&self, other: &Form<T>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MessageSender;
+    use wasm_bindgen_test::*;
+
+    #[derive(Clone, Default)]
+    struct Login {
+        email: String,
+        password: String,
+    }
+
+    impl FormFields for Login {
+        fn names() -> &'static [&'static str] {
+            &["email", "password"]
+        }
+
+        fn value(&self, name: &str) -> Option<String> {
+            match name {
+                "email" => Some(self.email.clone()),
+                "password" => Some(self.password.clone()),
+                _ => None,
+            }
+        }
+
+        fn set_value(&mut self, name: &str, value: String) {
+            match name {
+                "email" => self.email = value,
+                "password" => self.password = value,
+                _ => {}
+            }
+        }
+    }
+
+    fn login_form() -> Form<Login> {
+        Form::new(Login::default()).validator(|login: &Login, name| match name {
+            "email" if !login.email.contains('@') => Some("Invalid email.".to_string()),
+            "password" if login.password.is_empty() => Some("Required.".to_string()),
+            _ => None,
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn should_show_the_error_only_once_the_field_is_touched() {
+        let form = login_form();
+        let email = form.field("email");
+        email.set("ruukh".to_string());
+
+        let email = form.field("email");
+        assert_eq!(email.value, "ruukh");
+        assert!(!email.touched);
+        assert_eq!(email.error, None);
+
+        email.on_blur();
+
+        let email = form.field("email");
+        assert!(email.touched);
+        assert_eq!(email.error.as_ref().map(String::as_str), Some("Invalid email."));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_submit_only_when_all_fields_are_valid() {
+        let form = login_form();
+        form.field("email").set("ruukh@example.com".to_string());
+
+        let mut submitted = None;
+        form.submit(|login| submitted = Some(login));
+        assert!(submitted.is_none());
+        assert!(form.field("password").touched);
+        assert!(!form.is_valid());
+
+        form.field("password").set("secret".to_string());
+        form.submit(|login| submitted = Some(login));
+        assert_eq!(
+            submitted.map(|login| login.email),
+            Some("ruukh@example.com".to_string())
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_rerender_the_attached_component_on_change() {
+        let form = login_form();
+        let status = Rc::new(RefCell::new(Status::new((), MessageSender::inert())));
+        form.attach_to(&status);
+
+        form.field("email").set("ruukh".to_string());
+
+        assert!(status.borrow_mut().take_update_forced());
+    }
+}
@@ -0,0 +1,117 @@
+//! The values of the common attributes which accept only a few keywords, so
+//! that a typo in them is caught at compile time instead of being silently
+//! ignored by the browser.
+//!
+//! # Example
+//! ```ignore
+//! html! {
+//!     <a href="https://example.com" target={Target::Blank}>"Example"</a>
+//!     <form action="/login" method={Method::Post}></form>
+//! }
+//! ```
+
+use crate::vdom::velement::AttributeValue;
+use std::fmt;
+
+/// The browsing context to open a link or submit a form in, i.e. the
+/// `target` attribute of `<a>`, `<area>`, `<base>` and `<form>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// A new tab or window, `_blank`.
+    Blank,
+    /// The current browsing context, `_self`.
+    Self_,
+    /// The parent of the current browsing context, `_parent`.
+    Parent,
+    /// The topmost browsing context, `_top`.
+    Top,
+}
+
+impl Target {
+    /// The keyword of the target as it is written in HTML.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Target::Blank => "_blank",
+            Target::Self_ => "_self",
+            Target::Parent => "_parent",
+            Target::Top => "_top",
+        }
+    }
+}
+
+/// The HTTP method to submit a form with, i.e. the `method` attribute of
+/// `<form>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Submits the form data in the URL, `get`.
+    Get,
+    /// Submits the form data in the request body, `post`.
+    Post,
+    /// Closes the dialog the form is in, `dialog`.
+    Dialog,
+}
+
+impl Method {
+    /// The keyword of the method as it is written in HTML.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            Method::Post => "post",
+            Method::Dialog => "dialog",
+        }
+    }
+}
+
+macro_rules! impl_attribute_value {
+    ($($t:ty),*) => {
+        $(
+            impl fmt::Display for $t {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl From<$t> for AttributeValue {
+                fn from(val: $t) -> AttributeValue {
+                    AttributeValue::String(val.as_str().to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_attribute_value!(Target, Method);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn serialize(value: impl Into<AttributeValue>) -> String {
+        match value.into() {
+            AttributeValue::String(value) => value,
+            _ => panic!("Expected a string attribute value"),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_serialize_the_target() {
+        assert_eq!(serialize(Target::Blank), "_blank");
+        assert_eq!(serialize(Target::Self_), "_self");
+        assert_eq!(serialize(Target::Parent), "_parent");
+        assert_eq!(serialize(Target::Top), "_top");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_serialize_the_method() {
+        assert_eq!(serialize(Method::Get), "get");
+        assert_eq!(serialize(Method::Post), "post");
+        assert_eq!(serialize(Method::Dialog), "dialog");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_display_the_keyword() {
+        assert_eq!(Method::Post.to_string(), "post");
+        assert_eq!(Target::Blank.to_string(), "_blank");
+    }
+}
@@ -51,15 +51,44 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 use crate::{
     component::{Render, RootParent},
-    vdom::vcomponent::{ComponentManager, ComponentWrapper},
+    context::Scope,
+    expect::ExpectMsg,
+    vdom::{
+        vcomponent::{ComponentManager, ComponentWrapper},
+        velement::{with_attribute_transform, AttributeTransform},
+    },
+    web_api::Timeout,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
 };
-use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{window, Element, MessageChannel, MessagePort};
+use web_sys::{window, Element, Node};
 
 pub mod component;
+pub mod components;
+pub mod computed;
+pub mod context;
 mod dom;
+#[cfg(feature = "drop-zone")]
+pub mod drop_zone;
+pub mod event;
+mod expect;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+pub mod form;
+pub mod html_attrs;
+pub mod overlay;
+pub mod router;
+pub mod store;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+#[cfg(all(any(test, feature = "test-utils"), not(target_arch = "wasm32")))]
+pub mod testing;
 pub mod vdom;
+pub mod virtual_list;
+pub mod web_api;
 
 /// A VDOM Markup which is generated by using `html!` macro.
 pub type Markup<RCTX> = vdom::VNode<RCTX>;
@@ -67,8 +96,16 @@ pub type Markup<RCTX> = vdom::VNode<RCTX>;
 /// Things you'll require to build the next great App. Just glob import the
 /// prelude and start building your app.
 pub mod prelude {
-    pub use crate::component::{Component, Lifecycle, Render, SetState, StateSetter};
-    pub use crate::{App, Markup};
+    pub use crate::component::{
+        Component, Lifecycle, Render, RenderError, SetState, StateSetter, TryRender,
+    };
+    pub use crate::context::{consume_context, provide_context};
+    pub use crate::vdom::{
+        velement::{ClassList, CssProperties},
+        NodeRef,
+    };
+    pub use crate::web_api::{EventExt, TouchListExt};
+    pub use crate::{App, AppHandle, Markup, Scheduler};
     pub use ruukh_codegen::*;
 }
 
@@ -84,6 +121,14 @@ where
     COMP: Render<Props = (), Events = ()>,
 {
     manager: ComponentWrapper<COMP, RootParent>,
+    render_complete: Option<Box<dyn FnMut()>>,
+    attribute_transform: Option<AttributeTransform>,
+    /// The scope of the contexts provided to the whole app.
+    scope: Shared<Scope>,
+    /// The duration of the first render in milliseconds
+    first_render_ms: Option<f64>,
+    /// How the rerenders are scheduled
+    schedule: Schedule,
 }
 
 impl<COMP> App<COMP>
@@ -119,6 +164,25 @@ where
         Default::default()
     }
 
+    /// Creates a builder to configure how the app is mounted and rerendered,
+    /// finishing with [mount](struct.AppBuilder.html#method.mount).
+    ///
+    /// # Example
+    /// ```ignore
+    /// App::<MyApp>::builder()
+    ///     .hydrate(true)
+    ///     .scheduler(Scheduler::RequestAnimationFrame)
+    ///     .max_fps(30)
+    ///     .mount("app");
+    /// ```
+    pub fn builder() -> AppBuilder<COMP> {
+        AppBuilder {
+            app: App::new(),
+            hydrate: false,
+            append: false,
+        }
+    }
+
     /// Mounts the app on the given element in the DOM.
     ///
     /// The element may be anything that implements
@@ -145,24 +209,225 @@ where
     /// # }
     /// App::<MyApp>::new().mount("app");
     /// ```
-    pub fn mount(mut self, element: impl AppMount) {
+    pub fn mount(mut self, element: impl AppMount) -> MountedApp<COMP> {
         let parent = element.app_mount();
-        let (receiver, sender) = app_message_channel();
+        let (receiver, sender) = app_message_channel_with(self.schedule);
 
         // Every component requires a render context, so provided a void context.
         let root_parent = Rc::new(RefCell::new(()));
 
         // The first render
-        self.manager
-            .render_walk(parent.as_ref(), None, root_parent.clone(), sender.clone())
+        self.render(parent.as_ref(), None, root_parent.clone(), sender.clone())
             .unwrap();
 
-        // Rerender when it receives update messages.
+        self.rerender_on_message(parent, None, root_parent, receiver, sender)
+    }
+
+    /// Mounts the app after the existing children of the given element,
+    /// leaving them untouched.
+    ///
+    /// The app manages only its own nodes, which are kept together before an
+    /// empty comment even if more children are appended to the element later.
+    /// Useful to enhance a part of a page rendered on the server.
+    ///
+    /// # Example
+    /// ```ignore
+    /// App::<CommentBox>::new().mount_append("article");
+    /// ```
+    pub fn mount_append(mut self, element: impl AppMount) -> MountedApp<COMP> {
+        let parent = element.app_mount();
+        let (receiver, sender) = app_message_channel_with(self.schedule);
+        let root_parent = Rc::new(RefCell::new(()));
+
+        let anchor: Node = window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_comment("")
+            .into();
+        parent.append_child(&anchor).unwrap();
+
+        // The first render
+        self.render(
+            parent.as_ref(),
+            Some(&anchor),
+            root_parent.clone(),
+            sender.clone(),
+        )
+        .unwrap();
+
+        self.rerender_on_message(parent, Some(anchor), root_parent, receiver, sender)
+    }
+
+    /// Mounts the app on the given element which already contains the HTML
+    /// rendered on the server with
+    /// [render_to_string](vdom/ssr/fn.render_to_string.html).
+    ///
+    /// Instead of creating the DOM anew, the existing DOM is adopted while
+    /// attaching the event listeners and populating the node refs. Any part of
+    /// it that does not match the first render is rendered afresh, with a
+    /// warning in the debug builds.
+    ///
+    /// # Example
+    /// ```ignore
+    /// App::<MyApp>::new().hydrate("app");
+    /// ```
+    pub fn hydrate(mut self, element: impl AppMount) -> MountedApp<COMP> {
+        let parent = element.app_mount();
+        let (receiver, sender) = app_message_channel_with(self.schedule);
+        let root_parent = Rc::new(RefCell::new(()));
+
+        // The first render adopts the existing DOM.
+        let start = now();
+        let manager = &mut self.manager;
+        context::enter(self.scope.clone());
+        let rest = with_attribute_transform(self.attribute_transform, || {
+            manager.hydrate_walk(
+                parent.as_ref(),
+                parent.first_child(),
+                root_parent.clone(),
+                sender.clone(),
+            )
+        });
+        context::exit();
+        let rest = rest.unwrap();
+        vdom::hydrate::remove_rest(parent.as_ref(), rest).unwrap();
+        self.first_render_ms = Some(now() - start);
+        self.notify_render_complete();
+
+        self.rerender_on_message(parent, None, root_parent, receiver, sender)
+    }
+
+    /// Rerenders the app before the `next` node when it receives update
+    /// messages, until it is unmounted.
+    fn rerender_on_message(
+        self,
+        parent: Element,
+        next: Option<Node>,
+        root_parent: Shared<RootParent>,
+        receiver: MessageReceiver,
+        sender: MessageSender,
+    ) -> MountedApp<COMP> {
+        let first_render_ms = self.first_render_ms.unwrap_or(0.0);
+        let app = Rc::new(RefCell::new(Some(self)));
+
+        let app_clone = app.clone();
+        let parent_clone = parent.clone();
+        let next_clone = next.clone();
+        let sender_clone = sender.clone();
         receiver.react_on_message(move || {
-            self.manager
-                .render_walk(parent.as_ref(), None, root_parent.clone(), sender.clone())
+            if let Some(ref mut app) = *app_clone.borrow_mut() {
+                app.render(
+                    parent_clone.as_ref(),
+                    next_clone.as_ref(),
+                    root_parent.clone(),
+                    sender_clone.clone(),
+                )
                 .unwrap();
+            }
+        });
+
+        MountedApp {
+            app,
+            parent,
+            anchor: next,
+            sender,
+            first_render_ms,
+        }
+    }
+
+    /// Registers a callback which is invoked every time the app finishes
+    /// rendering, i.e. after all the changes are committed to the DOM.
+    ///
+    /// Unlike the `Lifecycle` hooks of a component, it is global to the app and
+    /// is invoked once per render after the whole tree is patched. Useful when
+    /// integrating with third-party libraries which need to see the updated
+    /// DOM.
+    ///
+    /// # Example
+    /// ```ignore
+    /// App::<MyApp>::new()
+    ///     .on_render_complete(|| {
+    ///         // The DOM is up to date here.
+    ///     })
+    ///     .mount("app");
+    /// ```
+    pub fn on_render_complete(mut self, callback: impl FnMut() + 'static) -> App<COMP> {
+        self.render_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Registers a transform which is applied to the value of every attribute
+    /// before it is set on an element, e.g. to rewrite the relative URLs or to
+    /// prefix the class names.
+    ///
+    /// Nothing is transformed unless it is registered, so there is no overhead
+    /// otherwise.
+    ///
+    /// # Example
+    /// ```ignore
+    /// App::<MyApp>::new()
+    ///     .set_attribute_transform(|name, value| {
+    ///         if name == "href" && value.starts_with('/') {
+    ///             format!("/my-app{}", value)
+    ///         } else {
+    ///             value.to_string()
+    ///         }
+    ///     })
+    ///     .mount("app");
+    /// ```
+    pub fn set_attribute_transform(mut self, transform: AttributeTransform) -> App<COMP> {
+        self.attribute_transform = Some(transform);
+        self
+    }
+
+    /// Provides a service to all the components of the app, which they get
+    /// with [consume_context](context/fn.consume_context.html) by its type.
+    ///
+    /// A component may still provide a value of the same type to its own
+    /// descendants, overriding the one provided by the app.
+    ///
+    /// # Example
+    /// ```ignore
+    /// App::<MyApp>::new()
+    ///     .provide(ApiClient::new("https://api.example.com"))
+    ///     .provide(Logger::default())
+    ///     .mount("app");
+    /// ```
+    pub fn provide<T: Clone + PartialEq + 'static>(self, service: T) -> App<COMP> {
+        self.scope.borrow_mut().provide(service);
+        self
+    }
+
+    /// Walks the component tree to patch the DOM before the `next` node and
+    /// then notifies that the render is complete.
+    fn render(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        root_parent: Shared<RootParent>,
+        sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        let start = now();
+        let manager = &mut self.manager;
+        context::enter(self.scope.clone());
+        let walked = with_attribute_transform(self.attribute_transform, || {
+            manager.render_walk(parent, next, root_parent, sender)
         });
+        context::exit();
+        walked?;
+        if self.first_render_ms.is_none() {
+            self.first_render_ms = Some(now() - start);
+        }
+        self.notify_render_complete();
+        Ok(())
+    }
+
+    /// Notifies that the render is complete.
+    fn notify_render_complete(&mut self) {
+        if let Some(ref mut render_complete) = self.render_complete {
+            render_complete();
+        }
     }
 }
 
@@ -174,77 +439,495 @@ where
     fn default() -> Self {
         App {
             manager: ComponentWrapper::new((), ()),
+            render_complete: None,
+            attribute_transform: None,
+            scope: Default::default(),
+            first_render_ms: None,
+            schedule: Schedule::default(),
         }
     }
 }
 
-/// Create a `MessageChannel` to propagate state change message to the app.
+/// Configures the options of an app before mounting it.
+///
+/// It is created with [App::builder](struct.App.html#method.builder).
+pub struct AppBuilder<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    app: App<COMP>,
+    hydrate: bool,
+    append: bool,
+}
+
+impl<COMP> AppBuilder<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    /// Whether to adopt the HTML rendered on the server, as
+    /// [App::hydrate](struct.App.html#method.hydrate) does. It takes
+    /// precedence over `append`.
+    pub fn hydrate(mut self, hydrate: bool) -> Self {
+        self.hydrate = hydrate;
+        self
+    }
+
+    /// Whether to mount after the existing children of the element, as
+    /// [App::mount_append](struct.App.html#method.mount_append) does.
+    pub fn append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Sets how the rerenders are scheduled once the state changes. The
+    /// rerenders are run on the animation frames by default.
+    pub fn scheduler(mut self, scheduler: Scheduler) -> Self {
+        self.app.schedule.scheduler = scheduler;
+        self
+    }
+
+    /// Caps the number of rerenders per second, delaying the ones which come
+    /// sooner. A zero removes the cap, which is the default.
+    pub fn max_fps(mut self, fps: u32) -> Self {
+        self.app.schedule.min_interval = if fps == 0 {
+            0.0
+        } else {
+            1000.0 / f64::from(fps)
+        };
+        self
+    }
+
+    /// See [App::on_render_complete](struct.App.html#method.on_render_complete).
+    pub fn on_render_complete(mut self, callback: impl FnMut() + 'static) -> Self {
+        self.app = self.app.on_render_complete(callback);
+        self
+    }
+
+    /// See
+    /// [App::set_attribute_transform](struct.App.html#method.set_attribute_transform).
+    pub fn attribute_transform(mut self, transform: AttributeTransform) -> Self {
+        self.app = self.app.set_attribute_transform(transform);
+        self
+    }
+
+    /// See [App::provide](struct.App.html#method.provide).
+    pub fn provide<T: Clone + PartialEq + 'static>(mut self, service: T) -> Self {
+        self.app = self.app.provide(service);
+        self
+    }
+
+    /// Mounts the app on the given element with the configured options.
+    pub fn mount(self, element: impl AppMount) -> MountedApp<COMP> {
+        if self.hydrate {
+            self.app.hydrate(element)
+        } else if self.append {
+            self.app.mount_append(element)
+        } else {
+            self.app.mount(element)
+        }
+    }
+}
+
+/// An app mounted on the DOM, which keeps on rerendering until it is
+/// unmounted.
+///
+/// It is returned by [App::mount](struct.App.html#method.mount) and the like.
+/// Dropping it leaves the app mounted.
+pub struct MountedApp<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    app: Shared<Option<App<COMP>>>,
+    parent: Element,
+    /// The anchor of an app mounted after the existing children.
+    anchor: Option<Node>,
+    sender: MessageSender,
+    /// The duration of the first render in milliseconds
+    first_render_ms: f64,
+}
+
+impl<COMP> MountedApp<COMP>
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    /// The time in milliseconds taken by the first render of the app when it
+    /// was mounted, i.e. to walk the whole tree and patch it onto the DOM, or
+    /// to adopt the DOM when hydrated.
+    ///
+    /// Measured with `performance.now()`, so it is only as precise as the
+    /// browser allows.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let app = App::<MyApp>::new().mount("app");
+    /// web_sys::console::log_1(&format!("Rendered in {}ms", app.first_render_ms()).into());
+    /// ```
+    pub fn first_render_ms(&self) -> f64 {
+        self.first_render_ms
+    }
+
+    /// Writes the VDOM last rendered by the app, with the markup of every
+    /// component nested within it, to compare it against the `outerHTML` of
+    /// the live DOM while debugging.
+    ///
+    /// The elements are written like structs named by their tag, e.g.
+    /// `div { class: "active", @click: .., child: Text("Hello") }`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let app = App::<MyApp>::new().mount("app");
+    /// web_sys::console::log_1(&app.debug_tree_to_string().into());
+    /// ```
+    pub fn debug_tree_to_string(&self) -> String {
+        self.app
+            .borrow()
+            .as_ref()
+            .map(|app| format!("{:?}", app.manager))
+            .unwrap_or_default()
+    }
+
+    /// Tears down the app. The pending rerender is cancelled, every component
+    /// is destroyed top-down and the nodes of the app are removed from the
+    /// element it is mounted on. The other children of the element are left
+    /// untouched.
+    ///
+    /// The state changes made afterwards are not rendered anymore. It is not
+    /// to be invoked while the app is rendering, e.g. from a lifecycle hook.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let widget = App::<Widget>::new().mount(element);
+    /// // Later, when the element is about to be removed.
+    /// widget.unmount()?;
+    /// ```
+    pub fn unmount(self) -> Result<(), JsValue> {
+        self.sender.close();
+        let app = self.app.borrow_mut().take();
+        if let Some(app) = app {
+            context::enter(app.scope.clone());
+            let removed = app.manager.remove(self.parent.as_ref());
+            context::exit();
+            removed?;
+        }
+        if let Some(ref anchor) = self.anchor {
+            self.parent.remove_child(anchor)?;
+        }
+        Ok(())
+    }
+
+    /// Turns it into a handle which is returned to JavaScript, so that the
+    /// app is unmounted from there.
+    ///
+    /// # Example
+    /// ```ignore
+    /// #[wasm_bindgen]
+    /// pub fn mount_widget(element: Element) -> AppHandle {
+    ///     App::<Widget>::new().mount(element).into_handle()
+    /// }
+    /// ```
+    ///
+    /// ```js
+    /// const widget = mount_widget(element);
+    /// // Later, when the element is about to be removed.
+    /// widget.unmount();
+    /// ```
+    pub fn into_handle(self) -> AppHandle
+    where
+        COMP: 'static,
+    {
+        AppHandle {
+            unmount: Box::new(move || self.unmount()),
+        }
+    }
+}
+
+/// A mounted app of any component, which is unmounted from JavaScript.
+///
+/// It is created with
+/// [MountedApp::into_handle](struct.MountedApp.html#method.into_handle).
+/// Dropping it, or letting it be garbage collected in JavaScript, leaves the
+/// app mounted.
+#[wasm_bindgen]
+pub struct AppHandle {
+    unmount: Box<dyn FnOnce() -> Result<(), JsValue>>,
+}
+
+#[wasm_bindgen]
+impl AppHandle {
+    /// Tears down the app, as in
+    /// [MountedApp::unmount](struct.MountedApp.html#method.unmount). It throws
+    /// if the nodes of the app could not be removed.
+    pub fn unmount(self) -> Result<(), JsValue> {
+        (self.unmount)()
+    }
+}
+
+/// The current time in milliseconds, for measuring the renders.
+fn now() -> f64 {
+    window()
+        .unwrap()
+        .performance()
+        .map_or(0.0, |performance| performance.now())
+}
+
+/// Create a message queue to propagate state change message to the app.
+///
+/// The messages are flushed on the next animation frame, so all the state
+/// changes made in between the frames are batched into a single render.
 fn app_message_channel() -> (MessageReceiver, MessageSender) {
-    let msg_channel = MessageChannel::new().unwrap();
+    app_message_channel_with(Schedule::default())
+}
+
+/// Create a message queue which flushes the messages as scheduled.
+fn app_message_channel_with(schedule: Schedule) -> (MessageReceiver, MessageSender) {
+    let handler: Shared<Option<Box<dyn FnMut()>>> = Rc::new(RefCell::new(None));
     let is_queued = Rc::new(RefCell::new(false));
+    let last_flush = Rc::new(Cell::new(std::f64::NEG_INFINITY));
+
+    let flush: Closure<dyn FnMut(JsValue)> = {
+        let handler = handler.clone();
+        let is_queued = is_queued.clone();
+        let last_flush = last_flush.clone();
+        Closure::wrap(Box::new(move |_| {
+            // Unblock the queue first, so that the state changes made while
+            // rendering are rendered too.
+            *is_queued.borrow_mut() = false;
+            last_flush.set(now());
+            if let Some(ref mut handler) = *handler.borrow_mut() {
+                handler();
+            }
+        }))
+    };
     (
-        MessageReceiver {
-            port: msg_channel.port2(),
-            is_queued: is_queued.clone(),
-        },
+        MessageReceiver { handler },
         MessageSender {
-            port: msg_channel.port1(),
+            flush: Some(Rc::new(flush)),
             is_queued,
+            scheduled: Rc::new(RefCell::new(None)),
+            schedule,
+            last_flush,
         },
     )
 }
 
-/// The receiving end of the message port which notifies the app for any state
-/// changes.
-struct MessageReceiver {
-    port: MessagePort,
-    is_queued: Shared<bool>,
+/// How the rerenders of an app are scheduled once its state changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scheduler {
+    /// Rerenders before the next repaint, so that all the state changes made
+    /// within a frame are rendered at once. It is the default.
+    RequestAnimationFrame,
+    /// Rerenders after a zero timeout, e.g. for an app which keeps on
+    /// rendering in a background tab, where the animation frames are paused.
+    Timeout,
 }
 
-impl MessageReceiver {
-    /// Invokes the handler, when it receives a message.
-    fn react_on_message(self, mut handler: impl FnMut() + 'static) {
-        let is_queued = self.is_queued.clone();
-        let closure: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(move |_| {
-            handler();
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::RequestAnimationFrame
+    }
+}
 
-            // Unblock the queue.
-            *is_queued.borrow_mut() = false;
-        }));
-        self.port
-            .set_onmessage(Some(closure.as_ref().unchecked_ref()));
+/// The options to schedule the rerenders of an app.
+#[derive(Clone, Copy, Default)]
+struct Schedule {
+    scheduler: Scheduler,
+    /// The least time in milliseconds between two rerenders
+    min_interval: f64,
+}
+
+/// A flush scheduled with either of the schedulers.
+enum Scheduled {
+    Frame(i32),
+    Timeout(i32),
+}
+
+/// The receiving end of the message queue which notifies the app for any
+/// state changes.
+struct MessageReceiver {
+    handler: Shared<Option<Box<dyn FnMut()>>>,
+}
 
-        // Leak the closure so that the app lives on for 'static lifetimes.
-        closure.forget();
+impl MessageReceiver {
+    /// Invokes the handler, when the queued messages are flushed.
+    ///
+    /// The handler lives as long as any of the senders, which are held by the
+    /// app itself, so the app lives on for 'static lifetimes.
+    fn react_on_message(self, handler: impl FnMut() + 'static) {
+        *self.handler.borrow_mut() = Some(Box::new(handler));
     }
 }
 
 /// MessageSender is responsible to message the App about state changes.
 #[derive(Clone)]
 struct MessageSender {
-    port: MessagePort,
+    flush: Option<Rc<Closure<dyn FnMut(JsValue)>>>,
     is_queued: Shared<bool>,
+    /// The flush scheduled last
+    scheduled: Shared<Option<Scheduled>>,
+    schedule: Schedule,
+    /// The time the messages were last flushed at
+    last_flush: Rc<Cell<f64>>,
 }
 
 impl MessageSender {
+    /// Creates a sender which does not message anything, for the components
+    /// which are never rerendered i.e. when rendered to a string.
+    fn inert() -> MessageSender {
+        MessageSender {
+            flush: None,
+            is_queued: Rc::new(RefCell::new(false)),
+            scheduled: Rc::new(RefCell::new(None)),
+            schedule: Schedule::default(),
+            last_flush: Rc::new(Cell::new(std::f64::NEG_INFINITY)),
+        }
+    }
+
     /// Sends an update message to the App.
     ///
     /// The components need to call this method when they desire the app to
-    /// be notified of state changes.
+    /// be notified of state changes. Only the first message within a frame
+    /// schedules a flush, the rest are batched along with it.
     fn do_react(&self) {
+        let flush = match self.flush {
+            Some(ref flush) => flush,
+            None => return,
+        };
         let is_queued = *self.is_queued.borrow();
         if !is_queued {
             *self.is_queued.borrow_mut() = true;
-            // Just send a `null` as we have only a single message to be sent.
-            self.port
-                .post_message(&JsValue::null())
-                .expect("Could not send the message");
+            // The flush is run before the next repaint, so that the rapid
+            // state changes are rendered at most once per frame. It also
+            // means that an event listener never has its element removed
+            // from under it while it is being dispatched.
+            let callback: &JsValue = (**flush).as_ref();
+            let window = window().unwrap();
+            // Waits out the rest of the least interval since the last flush,
+            // to cap the frame rate.
+            let delay = self.last_flush.get() + self.schedule.min_interval - now();
+            let scheduled = if self.schedule.scheduler == Scheduler::RequestAnimationFrame
+                && delay <= 0.0
+            {
+                window
+                    .request_animation_frame(callback.unchecked_ref())
+                    .map(Scheduled::Frame)
+            } else {
+                window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        callback.unchecked_ref(),
+                        delay.max(0.0).ceil() as i32,
+                    )
+                    .map(Scheduled::Timeout)
+            };
+            if let Ok(scheduled) = scheduled {
+                *self.scheduled.borrow_mut() = Some(scheduled);
+            }
+        }
+    }
+
+    /// Cancels the queued flush and blocks any more from being queued, once
+    /// the app is unmounted.
+    fn close(&self) {
+        // Never unblocked, as the flush is not run anymore.
+        *self.is_queued.borrow_mut() = true;
+        match self.scheduled.borrow_mut().take() {
+            Some(Scheduled::Frame(handle)) => {
+                let _ = window().unwrap().cancel_animation_frame(handle);
+            }
+            Some(Scheduled::Timeout(handle)) => window().unwrap().clear_timeout_with_handle(handle),
+            None => {}
+        }
+    }
+
+    /// Creates a sender which messages the App only once there are no more
+    /// messages for `ms` milliseconds.
+    fn debounced(&self, ms: u32) -> DebouncedSender {
+        DebouncedSender {
+            sender: self.clone(),
+            ms,
+            timeout: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Creates a sender which messages the App at most once every `ms`
+    /// milliseconds.
+    fn throttled(&self, ms: u32) -> ThrottledSender {
+        ThrottledSender {
+            sender: self.clone(),
+            ms,
+            is_pending: Rc::new(Cell::new(false)),
+            timeout: Rc::new(RefCell::new(None)),
         }
     }
 }
 
+/// A sender which coalesces the rapid-fire state changes, e.g. on every
+/// keystroke, into a single message to the App once they stop for a while.
+///
+/// Get one with `Status::debounced` and keep it in the state of the component,
+/// as the pending message is dropped along with its last clone.
+#[derive(Clone)]
+pub struct DebouncedSender {
+    sender: MessageSender,
+    ms: u32,
+    /// The message waiting for the quiet period to end
+    timeout: Shared<Option<Timeout>>,
+}
+
+impl DebouncedSender {
+    /// Sends an update message to the App after `ms` milliseconds, unless
+    /// another one is sent before that.
+    pub fn do_react(&self) {
+        let sender = self.sender.clone();
+        // Replacing the pending timeout clears it.
+        *self.timeout.borrow_mut() = Some(Timeout::new(self.ms, move || sender.do_react()));
+    }
+}
+
+/// A sender which messages the App at most once in an interval, for the
+/// state changes which happen continuously, e.g. on scroll or resize.
+///
+/// Get one with `Status::throttled` and keep it in the state of the component,
+/// as the pending message is dropped along with its last clone.
+#[derive(Clone)]
+pub struct ThrottledSender {
+    sender: MessageSender,
+    ms: u32,
+    /// Whether a message is waiting for the interval to end
+    is_pending: Rc<Cell<bool>>,
+    timeout: Shared<Option<Timeout>>,
+}
+
+impl ThrottledSender {
+    /// Sends an update message to the App at the end of the current
+    /// interval. The rest of the messages within the interval are batched
+    /// along with it.
+    pub fn do_react(&self) {
+        if self.is_pending.replace(true) {
+            return;
+        }
+        let sender = self.sender.clone();
+        let is_pending = self.is_pending.clone();
+        // The fired timeout is only replaced by the next one, as it must not
+        // be dropped while its callback is running.
+        *self.timeout.borrow_mut() = Some(Timeout::new(self.ms, move || {
+            is_pending.set(false);
+            sender.do_react();
+        }));
+    }
+}
+
 /// A Shared Value.
-type Shared<T> = Rc<RefCell<T>>;
+///
+/// Get a [WeakShared](type.WeakShared.html) of it with `Shared::downgrade`.
+pub type Shared<T> = Rc<RefCell<T>>;
+
+/// A weak reference to a Shared Value, which does not keep it alive.
+///
+/// Capture it instead of the `Shared` in the closures owned by the value
+/// itself, e.g. the one of a `GlobalListener` kept in the state of a
+/// component, so that they do not form a reference cycle. Such a closure
+/// bails when `WeakShared::upgrade` returns `None`.
+pub type WeakShared<T> = Weak<RefCell<T>>;
 
 /// Trait to get an element on which the App is going to be mounted.
 pub trait AppMount {
@@ -262,15 +945,86 @@ impl<'a> AppMount for &'a str {
             .document()
             .unwrap()
             .get_element_by_id(self)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Could not find element with id `{}` to mount the App.",
-                    self
-                )
-            })
+            .unwrap_or_else(|| mount_not_found("id", self))
+    }
+}
+
+/// Mounts the App on the `<body>` of the document.
+///
+/// # Example
+/// ```ignore
+/// App::<MyApp>::new().mount(Body);
+/// ```
+pub struct Body;
+
+impl AppMount for Body {
+    fn app_mount(self) -> Element {
+        window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .body()
+            .expect_msg("Could not find the <body> to mount the App.")
+            .into()
+    }
+}
+
+/// Mounts the App on the first element matching the CSS selector, e.g. a
+/// class, an attribute or a compound selector.
+///
+/// # Example
+/// ```ignore
+/// App::<MyApp>::new().mount(Selector("main [data-widget=\"comments\"]"));
+/// ```
+pub struct Selector<'a>(pub &'a str);
+
+impl<'a> AppMount for Selector<'a> {
+    fn app_mount(self) -> Element {
+        let selector = self.0;
+        window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .query_selector(selector)
+            .unwrap_or_else(|_| invalid_selector(selector))
+            .unwrap_or_else(|| mount_not_found("selector", selector))
     }
 }
 
+/// Fails when no element with the given id or selector exists to mount the
+/// App on.
+#[cfg(not(feature = "minimal"))]
+#[cold]
+fn mount_not_found(by: &str, query: &str) -> ! {
+    panic!(
+        "Could not find element with {} `{}` to mount the App.",
+        by, query
+    )
+}
+
+/// Fails when no element with the given id or selector exists to mount the
+/// App on.
+#[cfg(feature = "minimal")]
+fn mount_not_found(_: &str, _: &str) -> ! {
+    expect::fail("")
+}
+
+/// Fails when the selector to mount the App on is not a valid CSS selector.
+#[cfg(not(feature = "minimal"))]
+#[cold]
+fn invalid_selector(selector: &str) -> ! {
+    panic!(
+        "Could not mount the App on the invalid selector `{}`.",
+        selector
+    )
+}
+
+/// Fails when the selector to mount the App on is not a valid CSS selector.
+#[cfg(feature = "minimal")]
+fn invalid_selector(_: &str) -> ! {
+    expect::fail("")
+}
+
 impl AppMount for Element {
     fn app_mount(self) -> Element {
         self
@@ -288,3 +1042,830 @@ impl AppMount for String {
 fn message_sender() -> MessageSender {
     app_message_channel().1
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::{root_render_ctx, Component, Lifecycle, Status},
+        vdom::{
+            test::container,
+            vcomponent::VComponent,
+            velement::{Attribute, EventListener, VElement},
+            vlist::VList,
+            vtext::VText,
+            VNode,
+        },
+    };
+    use futures::Future;
+    use js_sys::Promise;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Event, HtmlElement};
+
+    /// A future which resolves on the next animation frame, after the
+    /// messages queued before it are flushed.
+    fn next_frame() -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window().unwrap().request_animation_frame(&resolve).unwrap();
+        }))
+    }
+
+    /// A future which resolves after `ms` milliseconds.
+    fn sleep(ms: i32) -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        }))
+    }
+
+    thread_local! {
+        static COUNTER_STATUS: RefCell<Option<Shared<Status<i32>>>> = RefCell::new(None);
+    }
+
+    struct Counter {
+        count: i32,
+        __status: Shared<Status<i32>>,
+    }
+
+    impl Component for Counter {
+        type Props = ();
+        type Events = ();
+        type State = i32;
+
+        fn init(_: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            let status = Rc::new(RefCell::new(status));
+            COUNTER_STATUS.with(|s| *s.borrow_mut() = Some(status.clone()));
+            Counter {
+                count: 0,
+                __status: status,
+            }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            let count = *self.__status.borrow().state_as_ref();
+            let changed = self.count != count;
+            self.count = count;
+            changed
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Lifecycle for Counter {}
+
+    impl Render for Counter {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text(self.count.to_string()))
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_invoke_render_complete_after_dom_is_updated() {
+        let div = container();
+        let rendered = Rc::new(RefCell::new(vec![]));
+        let calls = Rc::new(Cell::new(0));
+
+        let div_clone = div.clone();
+        let rendered_clone = rendered.clone();
+        let calls_clone = calls.clone();
+        let mut app = App::<Counter>::new().on_render_complete(move || {
+            calls_clone.set(calls_clone.get() + 1);
+            rendered_clone.borrow_mut().push(div_clone.inner_html());
+        });
+
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To render the app");
+
+        COUNTER_STATUS.with(|status| {
+            let status = status.borrow();
+            let mut status = status.as_ref().unwrap().borrow_mut();
+            *status.state_as_mut() = 5;
+            status.set_state_dirty(true);
+        });
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To rerender the app");
+
+        assert_eq!(calls.get(), 2);
+        assert_eq!(*rendered.borrow(), vec!["0".to_string(), "5".to_string()]);
+    }
+
+    thread_local! {
+        static CACHE: Cell<i32> = Cell::new(0);
+        static CACHE_VIEW_STATUS: RefCell<Option<Shared<Status<()>>>> = RefCell::new(None);
+    }
+
+    /// Renders the value of a cache it does not own.
+    struct CacheView {
+        __status: Shared<Status<()>>,
+    }
+
+    impl Component for CacheView {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            let status = Rc::new(RefCell::new(status));
+            CACHE_VIEW_STATUS.with(|s| *s.borrow_mut() = Some(status.clone()));
+            CacheView { __status: status }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Lifecycle for CacheView {}
+
+    impl Render for CacheView {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text(CACHE.with(Cell::get).to_string()))
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_rerender_when_the_update_is_forced() {
+        let div = container();
+        let mut app = App::<CacheView>::new();
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To render the app");
+
+        CACHE.with(|cache| cache.set(7));
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To rerender the app");
+        assert_eq!(div.inner_html(), "0");
+
+        CACHE_VIEW_STATUS.with(|status| {
+            status.borrow().as_ref().unwrap().borrow_mut().force_update();
+        });
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To rerender the app");
+        assert_eq!(div.inner_html(), "7");
+    }
+
+    struct Link;
+
+    impl Component for Link {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Link
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Lifecycle for Link {}
+
+    impl Render for Link {
+        fn render(&self) -> Markup<Self> {
+            VElement::childless(
+                "a",
+                vec![
+                    Attribute::new("href", "/docs"),
+                    Attribute::new("class", "link"),
+                ],
+                vec![],
+            )
+            .into()
+        }
+    }
+
+    struct Table;
+
+    impl Component for Table {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Table
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Lifecycle for Table {}
+
+    impl Render for Table {
+        fn render(&self) -> Markup<Self> {
+            let rows: Vec<VNode<Self>> = (0..2000)
+                .map(|index| {
+                    VElement::new(
+                        "tr",
+                        vec![Attribute::new("class", "row")],
+                        vec![],
+                        VElement::new(
+                            "td",
+                            vec![],
+                            vec![],
+                            VText::text(format!("Row {}", index)).into(),
+                        )
+                        .into(),
+                    )
+                    .into()
+                })
+                .collect();
+            VElement::new("table", vec![], vec![], VList::from(rows).into()).into()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_measure_the_first_render() {
+        let div = container();
+        let table = App::<Table>::new().mount(div.clone());
+
+        assert!(table.first_render_ms() > 0.0);
+        table.unmount().unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    fn should_transform_attributes_while_rendering() {
+        let div = container();
+        let mut app = App::<Link>::new().set_attribute_transform(|name, value| {
+            if name == "href" {
+                format!("/base{}", value)
+            } else {
+                value.to_string()
+            }
+        });
+
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To render the app");
+
+        assert_eq!(div.inner_html(), r#"<a href="/base/docs" class="link"></a>"#);
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct Api(&'static str);
+
+    struct Panel;
+
+    impl Component for Panel {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Panel
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Lifecycle for Panel {}
+
+    impl Render for Panel {
+        fn render(&self) -> Markup<Self> {
+            VElement::new(
+                "main",
+                vec![],
+                vec![],
+                VElement::new(
+                    "section",
+                    vec![],
+                    vec![],
+                    VComponent::new::<Endpoint>((), ()).into(),
+                )
+                .into(),
+            )
+            .into()
+        }
+    }
+
+    struct Endpoint;
+
+    impl Component for Endpoint {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Endpoint
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Lifecycle for Endpoint {}
+
+    impl Render for Endpoint {
+        fn render(&self) -> Markup<Self> {
+            let api = context::consume_context::<Api>().map_or("none", |api| api.0);
+            VText::text(api).into()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_provide_services_to_the_descendants() {
+        let div = container();
+        let mut app = App::<Panel>::new().provide(Api("https://api.ruukh.rs"));
+
+        app.render(div.as_ref(), None, root_render_ctx(), message_sender())
+            .expect("To render the app");
+
+        assert_eq!(
+            div.inner_html(),
+            "<main><section>https://api.ruukh.rs</section></main>"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_write_the_rendered_tree() {
+        let panel = App::<Panel>::new().mount(container());
+        assert_eq!(
+            panel.debug_tree_to_string(),
+            r#"Component(main { child: section { child: Component(Text("none")) } })"#
+        );
+        panel.unmount().unwrap();
+
+        let link = App::<Link>::new().mount(container());
+        assert_eq!(
+            link.debug_tree_to_string(),
+            r#"Component(a { href: "/docs", class: "link" })"#
+        );
+        link.unmount().unwrap();
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_batch_messages_into_a_single_flush() -> impl Future<Item = (), Error = JsValue> {
+        let (receiver, sender) = app_message_channel();
+        let flushes = Rc::new(Cell::new(0));
+        let flushes_clone = flushes.clone();
+        receiver.react_on_message(move || flushes_clone.set(flushes_clone.get() + 1));
+
+        for _ in 0..10 {
+            sender.do_react();
+        }
+        assert_eq!(flushes.get(), 0);
+
+        next_frame().map(move |_| {
+            assert_eq!(flushes.get(), 1);
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_message_once_the_debounced_messages_stop() -> impl Future<Item = (), Error = JsValue>
+    {
+        let (receiver, sender) = app_message_channel();
+        let flushes = Rc::new(Cell::new(0));
+        let flushes_clone = flushes.clone();
+        receiver.react_on_message(move || flushes_clone.set(flushes_clone.get() + 1));
+        let debounced = sender.debounced(50);
+
+        for _ in 0..10 {
+            debounced.do_react();
+        }
+
+        next_frame()
+            .and_then(move |_| {
+                assert_eq!(flushes.get(), 0);
+                sleep(100).and_then(|_| next_frame()).map(move |_| flushes)
+            })
+            .map(move |flushes| {
+                assert_eq!(flushes.get(), 1);
+                drop(debounced);
+            })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_message_at_most_once_per_throttled_interval(
+    ) -> impl Future<Item = (), Error = JsValue> {
+        let (receiver, sender) = app_message_channel();
+        let flushes = Rc::new(Cell::new(0));
+        let flushes_clone = flushes.clone();
+        receiver.react_on_message(move || flushes_clone.set(flushes_clone.get() + 1));
+        let throttled = sender.throttled(50);
+
+        for _ in 0..10 {
+            throttled.do_react();
+        }
+
+        sleep(100).and_then(|_| next_frame()).map(move |_| {
+            assert_eq!(flushes.get(), 1);
+            throttled.do_react();
+            assert!(throttled.is_pending.get());
+        })
+    }
+
+    thread_local! {
+        static DISMISS_CLICKS: Cell<i32> = Cell::new(0);
+    }
+
+    struct Dismissible {
+        dismissed: bool,
+        __status: Shared<Status<bool>>,
+    }
+
+    impl Component for Dismissible {
+        type Props = ();
+        type Events = ();
+        type State = bool;
+
+        fn init(_: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Dismissible {
+                dismissed: false,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            let dismissed = *self.__status.borrow().state_as_ref();
+            let changed = self.dismissed != dismissed;
+            self.dismissed = dismissed;
+            changed
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Lifecycle for Dismissible {}
+
+    impl Render for Dismissible {
+        fn render(&self) -> Markup<Self> {
+            if self.dismissed {
+                return VText::text("Dismissed").into();
+            }
+            VElement::new(
+                "div",
+                vec![],
+                vec![],
+                VElement::childless(
+                    "button",
+                    vec![],
+                    vec![EventListener::new(
+                        "click",
+                        Box::new(|this: &Dismissible, _: Event| {
+                            {
+                                let mut status = this.__status.borrow_mut();
+                                *status.state_as_mut() = true;
+                                status.set_state_dirty(true);
+                                status.do_react();
+                            }
+                            // The button is still around after the state
+                            // change which removes it.
+                            DISMISS_CLICKS.with(|clicks| clicks.set(clicks.get() + 1));
+                        }),
+                    )],
+                )
+                .into(),
+            )
+            .into()
+        }
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_remove_the_element_only_after_its_listener_completes(
+    ) -> impl Future<Item = (), Error = JsValue> {
+        let div = container();
+        App::<Dismissible>::new().mount(div.clone());
+        let button: HtmlElement = div
+            .query_selector("button")
+            .unwrap()
+            .unwrap()
+            .dyn_into()
+            .unwrap();
+
+        button.click();
+        assert_eq!(DISMISS_CLICKS.with(Cell::get), 1);
+        assert_eq!(div.inner_html(), "<div><button></button></div>");
+
+        next_frame().map(move |_| {
+            assert_eq!(div.inner_html(), "Dismissed");
+            // It does not listen anymore once removed.
+            button.click();
+            assert_eq!(DISMISS_CLICKS.with(Cell::get), 1);
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_mount_after_the_existing_children() -> impl Future<Item = (), Error = JsValue> {
+        let div = container();
+        div.set_inner_html("<p>Server</p>");
+        let existing = div.first_child().unwrap();
+
+        App::<Counter>::new().mount_append(div.clone());
+        assert_eq!(div.inner_html(), "<p>Server</p>0<!---->");
+
+        // Appended later by a non-Ruukh script.
+        div.append_child(&existing.clone_node().unwrap()).unwrap();
+        COUNTER_STATUS.with(|status| {
+            let status = status.borrow();
+            let mut status = status.as_ref().unwrap().borrow_mut();
+            *status.state_as_mut() = 5;
+            status.set_state_dirty(true);
+            status.do_react();
+        });
+
+        next_frame().map(move |_| {
+            assert_eq!(div.inner_html(), "<p>Server</p>5<!----><p></p>");
+            assert!(div.first_child().unwrap().is_same_node(Some(&existing)));
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn should_mount_on_the_element_matching_the_selector() {
+        let div = container();
+        div.set_attribute("data-widget", "docs").unwrap();
+        let body: Element = window().unwrap().document().unwrap().body().unwrap().into();
+        body.append_child(&div).unwrap();
+
+        let link = App::<Link>::new().mount(Selector("div[data-widget=\"docs\"]"));
+        assert_eq!(div.inner_html(), r#"<a href="/docs" class="link"></a>"#);
+
+        link.unmount().unwrap();
+        body.remove_child(&div).unwrap();
+    }
+
+    thread_local! {
+        static WIDGET_HOOKS: RefCell<Vec<&'static str>> = RefCell::new(vec![]);
+    }
+
+    struct Widget;
+
+    impl Component for Widget {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Widget
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Lifecycle for Widget {
+        fn before_destroy(&self) {
+            WIDGET_HOOKS.with(|hooks| hooks.borrow_mut().push("before_destroy"));
+        }
+
+        fn destroyed(&self) {
+            WIDGET_HOOKS.with(|hooks| hooks.borrow_mut().push("destroyed"));
+        }
+    }
+
+    impl Render for Widget {
+        fn render(&self) -> Markup<Self> {
+            VElement::new("div", vec![], vec![], VText::text("Widget").into()).into()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_unmount_the_app() {
+        let div = container();
+        div.set_inner_html("<p>Server</p>");
+
+        let widget = App::<Widget>::new().mount_append(div.clone());
+        assert_eq!(div.inner_html(), "<p>Server</p><div>Widget</div><!---->");
+
+        widget.unmount().unwrap();
+        assert_eq!(div.inner_html(), "<p>Server</p>");
+        assert_eq!(
+            WIDGET_HOOKS.with(|hooks| hooks.borrow().clone()),
+            vec!["before_destroy", "destroyed"]
+        );
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_not_rerender_once_unmounted() -> impl Future<Item = (), Error = JsValue> {
+        let div = container();
+        let counter = App::<Counter>::new().mount(div.clone());
+        assert_eq!(div.inner_html(), "0");
+
+        // Queues a rerender which is cancelled.
+        COUNTER_STATUS.with(|status| {
+            let status = status.borrow();
+            let mut status = status.as_ref().unwrap().borrow_mut();
+            *status.state_as_mut() = 5;
+            status.set_state_dirty(true);
+            status.do_react();
+        });
+        counter.unmount().unwrap();
+        assert_eq!(div.inner_html(), "");
+
+        next_frame().map(move |_| {
+            assert_eq!(div.inner_html(), "");
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn should_unmount_the_app_through_its_handle() {
+        let div = container();
+        let handle = App::<Counter>::new().mount(div.clone()).into_handle();
+        assert_eq!(div.inner_html(), "0");
+
+        handle.unmount().unwrap();
+        assert_eq!(div.inner_html(), "");
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_mount_an_app_configured_with_the_builder() -> impl Future<Item = (), Error = JsValue>
+    {
+        let div = container();
+        let counter = App::<Counter>::builder()
+            .scheduler(Scheduler::Timeout)
+            .max_fps(30)
+            .mount(div.clone());
+        assert_eq!(div.inner_html(), "0");
+
+        COUNTER_STATUS.with(|status| {
+            let status = status.borrow();
+            let mut status = status.as_ref().unwrap().borrow_mut();
+            *status.state_as_mut() = 5;
+            status.set_state_dirty(true);
+            status.do_react();
+        });
+
+        sleep(100).map(move |_| {
+            assert_eq!(div.inner_html(), "5");
+            counter.unmount().unwrap();
+        })
+    }
+
+    thread_local! {
+        static TICKER_STATUS: RefCell<Option<Shared<Status<i32>>>> = RefCell::new(None);
+    }
+
+    struct Ticker {
+        ticks: i32,
+        __status: Shared<Status<i32>>,
+    }
+
+    impl Component for Ticker {
+        type Props = ();
+        type Events = ();
+        type State = i32;
+
+        const ISOLATED: bool = true;
+
+        fn init(_: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            let status = Rc::new(RefCell::new(status));
+            TICKER_STATUS.with(|s| *s.borrow_mut() = Some(status.clone()));
+            Ticker {
+                ticks: 0,
+                __status: status,
+            }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            let ticks = *self.__status.borrow().state_as_ref();
+            let changed = self.ticks != ticks;
+            self.ticks = ticks;
+            changed
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Lifecycle for Ticker {}
+
+    impl Render for Ticker {
+        fn render(&self) -> Markup<Self> {
+            VText::text(self.ticks.to_string()).into()
+        }
+    }
+
+    struct Dashboard;
+
+    impl Component for Dashboard {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Dashboard
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Lifecycle for Dashboard {}
+
+    impl Render for Dashboard {
+        fn render(&self) -> Markup<Self> {
+            VElement::new(
+                "div",
+                vec![],
+                vec![],
+                VComponent::new::<Ticker>((), ()).into(),
+            )
+            .into()
+        }
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_rerender_only_the_isolated_component() -> impl Future<Item = (), Error = JsValue> {
+        let div = container();
+        let renders = Rc::new(Cell::new(0));
+        let renders_clone = renders.clone();
+        App::<Dashboard>::new()
+            .on_render_complete(move || renders_clone.set(renders_clone.get() + 1))
+            .mount(div.clone());
+        assert_eq!(div.inner_html(), "<div><!---->0<!----></div>");
+
+        TICKER_STATUS.with(|status| {
+            let status = status.borrow();
+            let mut status = status.as_ref().unwrap().borrow_mut();
+            *status.state_as_mut() = 1;
+            status.set_state_dirty(true);
+            status.do_react();
+        });
+
+        next_frame().map(move |_| {
+            assert_eq!(div.inner_html(), "<div><!---->1<!----></div>");
+            // The app did not walk its tree for it.
+            assert_eq!(renders.get(), 1);
+        })
+    }
+}
@@ -0,0 +1,134 @@
+//! Overlays which are rendered outside of the component tree of the App.
+//!
+//! Useful to show toasts, dialogs and the likes imperatively from within the
+//! event handlers. Each overlay is rendered into its own container at the end
+//! of `document.body` and has its own render loop, so that the components
+//! within it are reactive to their own state changes.
+//!
+//! # Example
+//! ```ignore
+//! let handle = overlay::show(html! {
+//!     <div class={"toast"}>"Saved!"</div>
+//! });
+//!
+//! // Later on...
+//! handle.close();
+//! ```
+
+use crate::{
+    app_message_channel, component::RootParent, dom::DOMPatch, expect::ExpectMsg, Markup,
+    MessageSender, Shared,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::prelude::JsValue;
+use web_sys::{window, Element};
+
+/// The handle to an overlay which is being shown. Use it to close the overlay.
+///
+/// Dropping the handle does not close the overlay.
+#[derive(Clone)]
+pub struct OverlayHandle {
+    overlay: Shared<Option<Overlay>>,
+}
+
+struct Overlay {
+    container: Element,
+    markup: Markup<RootParent>,
+}
+
+/// Shows the markup as an overlay on top of the App.
+pub fn show(markup: Markup<RootParent>) -> OverlayHandle {
+    let document = window()
+        .and_then(|window| window.document())
+        .expect_msg("Could not find the document to show the overlay.");
+    let container = document
+        .create_element("div")
+        .expect_msg("Could not create the overlay.");
+    container.set_class_name("ruukh-overlay");
+    document
+        .body()
+        .expect_msg("Could not find the body to show the overlay.")
+        .append_child(container.as_ref())
+        .expect_msg("Could not show the overlay.");
+
+    let (receiver, sender) = app_message_channel();
+    let handle = OverlayHandle {
+        overlay: Rc::new(RefCell::new(Some(Overlay { container, markup }))),
+    };
+
+    // The first render
+    handle
+        .render(sender.clone(), true)
+        .expect_msg("Could not render the overlay.");
+
+    // Rerender when it receives update messages.
+    let cloned = handle.clone();
+    receiver.react_on_message(move || {
+        cloned
+            .render(sender.clone(), false)
+            .expect_msg("Could not rerender the overlay.");
+    });
+
+    handle
+}
+
+impl OverlayHandle {
+    /// Whether the overlay is still being shown.
+    pub fn is_open(&self) -> bool {
+        self.overlay.borrow().is_some()
+    }
+
+    /// Closes the overlay by removing its contents and its container from the
+    /// DOM. Closing an already closed overlay does nothing.
+    pub fn close(&self) {
+        if let Some(overlay) = self.overlay.borrow_mut().take() {
+            overlay
+                .markup
+                .remove(overlay.container.as_ref())
+                .expect_msg("Could not remove the overlay.");
+            overlay.container.remove();
+        }
+    }
+
+    fn render(&self, sender: MessageSender, is_first: bool) -> Result<(), JsValue> {
+        let mut overlay = self.overlay.borrow_mut();
+        // It is closed already, there is nothing to render.
+        let overlay = match *overlay {
+            Some(ref mut overlay) => overlay,
+            None => return Ok(()),
+        };
+
+        // Every component requires a render context, so provided a void context.
+        let root_parent = Rc::new(RefCell::new(()));
+        let parent = overlay.container.as_ref();
+        if is_first {
+            overlay
+                .markup
+                .patch(None, parent, None, root_parent.clone(), sender.clone())?;
+        }
+        overlay.markup.render_walk(parent, None, root_parent, sender)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vdom::{vtext::VText, VNode};
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_show_and_close_an_overlay() {
+        let handle = show(VNode::from(VText::text("Saved!")));
+        let container = handle.overlay.borrow().as_ref().unwrap().container.clone();
+
+        assert!(handle.is_open());
+        assert_eq!(container.inner_html(), "Saved!");
+        assert!(container.parent_node().is_some());
+
+        handle.close();
+
+        assert!(!handle.is_open());
+        assert_eq!(container.inner_html(), "");
+        assert!(container.parent_node().is_none());
+    }
+}
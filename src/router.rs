@@ -0,0 +1,785 @@
+//! Client-side routing built on the [History](../web_api/history/struct.History.html).
+//!
+//! The [Router](struct.Router.html) keeps track of the current location and
+//! rerenders on every navigation. Within it, a [Route](struct.Route.html)
+//! renders only when its path matches the location, a
+//! [Link](struct.Link.html) navigates without reloading the page and a
+//! [Redirect](struct.Redirect.html) navigates as soon as it is mounted. A
+//! [RouteTransition](struct.RouteTransition.html) animates the route which is
+//! left out while the route which is navigated to animates in.
+//!
+//! The Router uses the `History` provided with
+//! [App::provide](../struct.App.html#method.provide), if any, and provides
+//! it to its descendants.
+//!
+//! # Example
+//! ```ignore
+//! let app = VComponent::new::<Router>(
+//!     RouterProps {
+//!         render: Rc::new(|| html! {
+//!             { VComponent::new::<Route>(RouteProps {
+//!                 path: "/users/:id",
+//!                 render: Rc::new(|params| html! {
+//!                     "User "{ params.get("id").unwrap() }
+//!                 }),
+//!             }, ()) }
+//!             { VComponent::new::<Link>(LinkProps {
+//!                 to: "/users/5".to_string(),
+//!                 render: Rc::new(|| html! { "Profile" }),
+//!             }, ()) }
+//!         }),
+//!     },
+//!     (),
+//! );
+//! ```
+
+use crate::{
+    component::{Component, Lifecycle, Render, Status},
+    context::{consume_context, provide_context},
+    expect::ExpectMsg,
+    vdom::{
+        velement::{Attribute, EventListener, VElement},
+        vlist::VList,
+        Key, VNode,
+    },
+    web_api::{
+        history::{History, HistoryListener, Location},
+        EventExt, MouseEvent, Timeout,
+    },
+    Markup, Shared, WeakShared,
+};
+use fnv::FnvBuildHasher;
+use indexmap::IndexMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// The values captured by the `:param` segments of a matched path.
+///
+/// It is provided to the descendants of the matching
+/// [Route](struct.Route.html), so they can consume it as a context.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params(HashMap<String, String, FnvBuildHasher>);
+
+impl Params {
+    /// The value captured by the param, without its leading `:`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+/// Matches the path against the pattern segment by segment, capturing the
+/// `:param` segments of the pattern. Both must have the same number of
+/// segments, ignoring the leading and trailing slashes.
+///
+/// # Example
+/// ```
+/// # use ruukh::router::match_path;
+/// let params = match_path("/users/:id", "/users/5").unwrap();
+/// assert_eq!(params.get("id"), Some("5"));
+/// assert!(match_path("/users/:id", "/users/5/posts").is_none());
+/// ```
+pub fn match_path(pattern: &str, path: &str) -> Option<Params> {
+    let mut patterns = pattern.split('/').filter(|segment| !segment.is_empty());
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let mut params = Params::default();
+    loop {
+        match (patterns.next(), segments.next()) {
+            (None, None) => return Some(params),
+            (Some(pattern), Some(segment)) => {
+                if pattern.starts_with(':') {
+                    params.0.insert(pattern[1..].to_string(), segment.to_string());
+                } else if pattern != segment {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// The props of a [Router](struct.Router.html).
+pub struct RouterProps {
+    /// Renders the routes and links, which may be nested deeper within.
+    pub render: Rc<dyn Fn() -> Markup<Router>>,
+}
+
+/// The state of a [Router](struct.Router.html).
+#[derive(Default)]
+pub struct RouterState {
+    location: Option<Location>,
+}
+
+/// Keeps track of the current location, providing it to the routes within.
+pub struct Router {
+    props: RouterProps,
+    location: Location,
+    history: History,
+    _listener: HistoryListener,
+    status: Shared<Status<RouterState>>,
+}
+
+impl Component for Router {
+    type Props = RouterProps;
+    type Events = ();
+    type State = RouterState;
+
+    fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+        let history = consume_context::<History>().unwrap_or_default();
+        let status = Rc::new(RefCell::new(status));
+
+        // The listener is owned by the router, so it holds the status weakly.
+        let weak: WeakShared<_> = Shared::downgrade(&status);
+        let listener = history.listen(move |location| {
+            let status = match weak.upgrade() {
+                Some(status) => status,
+                None => return,
+            };
+            let mut status = status.borrow_mut();
+            status.state_as_mut().location = Some(location.clone());
+            status.set_state_dirty(true);
+            status.do_react();
+        });
+
+        Router {
+            props,
+            location: history.location(),
+            history,
+            _listener: listener,
+            status,
+        }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        // The render closure cannot be compared, so always rerender.
+        self.status.borrow_mut().set_props_dirty(true);
+        Some(std::mem::replace(&mut self.props, props))
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        let location = self.status.borrow_mut().state_as_mut().location.take();
+        match location {
+            Some(ref location) if *location != self.location => {
+                self.location = location.clone();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        Some(&self.status)
+    }
+}
+
+impl Lifecycle for Router {}
+
+impl Render for Router {
+    fn render(&self) -> Markup<Self> {
+        provide_context(self.history.clone());
+        provide_context(self.location.clone());
+        (self.props.render)()
+    }
+}
+
+/// The props of a [Route](struct.Route.html).
+pub struct RouteProps {
+    /// The pattern of the path to match, e.g. `/users/:id`.
+    pub path: &'static str,
+    /// Renders the content of the route with the captured params.
+    pub render: Rc<dyn Fn(&Params) -> Markup<Route>>,
+}
+
+/// Renders its content only when its path matches the current location of
+/// the [Router](struct.Router.html) it is within.
+pub struct Route {
+    props: RouteProps,
+}
+
+impl Component for Route {
+    type Props = RouteProps;
+    type Events = ();
+    type State = ();
+
+    fn init(props: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+        Route { props }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        Some(std::mem::replace(&mut self.props, props))
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        false
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        None
+    }
+}
+
+impl Lifecycle for Route {}
+
+impl Render for Route {
+    fn render(&self) -> Markup<Self> {
+        let location = consume_context::<Location>()
+            .expect_msg("A Route is expected to be rendered within a Router.");
+        match match_path(self.props.path, &location.pathname) {
+            Some(params) => {
+                let content = (self.props.render)(&params);
+                provide_context(params);
+                content
+            }
+            // Holds its place so that the siblings are not moved around.
+            None => VNode::empty(),
+        }
+    }
+}
+
+/// The props of a [Link](struct.Link.html).
+pub struct LinkProps {
+    /// The path to navigate to.
+    pub to: String,
+    /// Renders the content of the link.
+    pub render: Rc<dyn Fn() -> Markup<Link>>,
+}
+
+/// An anchor which navigates with the `History` of the
+/// [Router](struct.Router.html) it is within, instead of reloading the page.
+///
+/// The clicks with a modifier key are left to the browser, so that the link
+/// can still be opened in a new tab.
+pub struct Link {
+    props: LinkProps,
+    history: History,
+}
+
+impl Link {
+    fn on_click(&self, event: MouseEvent) {
+        if event.button() != 0
+            || event.ctrl_key()
+            || event.meta_key()
+            || event.shift_key()
+            || event.alt_key()
+        {
+            return;
+        }
+        event.prevent_default();
+        let _ = self.history.push(&self.props.to);
+    }
+}
+
+impl Component for Link {
+    type Props = LinkProps;
+    type Events = ();
+    type State = ();
+
+    fn init(props: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+        Link {
+            props,
+            history: consume_context::<History>().unwrap_or_default(),
+        }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        Some(std::mem::replace(&mut self.props, props))
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        false
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        None
+    }
+}
+
+impl Lifecycle for Link {}
+
+impl Render for Link {
+    fn render(&self) -> Markup<Self> {
+        VElement::new(
+            "a",
+            vec![Attribute::new("href", self.props.to.clone())],
+            vec![EventListener::new(
+                "click",
+                Box::new(|this: &Link, event| this.on_click(event)),
+            )],
+            (self.props.render)(),
+        )
+        .into()
+    }
+}
+
+/// The props of a [Redirect](struct.Redirect.html).
+pub struct RedirectProps {
+    /// The path to navigate to.
+    pub to: String,
+}
+
+/// Navigates to its path as soon as it is mounted, replacing the current
+/// entry of the history. Render it within a [Route](struct.Route.html) to
+/// redirect from the path of the route.
+pub struct Redirect {
+    props: RedirectProps,
+    history: History,
+}
+
+impl Component for Redirect {
+    type Props = RedirectProps;
+    type Events = ();
+    type State = ();
+
+    fn init(props: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+        Redirect {
+            props,
+            history: consume_context::<History>().unwrap_or_default(),
+        }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        Some(std::mem::replace(&mut self.props, props))
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        false
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        None
+    }
+}
+
+impl Lifecycle for Redirect {
+    fn mounted(&self) {
+        let _ = self.history.replace(&self.props.to);
+    }
+}
+
+impl Render for Redirect {
+    fn render(&self) -> Markup<Self> {
+        VNode::empty()
+    }
+}
+
+/// A route of a [RouteTransition](struct.RouteTransition.html).
+pub struct TransitionRoute {
+    /// The pattern of the path to match, e.g. `/users/:id`.
+    pub path: &'static str,
+    /// Renders the content of the route with the captured params.
+    pub render: Rc<dyn Fn(&Params) -> Markup<RouteTransition>>,
+}
+
+/// The props of a [RouteTransition](struct.RouteTransition.html).
+pub struct RouteTransitionProps {
+    /// The routes, of which the first one matching the location is rendered.
+    pub routes: Vec<TransitionRoute>,
+    /// The prefix of the classes of the pages, e.g. `page` for the `page`,
+    /// `page-enter` and `page-leave` classes.
+    pub name: &'static str,
+    /// How long the pages animate in & out, in milliseconds.
+    pub duration: u32,
+}
+
+impl RouteTransitionProps {
+    /// The render of the first route matching the path, along with its
+    /// params.
+    fn find(&self, pathname: &str) -> Option<(RenderRoute, Params)> {
+        self.routes.iter().find_map(|route| {
+            match_path(route.path, pathname).map(|params| (route.render.clone(), params))
+        })
+    }
+}
+
+/// Renders the content of a route with the captured params.
+type RenderRoute = Rc<dyn Fn(&Params) -> Markup<RouteTransition>>;
+
+/// The state of a [RouteTransition](struct.RouteTransition.html).
+#[derive(Default)]
+pub struct RouteTransitionState {
+    location: Option<Location>,
+    /// The keys of the pages which are done animating
+    finished: Vec<u64>,
+}
+
+/// The content of a matched route, which is kept till it has animated out.
+struct Page {
+    key: u64,
+    render: RenderRoute,
+    params: Params,
+    phase: Phase,
+    /// Finishes the animation of the page, if it is animating
+    _timeout: Option<Timeout>,
+}
+
+/// Where a page is in its transition.
+#[derive(Clone, Copy, PartialEq)]
+enum Phase {
+    Entering,
+    Entered,
+    Leaving,
+}
+
+/// Renders the route matching the current location of the
+/// [Router](struct.Router.html) it is within, like a set of
+/// [Route](struct.Route.html)s. On a navigation, the content of the route
+/// which is left is kept with the `{name}-leave` class for the `duration`,
+/// while the content of the route which is navigated to is mounted alongside
+/// it with the `{name}-enter` class, so that they are cross-faded with CSS.
+///
+/// Each content is wrapped in a `div` with the `{name}` class once it is done
+/// animating. The params are passed to the `render` of the route, but are not
+/// provided as a context, as the leaving content has its own.
+///
+/// # Example
+/// ```ignore
+/// VComponent::new::<RouteTransition>(
+///     RouteTransitionProps {
+///         routes: vec![
+///             TransitionRoute {
+///                 path: "/",
+///                 render: Rc::new(|_| html! { "Home" }),
+///             },
+///             TransitionRoute {
+///                 path: "/users/:id",
+///                 render: Rc::new(|params| html! { "User "{ params.get("id").unwrap() } }),
+///             },
+///         ],
+///         name: "page",
+///         duration: 300,
+///     },
+///     (),
+/// )
+/// ```
+///
+/// ```css
+/// .page-enter { animation: fade-in 300ms; }
+/// .page-leave { animation: fade-out 300ms; position: absolute; }
+/// ```
+pub struct RouteTransition {
+    props: RouteTransitionProps,
+    pathname: String,
+    /// The page of the current location, if any route matches it
+    current: Option<Page>,
+    /// The pages which are animating out, the oldest first
+    leaving: Vec<Page>,
+    next_key: u64,
+    _listener: HistoryListener,
+    status: Shared<Status<RouteTransitionState>>,
+}
+
+impl RouteTransition {
+    /// Creates the page of the route matching the path, if any.
+    fn page(&mut self, pathname: &str, phase: Phase) -> Option<Page> {
+        let (render, params) = self.props.find(pathname)?;
+        let key = self.next_key;
+        self.next_key += 1;
+        let timeout = match phase {
+            Phase::Entered => None,
+            _ => Some(self.finish_after_duration(key)),
+        };
+        Some(Page {
+            key,
+            render,
+            params,
+            phase,
+            _timeout: timeout,
+        })
+    }
+
+    /// Marks the page as done animating once the duration has passed.
+    fn finish_after_duration(&self, key: u64) -> Timeout {
+        // The timeout is owned by the component, so it holds the status
+        // weakly.
+        let weak: WeakShared<_> = Shared::downgrade(&self.status);
+        Timeout::new(self.props.duration, move || {
+            let status = match weak.upgrade() {
+                Some(status) => status,
+                None => return,
+            };
+            let mut status = status.borrow_mut();
+            status.state_as_mut().finished.push(key);
+            status.set_state_dirty(true);
+            status.do_react();
+        })
+    }
+}
+
+impl Component for RouteTransition {
+    type Props = RouteTransitionProps;
+    type Events = ();
+    type State = RouteTransitionState;
+
+    fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+        let history = consume_context::<History>().unwrap_or_default();
+        let status = Rc::new(RefCell::new(status));
+
+        let weak: WeakShared<_> = Shared::downgrade(&status);
+        let listener = history.listen(move |location| {
+            let status = match weak.upgrade() {
+                Some(status) => status,
+                None => return,
+            };
+            let mut status = status.borrow_mut();
+            status.state_as_mut().location = Some(location.clone());
+            status.set_state_dirty(true);
+            status.do_react();
+        });
+
+        let pathname = history.location().pathname;
+        let mut transition = RouteTransition {
+            props,
+            pathname: pathname.clone(),
+            current: None,
+            leaving: vec![],
+            next_key: 0,
+            _listener: listener,
+            status,
+        };
+        // Nothing animates on the first render.
+        transition.current = transition.page(&pathname, Phase::Entered);
+        transition
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        // The render closures cannot be compared, so always rerender.
+        self.status.borrow_mut().set_props_dirty(true);
+        let old = std::mem::replace(&mut self.props, props);
+        // The current page renders with the new routes, whereas the leaving
+        // pages keep rendering as they were left.
+        if let Some(ref mut page) = self.current {
+            if let Some((render, params)) = self.props.find(&self.pathname) {
+                page.render = render;
+                page.params = params;
+            }
+        }
+        Some(old)
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        let (location, finished) = {
+            let mut status = self.status.borrow_mut();
+            let state = status.state_as_mut();
+            (state.location.take(), std::mem::replace(&mut state.finished, vec![]))
+        };
+        let mut changed = !finished.is_empty();
+        self.leaving.retain(|page| !finished.contains(&page.key));
+        if let Some(ref mut page) = self.current {
+            if finished.contains(&page.key) {
+                page.phase = Phase::Entered;
+                page._timeout = None;
+            }
+        }
+
+        match location {
+            Some(ref location) if location.pathname != self.pathname => {
+                self.pathname = location.pathname.clone();
+                if let Some(mut page) = self.current.take() {
+                    page.phase = Phase::Leaving;
+                    page._timeout = Some(self.finish_after_duration(page.key));
+                    self.leaving.push(page);
+                }
+                let pathname = self.pathname.clone();
+                self.current = self.page(&pathname, Phase::Entering);
+                changed = true;
+            }
+            _ => {}
+        }
+        changed
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        Some(&self.status)
+    }
+}
+
+impl Lifecycle for RouteTransition {}
+
+impl Render for RouteTransition {
+    fn render(&self) -> Markup<Self> {
+        let pages: VList<Self> = self
+            .leaving
+            .iter()
+            .chain(self.current.iter())
+            .map(|page| {
+                let class = match page.phase {
+                    Phase::Entering => format!("{}-enter", self.props.name),
+                    Phase::Entered => self.props.name.to_string(),
+                    Phase::Leaving => format!("{}-leave", self.props.name),
+                };
+                let content = VElement::new(
+                    "div",
+                    vec![Attribute::new("class", class)],
+                    vec![],
+                    (page.render)(&page.params),
+                );
+                (Key::new(page.key), VNode::from(content))
+            })
+            .collect::<IndexMap<_, _, FnvBuildHasher>>()
+            .into();
+        pages.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        dom::DOMPatch,
+        vdom::{test::container, vcomponent::VComponent, vtext::VText},
+    };
+    use futures::Future;
+    use js_sys::Promise;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::*;
+    use web_sys::{window, Event};
+
+    /// A future which resolves after `ms` milliseconds.
+    fn sleep(ms: i32) -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        }))
+    }
+
+    #[wasm_bindgen_test]
+    fn should_match_the_path_segment_by_segment() {
+        let params = match_path("/users/:id/posts/:post", "/users/5/posts/7/").unwrap();
+        assert_eq!(params.get("id"), Some("5"));
+        assert_eq!(params.get("post"), Some("7"));
+
+        assert_eq!(match_path("/", "/"), Some(Params::default()));
+        assert!(match_path("/users", "/users/5").is_none());
+        assert!(match_path("/users/:id", "/posts/5").is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_render_only_the_matching_routes() {
+        let history = History::new();
+        let original = history.location();
+        history.replace("/users/5").unwrap();
+
+        let route = |path, name: &'static str| -> VNode<Router> {
+            VComponent::new::<Route>(
+                RouteProps {
+                    path,
+                    render: Rc::new(move |params| {
+                        VText::text(format!("{} {}", name, params.get("id").unwrap_or(""))).into()
+                    }),
+                },
+                (),
+            )
+            .into()
+        };
+        let mut router: VNode<()> = VComponent::new::<Router>(
+            RouterProps {
+                render: Rc::new(move || {
+                    VList::from(vec![
+                        route("/", "Home"),
+                        route("/users/:id", "User"),
+                        VComponent::new::<Link>(
+                            LinkProps {
+                                to: "/".to_string(),
+                                render: Rc::new(|| VText::text("Home").into()),
+                            },
+                            (),
+                        )
+                        .into(),
+                    ])
+                    .into()
+                }),
+            },
+            (),
+        )
+        .into();
+        let div = container();
+        router
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        router
+            .render_walk(div.as_ref(), None, root_render_ctx(), crate::message_sender())
+            .expect("To render div");
+
+        assert_eq!(div.inner_html(), r#"<!---->User 5<a href="/">Home</a>"#);
+
+        let url = format!("{}{}{}", original.pathname, original.search, original.hash);
+        history.replace(&url).unwrap();
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_keep_the_left_route_while_it_animates_out() -> impl Future<Item = (), Error = JsValue>
+    {
+        let history = History::new();
+        let original = history.location();
+        history.replace("/a").unwrap();
+
+        let route = |path, text: &'static str| TransitionRoute {
+            path,
+            render: Rc::new(move |_| VText::text(text).into()),
+        };
+        let mut router: VNode<()> = VComponent::new::<Router>(
+            RouterProps {
+                render: Rc::new(move || {
+                    VComponent::new::<RouteTransition>(
+                        RouteTransitionProps {
+                            routes: vec![route("/a", "A"), route("/b", "B")],
+                            name: "page",
+                            duration: 50,
+                        },
+                        (),
+                    )
+                    .into()
+                }),
+            },
+            (),
+        )
+        .into();
+        let div = container();
+        router
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        router
+            .render_walk(div.as_ref(), None, root_render_ctx(), crate::message_sender())
+            .expect("To render div");
+        assert_eq!(div.inner_html(), r#"<div class="page">A</div>"#);
+
+        // Navigates as the browser does, so that the History of the Router is
+        // notified.
+        history.replace("/b").unwrap();
+        window()
+            .unwrap()
+            .dispatch_event(&Event::new("popstate").unwrap())
+            .unwrap();
+        router
+            .render_walk(div.as_ref(), None, root_render_ctx(), crate::message_sender())
+            .expect("To render div");
+        assert_eq!(
+            div.inner_html(),
+            r#"<div class="page-leave">A</div><div class="page-enter">B</div>"#
+        );
+
+        sleep(100).map(move |_| {
+            router
+                .render_walk(div.as_ref(), None, root_render_ctx(), crate::message_sender())
+                .expect("To render div");
+            assert_eq!(div.inner_html(), r#"<div class="page">B</div>"#);
+
+            let url = format!("{}{}{}", original.pathname, original.search, original.hash);
+            history.replace(&url).unwrap();
+        })
+    }
+}
@@ -0,0 +1,217 @@
+//! A store of the state shared across the app, which is changed only by
+//! dispatching actions to its reducer.
+//!
+//! Provide the [Store](struct.Store.html) to the whole app with
+//! [App::provide](../struct.App.html#method.provide), then subscribe to it in
+//! the components which render its state.
+//!
+//! # Example
+//! ```ignore
+//! enum Action {
+//!     Increment,
+//!     Reset,
+//! }
+//!
+//! let store = Store::new(0, |count: &i32, action| match action {
+//!     Action::Increment => count + 1,
+//!     Action::Reset => 0,
+//! });
+//! App::<MyApp>::new().provide(store).mount("app");
+//!
+//! impl Lifecycle for Badge {
+//!     fn created(&self) {
+//!         let store = consume_context::<Store<i32, Action>>().unwrap();
+//!         let setter = self.state_setter();
+//!         let subscription = store.select(
+//!             |count| *count > 99,
+//!             move |overflows| setter.set_state(|state| state.overflows = *overflows),
+//!         );
+//!         self.set_state(|state| state.subscription = Some(subscription.clone()));
+//!     }
+//! }
+//! ```
+
+use crate::Shared;
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+/// The subscribers of a store along with their ids.
+type Subscribers<S> = RefCell<Vec<(usize, Rc<dyn Fn(&S)>)>>;
+
+/// A state which is changed by the reducer on every dispatched action, which
+/// then notifies its subscribers.
+///
+/// Clones share the same state, and are equal only to each other.
+pub struct Store<S, A>(Rc<Inner<S, A>>);
+
+/// The state of a `Store` which is shared by its clones.
+struct Inner<S, A> {
+    state: Shared<S>,
+    reducer: Box<dyn Fn(&S, A) -> S>,
+    subscribers: Rc<Subscribers<S>>,
+    /// The id of the next subscriber
+    next_id: Cell<usize>,
+}
+
+impl<S: Clone + 'static, A> Store<S, A> {
+    /// Create a store with the initial state and the reducer which derives the
+    /// next state from the current one and an action.
+    pub fn new(state: S, reducer: impl Fn(&S, A) -> S + 'static) -> Store<S, A> {
+        Store(Rc::new(Inner {
+            state: Rc::new(RefCell::new(state)),
+            reducer: Box::new(reducer),
+            subscribers: Rc::new(RefCell::new(vec![])),
+            next_id: Cell::new(0),
+        }))
+    }
+
+    /// The current state.
+    pub fn state(&self) -> S {
+        self.0.state.borrow().clone()
+    }
+
+    /// Applies the reducer on the action and notifies the subscribers of the
+    /// next state.
+    pub fn dispatch(&self, action: A) {
+        let next = (self.0.reducer)(&self.0.state.borrow(), action);
+        *self.0.state.borrow_mut() = next.clone();
+
+        // The subscribers may subscribe or dispatch more.
+        let subscribers = self.0.subscribers.borrow().clone();
+        for (_, subscriber) in subscribers.iter() {
+            subscriber(&next);
+        }
+    }
+
+    /// Registers a listener which is invoked with the next state on every
+    /// dispatch, until the returned subscription is dropped along with all of
+    /// its clones.
+    pub fn subscribe(&self, listener: impl Fn(&S) + 'static) -> StoreSubscription {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        self.0
+            .subscribers
+            .borrow_mut()
+            .push((id, Rc::new(listener)));
+
+        let subscribers: Weak<Subscribers<S>> = Rc::downgrade(&self.0.subscribers);
+        StoreSubscription(Rc::new(Subscribed {
+            unsubscribe: Box::new(move || {
+                if let Some(subscribers) = subscribers.upgrade() {
+                    subscribers.borrow_mut().retain(|(other, _)| *other != id);
+                }
+            }),
+        }))
+    }
+
+    /// Registers a listener which is invoked with the value derived from the
+    /// next state, only when it differs from the one derived before.
+    pub fn select<T: PartialEq + 'static>(
+        &self,
+        selector: impl Fn(&S) -> T + 'static,
+        listener: impl Fn(&T) + 'static,
+    ) -> StoreSubscription {
+        let selected = RefCell::new(selector(&self.0.state.borrow()));
+        self.subscribe(move |state| {
+            let next = selector(state);
+            if *selected.borrow() != next {
+                *selected.borrow_mut() = next;
+                listener(&*selected.borrow());
+            }
+        })
+    }
+}
+
+impl<S, A> Clone for Store<S, A> {
+    fn clone(&self) -> Self {
+        Store(self.0.clone())
+    }
+}
+
+impl<S, A> PartialEq for Store<S, A> {
+    /// Stores are equal only when they are the clones of the same Store.
+    fn eq(&self, other: &Store<S, A>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A listener registered with `Store::subscribe` or `Store::select`, which is
+/// unregistered once it is dropped along with all of its clones.
+///
+/// Keep it in the state of the component, so that the listener is
+/// unregistered when the component is destroyed.
+#[derive(Clone)]
+pub struct StoreSubscription(Rc<Subscribed>);
+
+/// The registration of a `StoreSubscription` which is shared by its clones.
+struct Subscribed {
+    unsubscribe: Box<dyn Fn()>,
+}
+
+impl PartialEq for StoreSubscription {
+    /// StoreSubscriptions are equal only when they are the clones of the same
+    /// StoreSubscription.
+    fn eq(&self, other: &StoreSubscription) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Subscribed {
+    fn drop(&mut self) {
+        (self.unsubscribe)();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    enum Action {
+        Add(i32),
+        Reset,
+    }
+
+    fn counter() -> Store<i32, Action> {
+        Store::new(0, |count: &i32, action| match action {
+            Action::Add(by) => count + by,
+            Action::Reset => 0,
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn should_notify_the_subscribers_until_unsubscribed() {
+        let store = counter();
+        let notified = Rc::new(RefCell::new(vec![]));
+        let notified_clone = notified.clone();
+        let subscription = store.subscribe(move |count| notified_clone.borrow_mut().push(*count));
+
+        store.dispatch(Action::Add(2));
+        store.dispatch(Action::Add(3));
+        assert_eq!(store.state(), 5);
+
+        drop(subscription);
+        store.dispatch(Action::Reset);
+        assert_eq!(store.state(), 0);
+        assert_eq!(*notified.borrow(), vec![2, 5]);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_notify_the_selectors_only_on_change() {
+        let store = counter();
+        let notified = Rc::new(RefCell::new(vec![]));
+        let notified_clone = notified.clone();
+        let _subscription = store.select(
+            |count| *count >= 10,
+            move |overflows| notified_clone.borrow_mut().push(*overflows),
+        );
+
+        store.dispatch(Action::Add(4));
+        store.dispatch(Action::Add(8));
+        store.dispatch(Action::Add(1));
+        store.dispatch(Action::Reset);
+        assert_eq!(*notified.borrow(), vec![true, false]);
+    }
+}
@@ -0,0 +1,517 @@
+//! Utilities to test the markup rendered by components without a DOM.
+//!
+//! They are available in the tests of Ruukh itself and, for the tests of your
+//! app, with the `test-utils` feature on.
+//!
+//! # Example
+//! ```ignore
+//! let counter = Counter::init(CounterProps!(), CounterEvents!(), status);
+//! let markup = counter.render();
+//!
+//! assert!(has_listener(&markup, "click"));
+//! trigger_listener(&markup, &counter, "click", Event::new("click").unwrap());
+//! ```
+//!
+//! The output of a single render can be queried for its elements and nested
+//! components, which are not rendered themselves.
+//!
+//! ```ignore
+//! let output = ShallowRenderer::<Toolbar>::render(ToolbarProps!(saved: false));
+//! assert_eq!(output.find("button.primary").len(), 1);
+//! assert_eq!(output.props_of::<Badge>().count, 3);
+//! ```
+//!
+//! The whole markup can also be compared against a snapshot stored in a
+//! file, which needs no DOM either.
+//!
+//! ```ignore
+//! let snapshot = render_to_snapshot::<Card>(CardProps!(title: "Hello"), Default::default());
+//! assert_eq!(snapshot, include_str!("card.snap"));
+//! ```
+
+use crate::{
+    component::{FromEventProps, Render, Status},
+    context::{self, Scope},
+    vdom::{
+        ssr::{self, ToHtml},
+        vcomponent::VComponent,
+        velement::{AttributeValue, VElement},
+        VNode,
+    },
+    Markup, MessageSender,
+};
+use std::{cell::RefCell, marker::PhantomData, rc::Rc};
+use web_sys::Event;
+
+/// Renders a component once without a DOM, leaving the nested components
+/// unrendered so that only its own render logic is tested.
+///
+/// Any component is rendered with
+/// [render_with_events](#method.render_with_events), whereas
+/// [render](#method.render) takes the props alone, so it is only for the
+/// components without events.
+pub struct ShallowRenderer<COMP>(PhantomData<COMP>);
+
+impl<COMP: Render> ShallowRenderer<COMP> {
+    /// Renders the component with the props and events. None of the lifecycle
+    /// hooks are invoked.
+    pub fn render_with_events(props: COMP::Props, events: COMP::Events) -> RenderedOutput<COMP> {
+        let component = COMP::init(
+            props,
+            events,
+            Status::new(COMP::State::default(), MessageSender::inert()),
+        );
+        context::enter(Rc::new(RefCell::new(Scope::default())));
+        let markup = component.render();
+        context::exit();
+        RenderedOutput { component, markup }
+    }
+}
+
+impl<COMP: Render<Events = ()>> ShallowRenderer<COMP> {
+    /// Renders the component with the props. None of the lifecycle hooks are
+    /// invoked.
+    pub fn render(props: COMP::Props) -> RenderedOutput<COMP> {
+        ShallowRenderer::render_with_events(props, ())
+    }
+}
+
+/// The markup rendered by a `ShallowRenderer` along with the component which
+/// rendered it.
+pub struct RenderedOutput<COMP> {
+    component: COMP,
+    markup: Markup<COMP>,
+}
+
+impl<COMP: Render> RenderedOutput<COMP> {
+    /// The component which rendered the markup, to trigger its listeners.
+    pub fn component(&self) -> &COMP {
+        &self.component
+    }
+
+    /// The rendered markup.
+    pub fn markup(&self) -> &Markup<COMP> {
+        &self.markup
+    }
+
+    /// Finds the elements, in document order, which match a selector like
+    /// `button`, `.primary`, `#save`, `[disabled]` or `[type="text"]`, or a
+    /// compound of them like `button.primary[disabled]`. Combinators like
+    /// `div > button` are not supported.
+    pub fn find(&self, selector: &str) -> Vec<&VElement<COMP>> {
+        let selector = Selector::parse(selector);
+        self.nodes()
+            .into_iter()
+            .filter_map(|node| match node {
+                VNode::Element(el) if selector.matches(el) => Some(el),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Finds the nested components of the type `C`, in document order.
+    pub fn find_component<C: Render>(&self) -> Vec<&VComponent<COMP>>
+    where
+        C::Events: FromEventProps<COMP>,
+    {
+        self.nodes()
+            .into_iter()
+            .filter_map(|node| match node {
+                VNode::Component(comp) if comp.props_of::<C>().is_some() => Some(comp),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The props passed to the first nested component of the type `C`.
+    ///
+    /// # Panics
+    /// Panics if no component of the type is rendered.
+    pub fn props_of<C: Render>(&self) -> &C::Props
+    where
+        C::Events: FromEventProps<COMP>,
+    {
+        self.nodes()
+            .into_iter()
+            .filter_map(|node| match node {
+                VNode::Component(comp) => comp.props_of::<C>(),
+                _ => None,
+            })
+            .next()
+            .expect("No component of the type is rendered.")
+    }
+
+    /// All the rendered nodes in document order.
+    fn nodes(&self) -> Vec<&VNode<COMP>> {
+        let mut nodes = vec![];
+        self.markup.collect_nodes(&mut nodes);
+        nodes
+    }
+}
+
+/// A compound selector of the tag, ids, classes and attributes of an element.
+#[derive(Debug, Default, PartialEq)]
+struct Selector {
+    tag: Option<String>,
+    ids: Vec<String>,
+    classes: Vec<String>,
+    /// The attributes to be present, along with the values to be equal to
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Selector {
+        let mut parsed = Selector::default();
+        let mut rest = selector.trim();
+
+        let is_prefix = |c: char| c == '.' || c == '#' || c == '[';
+        let tag_len = rest.find(is_prefix).unwrap_or_else(|| rest.len());
+        if tag_len > 0 {
+            parsed.tag = Some(rest[..tag_len].to_string());
+        }
+        rest = &rest[tag_len..];
+
+        while let Some(prefix) = rest.chars().next() {
+            if prefix == '[' {
+                let end = rest.find(']').unwrap_or_else(|| rest.len());
+                let attribute = &rest[1..end];
+                parsed.attributes.push(match attribute.find('=') {
+                    Some(eq) => {
+                        let value = attribute[eq + 1..].trim().trim_matches(|c| c == '"');
+                        (attribute[..eq].trim().to_string(), Some(value.to_string()))
+                    }
+                    None => (attribute.trim().to_string(), None),
+                });
+                rest = rest.get(end + 1..).unwrap_or("");
+            } else {
+                let end = rest[1..].find(is_prefix).map_or(rest.len(), |end| end + 1);
+                let name = rest[1..end].to_string();
+                if prefix == '#' {
+                    parsed.ids.push(name);
+                } else {
+                    parsed.classes.push(name);
+                }
+                rest = &rest[end..];
+            }
+        }
+        parsed
+    }
+
+    fn matches<RCTX>(&self, el: &VElement<RCTX>) -> bool {
+        let value = |key: &str| match el.attribute(key) {
+            Some(AttributeValue::String(value)) => Some(value.clone()),
+            Some(AttributeValue::Bool(true)) => Some(String::new()),
+            Some(AttributeValue::Style(props)) => Some(props.to_string()),
+            Some(AttributeValue::Classes(classes)) => Some(classes.to_string()),
+            _ => None,
+        };
+        let classes = value("class").unwrap_or_default();
+        self.tag.as_ref().map_or(true, |tag| tag == el.tag())
+            && self.ids.iter().all(|id| value("id").as_ref() == Some(id))
+            && self
+                .classes
+                .iter()
+                .all(|class| classes.split_whitespace().any(|c| c == class))
+            && self.attributes.iter().all(|(key, expected)| match value(key) {
+                Some(value) => expected.as_ref().map_or(true, |expected| *expected == value),
+                None => false,
+            })
+    }
+}
+
+/// How the components nested in a snapshot are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SnapshotOptions {
+    /// Whether the markup of the nested components is written within their
+    /// boundaries, e.g. `<!-- <Button> --><button>..</button><!-- </Button> -->`.
+    /// Otherwise only a `<!-- <Button> -->` stub is written for them.
+    pub deep: bool,
+}
+
+/// Whether any element in the markup listens to the event type. The elements
+/// rendered by the nested components are not searched.
+pub fn has_listener<RCTX>(markup: &Markup<RCTX>, type_: &str) -> bool {
+    markup.find_listening(type_).is_some()
+}
+
+/// Invokes the listeners of the event type on the first element in the markup
+/// which listens to it, with the component which rendered the markup. Returns
+/// whether any listener was invoked.
+///
+/// Only the markup which is not yet patched onto the DOM can be triggered, as
+/// the listeners are handed over to the DOM once patched.
+pub fn trigger_listener<RCTX>(
+    markup: &Markup<RCTX>,
+    render_ctx: &RCTX,
+    type_: &str,
+    event: Event,
+) -> bool {
+    markup
+        .find_listening(type_)
+        .map_or(false, |el| el.trigger(type_, render_ctx, event))
+}
+
+/// Renders the component with the props to an HTML-like string, with the
+/// nested components marked by their boundaries. None of the lifecycle hooks
+/// are invoked.
+///
+/// The snapshot is meant to be stored in a `.snap` file and compared in the
+/// tests, so that any change in the markup is noticed.
+pub fn render_to_snapshot<COMP>(props: COMP::Props, options: SnapshotOptions) -> String
+where
+    COMP: Render<Events = ()>,
+{
+    let instance = COMP::init(
+        props,
+        (),
+        Status::new(COMP::State::default(), MessageSender::inert()),
+    );
+    let mut html = String::new();
+    context::enter(Rc::new(RefCell::new(Scope::default())));
+    ssr::with_component_markers(options.deep, || {
+        let mut markup = instance.render();
+        markup.to_html(&mut html, Rc::new(RefCell::new(instance)));
+    });
+    context::exit();
+    html
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::{Component, Lifecycle},
+        vdom::{velement::EventListener, vtext::VText},
+        Shared,
+    };
+    use wasm_bindgen_test::*;
+
+    struct Clicker {
+        count: i32,
+        __status: Shared<Status<i32>>,
+    }
+
+    impl Lifecycle for Clicker {}
+
+    impl Component for Clicker {
+        type Props = ();
+        type Events = ();
+        type State = i32;
+
+        fn init(_: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Clicker {
+                count: 0,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            let count = *self.__status.borrow().state_as_ref();
+            let changed = self.count != count;
+            self.count = count;
+            changed
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Render for Clicker {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "button",
+                vec![],
+                vec![EventListener::new(
+                    "click",
+                    Box::new(|this: &Clicker, _: Event| {
+                        let mut status = this.__status.borrow_mut();
+                        *status.state_as_mut() += 1;
+                        status.set_state_dirty(true);
+                    }),
+                )],
+                VNode::from(VText::text(self.count.to_string())),
+            ))
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_find_listeners_of_the_markup() {
+        let clicker = Clicker::init((), (), Status::new(0, crate::message_sender()));
+        let markup = clicker.render();
+
+        assert!(has_listener(&markup, "click"));
+        assert!(!has_listener(&markup, "input"));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_update_state_when_triggering_a_listener() {
+        let mut clicker = Clicker::init((), (), Status::new(0, crate::message_sender()));
+        let markup = clicker.render();
+
+        let event = Event::new("click").unwrap();
+        assert!(trigger_listener(&markup, &clicker, "click", event));
+        assert!(clicker.__status.borrow().is_state_dirty());
+
+        assert!(clicker.refresh_state());
+        assert_eq!(clicker.count, 1);
+    }
+
+    struct Card {
+        title: &'static str,
+    }
+
+    impl Lifecycle for Card {}
+
+    impl Component for Card {
+        type Props = &'static str;
+        type Events = ();
+        type State = ();
+
+        fn init(title: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Card { title }
+        }
+
+        fn update(&mut self, title: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            Some(std::mem::replace(&mut self.title, title))
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Card {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "div",
+                vec![],
+                vec![],
+                vec![
+                    VNode::from(VElement::new(
+                        "h1",
+                        vec![],
+                        vec![],
+                        VNode::from(VText::text(self.title)),
+                    )),
+                    VNode::from(VComponent::new::<Clicker>((), ())),
+                ]
+                .into(),
+            ))
+        }
+    }
+
+    #[test]
+    fn should_render_a_snapshot_with_the_nested_components() {
+        let shallow = render_to_snapshot::<Card>("Hello", SnapshotOptions::default());
+        assert_eq!(shallow, "<div><h1>Hello</h1><!-- <Clicker> --></div>");
+
+        let deep = render_to_snapshot::<Card>("Hello", SnapshotOptions { deep: true });
+        assert_eq!(
+            deep,
+            "<div><h1>Hello</h1><!-- <Clicker> --><button>0</button><!-- </Clicker> --></div>"
+        );
+    }
+
+    struct Toolbar;
+
+    impl Lifecycle for Toolbar {}
+
+    impl Component for Toolbar {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Toolbar
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Toolbar {
+        fn render(&self) -> Markup<Self> {
+            VElement::from_tag("nav")
+                .child(
+                    VElement::from_tag("button")
+                        .attr("id", "save")
+                        .attr("class", "primary large")
+                        .attr("disabled", true),
+                )
+                .child(VElement::from_tag("button").attr("type", "reset"))
+                .child(VComponent::new::<Card>("Saved", ()))
+                .into()
+        }
+    }
+
+    #[test]
+    fn should_parse_compound_selectors() {
+        assert_eq!(
+            Selector::parse(r#"button.primary#save[type="submit"][disabled]"#),
+            Selector {
+                tag: Some("button".to_string()),
+                ids: vec!["save".to_string()],
+                classes: vec!["primary".to_string()],
+                attributes: vec![
+                    ("type".to_string(), Some("submit".to_string())),
+                    ("disabled".to_string(), None),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn should_find_elements_and_components_in_a_shallow_render() {
+        let output = ShallowRenderer::<Toolbar>::render(());
+
+        assert_eq!(output.find("button").len(), 2);
+        assert_eq!(output.find("button.primary.large#save[disabled]").len(), 1);
+        assert_eq!(output.find(r#"[type="reset"]"#)[0].tag(), "button");
+        assert!(output.find("nav.primary").is_empty());
+
+        assert_eq!(output.find_component::<Card>().len(), 1);
+        assert!(output.find_component::<Clicker>().is_empty());
+        assert_eq!(*output.props_of::<Card>(), "Saved");
+    }
+
+    #[test]
+    fn should_write_the_render_output_deterministically() {
+        let output = ShallowRenderer::<Toolbar>::render(());
+        assert_eq!(
+            output.markup().to_test_html(),
+            "<nav>\
+             <button class=\"primary large\" disabled id=\"save\"></button>\
+             <button type=\"reset\"></button>\
+             <!-- <Card> -->\
+             </nav>"
+        );
+
+        let markup: Markup<()> = VElement::from_tag("p")
+            .attr("title", "Greeting")
+            .attr("class", "lead")
+            .child(VText::text("  Hello \n    World  "))
+            .into();
+        assert_eq!(
+            markup.to_test_html(),
+            r#"<p class="lead" title="Greeting"> Hello World </p>"#
+        );
+    }
+}
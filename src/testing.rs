@@ -0,0 +1,24 @@
+//! Testing components in the plain `#[test]` functions, without a browser.
+//!
+//! It gathers the utilities of [test_utils](../test_utils/index.html) which
+//! need no DOM, so it is available with the `test-utils` feature on, except
+//! in the WASM builds.
+//!
+//! # Example
+//! ```ignore
+//! #[test]
+//! fn should_render_the_card() {
+//!     let snapshot = render_to_snapshot::<Card>(CardProps!(title: "Hello"), Default::default());
+//!     assert_eq!(snapshot, include_str!("card.snap"));
+//! }
+//!
+//! #[test]
+//! fn should_render_the_save_button() {
+//!     let output = ShallowRenderer::<Toolbar>::render(ToolbarProps!(saved: false));
+//!     assert_eq!(output.find("button.primary").len(), 1);
+//! }
+//! ```
+
+pub use crate::test_utils::{
+    render_to_snapshot, RenderedOutput, ShallowRenderer, SnapshotOptions,
+};
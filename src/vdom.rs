@@ -4,23 +4,38 @@ use crate::{
     component::Render,
     dom::DOMPatch,
     vdom::{
+        hydrate::Hydrate,
+        ssr::ToHtml,
         vcomponent::VComponent,
         velement::VElement,
         vlist::VList,
+        vportal::VPortal,
+        vstatic::VStatic,
         vtext::VText
     },
     MessageSender,
     Shared
 };
-use std::borrow::Cow;
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    cell::RefCell,
+    fmt,
+    hash::{Hash, Hasher},
+    rc::Rc
+};
 use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
+use web_sys::{Element, Node};
 
 pub mod vcomponent;
 pub mod velement;
 pub mod vlist;
+pub mod vportal;
+pub mod vstatic;
 pub mod vtext;
 mod conversions;
+pub(crate) mod hydrate;
+pub mod ssr;
 
 /// A virtual node in a virtual DOM tree.
 pub enum VNode<RCTX> {
@@ -32,11 +47,24 @@ pub enum VNode<RCTX> {
     List(VList<RCTX>),
     /// A component vnode
     Component(VComponent<RCTX>),
+    /// A portal vnode
+    Portal(VPortal<RCTX>),
+    /// A static vnode, which is constructed once by `html_static!`
+    Static(VStatic<RCTX>),
+    /// An empty vnode which holds its place in the DOM with an empty comment,
+    /// so that whatever replaces it later is inserted in its place. Create it
+    /// with `VNode::empty()`.
+    Empty(VText<RCTX>),
     /// The empty variant
     None
 }
 
 impl<RCTX> VNode<RCTX> {
+    /// Create an empty VNode which renders nothing but a placeholder comment.
+    pub fn empty() -> VNode<RCTX> {
+        VNode::Empty(VText::comment(""))
+    }
+
     /// Whether the VNode is of `None` variant. 
     pub fn is_none(&self) -> bool {
         match self {
@@ -44,6 +72,93 @@ impl<RCTX> VNode<RCTX> {
             _ => false
         }
     }
+
+    /// Whether the VNode is of `Empty` variant.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            VNode::Empty(_) => true,
+            _ => false
+        }
+    }
+
+    /// Whether the VNode renders no node at all.
+    pub(crate) fn renders_nothing(&self) -> bool {
+        match self {
+            VNode::None => true,
+            VNode::List(list) => list.renders_nothing(),
+            _ => false
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VNode<RCTX> {
+    /// Collects the node along with its descendants in document order. The
+    /// nodes rendered by the nested components are not collected.
+    pub(crate) fn collect_nodes<'a>(&'a self, nodes: &mut Vec<&'a VNode<RCTX>>) {
+        nodes.push(self);
+        match self {
+            VNode::Element(el) => el.collect_nodes(nodes),
+            VNode::List(list) => list.collect_nodes(nodes),
+            VNode::Portal(portal) => portal.collect_nodes(nodes),
+            VNode::Text(_)
+            | VNode::Component(_)
+            | VNode::Static(_)
+            | VNode::Empty(_)
+            | VNode::None => {}
+        }
+    }
+
+    /// Writes the markup as a deterministic HTML string to compare against the
+    /// snapshots in tests, without a browser.
+    ///
+    /// The attributes are sorted by their names and the runs of whitespace in
+    /// the text are collapsed into a single space. The nested components are
+    /// written as a `<!-- <Name> -->` marker instead of being rendered, and
+    /// the portals are skipped.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let markup: Markup<()> = html! {
+    ///     <a href={"/"} class={"home"}>"  Home  "</a>
+    /// };
+    /// assert_eq!(markup.to_test_html(), r#"<a class="home" href="/"> Home </a>"#);
+    /// ```
+    pub fn to_test_html(&self) -> String {
+        let mut html = String::new();
+        self.write_test_html(&mut html);
+        html
+    }
+
+    /// Writes the markup deterministically into the string.
+    pub(crate) fn write_test_html(&self, html: &mut String) {
+        match self {
+            VNode::Text(txt) => txt.write_test_html(html),
+            VNode::Element(el) => el.write_test_html(html),
+            VNode::List(list) => list.write_test_html(html),
+            VNode::Component(comp) => {
+                html.push_str(&format!("<!-- <{}> -->", comp.name()));
+            }
+            VNode::Static(vstatic) => vstatic.write_test_html(html),
+            VNode::Empty(placeholder) => placeholder.write_test_html(html),
+            VNode::Portal(_) | VNode::None => {}
+        }
+    }
+
+    /// Finds the first element, in document order, which listens to the event
+    /// type. The elements rendered by the nested components are not searched.
+    pub(crate) fn find_listening(&self, type_: &str) -> Option<&VElement<RCTX>> {
+        match self {
+            VNode::Element(el) => el.find_listening(type_),
+            VNode::List(list) => list.find_listening(type_),
+            VNode::Portal(portal) => portal.find_listening(type_),
+            VNode::Text(_)
+            | VNode::Component(_)
+            | VNode::Static(_)
+            | VNode::Empty(_)
+            | VNode::None => None
+        }
+    }
 }
 
 macro_rules! patch {
@@ -70,6 +185,21 @@ macro_rules! patch {
     };
 }
 
+impl<RCTX> fmt::Debug for VNode<RCTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VNode::Text(ref text) => text.fmt(f),
+            VNode::Element(ref el) => el.fmt(f),
+            VNode::List(ref list) => list.fmt(f),
+            VNode::Component(ref comp) => comp.fmt(f),
+            VNode::Portal(ref portal) => portal.fmt(f),
+            VNode::Static(ref vstatic) => vstatic.fmt(f),
+            VNode::Empty(ref empty) => empty.fmt(f),
+            VNode::None => f.write_str("None"),
+        }
+    }
+}
+
 impl<RCTX: Render> DOMPatch for VNode<RCTX> {
     type RenderContext = RCTX;
     type Node = Node;
@@ -85,8 +215,11 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(ref mut el) => el.render_walk(parent, next, render_ctx, rx_sender),
             VNode::List(ref mut list) => list.render_walk(parent, next, render_ctx, rx_sender),
             VNode::Component(ref mut comp) => comp.render_walk(parent, next, render_ctx, rx_sender),
+            VNode::Portal(ref mut portal) => portal.render_walk(parent, next, render_ctx, rx_sender),
             // There is nothing to walk on.
             VNode::Text(_) => Ok(()),
+            VNode::Static(_) => Ok(()),
+            VNode::Empty(_) => Ok(()),
             VNode::None => Ok(())
         }
     }
@@ -112,6 +245,15 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Component(ref mut new_comp) => {
                 patch!(Component => new_comp, old, parent, next, render_ctx, rx_sender)
             }
+            VNode::Portal(ref mut new_portal) => {
+                patch!(Portal => new_portal, old, parent, next, render_ctx, rx_sender)
+            }
+            VNode::Static(ref mut new_static) => {
+                patch!(Static => new_static, old, parent, next, render_ctx, rx_sender)
+            }
+            VNode::Empty(ref mut new_placeholder) => {
+                patch!(Empty => new_placeholder, old, parent, next, render_ctx, rx_sender)
+            }
             VNode::None => {
                 if let Some(old) = old {
                     old.remove(parent)?;
@@ -127,6 +269,9 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.reorder(parent, next),
             VNode::List(li) => li.reorder(parent, next),
             VNode::Component(comp) => comp.reorder(parent, next),
+            VNode::Portal(portal) => portal.reorder(parent, next),
+            VNode::Static(vstatic) => vstatic.reorder(parent, next),
+            VNode::Empty(placeholder) => placeholder.reorder(parent, next),
             VNode::None => Ok(())
         }
     }
@@ -137,6 +282,9 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.remove(parent),
             VNode::List(li) => li.remove(parent),
             VNode::Component(comp) => comp.remove(parent),
+            VNode::Portal(portal) => portal.remove(parent),
+            VNode::Static(vstatic) => vstatic.remove(parent),
+            VNode::Empty(placeholder) => placeholder.remove(parent),
             VNode::None => Ok(())
         }
     }
@@ -147,16 +295,76 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.node(),
             VNode::List(li) => li.node(),
             VNode::Component(comp) => comp.node(),
+            VNode::Portal(portal) => portal.node(),
+            VNode::Static(vstatic) => vstatic.node(),
+            VNode::Empty(placeholder) => placeholder.node(),
             VNode::None => None
         }
     }
 }
 
+impl<RCTX: Render> Hydrate for VNode<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        match self {
+            VNode::Text(txt) => txt.hydrate_walk(parent, node, render_ctx, rx_sender),
+            VNode::Element(el) => el.hydrate_walk(parent, node, render_ctx, rx_sender),
+            VNode::List(li) => li.hydrate_walk(parent, node, render_ctx, rx_sender),
+            VNode::Component(comp) => comp.hydrate_walk(parent, node, render_ctx, rx_sender),
+            VNode::Static(vstatic) => vstatic.hydrate_walk(parent, node, render_ctx, rx_sender),
+            VNode::Empty(placeholder) => {
+                placeholder.hydrate_walk(parent, node, render_ctx, rx_sender)
+            }
+            // The portal is not rendered on the server, so render it afresh.
+            VNode::Portal(portal) => {
+                portal.patch(
+                    None,
+                    parent,
+                    node.as_ref(),
+                    render_ctx.clone(),
+                    rx_sender.clone(),
+                )?;
+                portal.render_walk(parent, node.as_ref(), render_ctx, rx_sender)?;
+                Ok(node)
+            }
+            VNode::None => Ok(node),
+        }
+    }
+}
+
+impl<RCTX: Render> ToHtml for VNode<RCTX> {
+    type RenderContext = RCTX;
+
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>) {
+        match self {
+            VNode::Text(txt) => txt.to_html(html, render_ctx),
+            VNode::Element(el) => el.to_html(html, render_ctx),
+            VNode::List(li) => li.to_html(html, render_ctx),
+            VNode::Component(comp) => comp.to_html(html, render_ctx),
+            VNode::Static(vstatic) => vstatic.to_html(html, render_ctx),
+            VNode::Empty(placeholder) => placeholder.to_html(html, render_ctx),
+            // The portal is rendered into its target, not in its place.
+            VNode::Portal(_) => {}
+            VNode::None => {}
+        }
+    }
+}
+
 /// Keys to identify a VNode in VDOM.
 /// 
 /// Users don't need to explicitly use the `Key` type in html! macro. Any 
 /// supported type is automatically converted to it.
 /// 
+/// Tuples of the keyable values are supported as well. Any other value which
+/// is `Hash + Eq`, like a UUID, is keyed with `Key::any`.
+/// 
 /// Note:
 /// WASM only supported 32-bit and 64-bit of the integers.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -171,6 +379,8 @@ pub enum Key {
     U64(u64),
     /// A `String` key
     String(String),
+    /// Any other `Hash + Eq` key
+    Any(AnyKey),
 }
 
 impl Key {
@@ -178,6 +388,57 @@ impl Key {
     pub fn new<T: Into<Key>>(val: T) -> Key {
         val.into()
     }
+
+    /// Construct a Key of any `Hash + Eq` value. The keys of different types
+    /// are never equal.
+    pub fn any<T: Hash + Eq + 'static>(val: T) -> Key {
+        Key::Any(AnyKey(Rc::new(val)))
+    }
+}
+
+/// A key of any `Hash + Eq` value, compared by its type & value.
+#[derive(Clone)]
+pub struct AnyKey(Rc<dyn KeyValue>);
+
+/// The type-erased value of an `AnyKey`.
+trait KeyValue {
+    fn as_any(&self) -> &dyn Any;
+
+    fn eq_key(&self, other: &dyn KeyValue) -> bool;
+
+    fn hash_key(&self, state: &mut dyn Hasher);
+}
+
+impl<T: Hash + Eq + 'static> KeyValue for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_key(&self, other: &dyn KeyValue) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .map_or(false, |other| self == other)
+    }
+
+    fn hash_key(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<T>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+impl PartialEq for AnyKey {
+    fn eq(&self, other: &AnyKey) -> bool {
+        self.0.eq_key(&*other.0)
+    }
+}
+
+impl Eq for AnyKey {}
+
+impl Hash for AnyKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash_key(state);
+    }
 }
 
 macro_rules! convert {
@@ -234,11 +495,126 @@ impl From<String> for Key {
     }
 }
 
+macro_rules! tuple {
+    ($($t:ident),*) => {
+        impl<$($t: Hash + Eq + 'static),*> From<($($t),*)> for Key {
+            fn from(tuple: ($($t),*)) -> Key {
+                Key::any(tuple)
+            }
+        }
+    };
+}
+
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+
+/// A reference to the DOM element rendered by an element in html! macro.
+///
+/// Pass it to an element with `ref={self.my_ref.clone()}` and get the live
+/// element with `my_ref.get()` once the component is mounted. It is cleared
+/// when the element is removed from the DOM.
+///
+/// # Example
+/// ```ignore
+/// #[component]
+/// struct Search {
+///     #[state]
+///     input_ref: NodeRef,
+/// }
+///
+/// impl Lifecycle for Search {
+///     fn mounted(&self) {
+///         let input = self.input_ref.get().unwrap();
+///     }
+/// }
+///
+/// impl Render for Search {
+///     fn render(&self) -> Markup<Self> {
+///         html! {
+///             <input ref={self.input_ref.clone()}>
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct NodeRef(Shared<Option<Element>>);
+
+impl NodeRef {
+    /// Create an empty NodeRef.
+    pub fn new() -> NodeRef {
+        NodeRef(Rc::new(RefCell::new(None)))
+    }
+
+    /// Get the referenced element if it is attached to the DOM.
+    pub fn get(&self) -> Option<Element> {
+        self.0.borrow().clone()
+    }
+
+    /// Set the referenced element.
+    pub(crate) fn set(&self, el: Option<Element>) {
+        *self.0.borrow_mut() = el;
+    }
+}
+
+impl PartialEq for NodeRef {
+    /// NodeRefs are equal only when they are the clones of the same NodeRef.
+    fn eq(&self, other: &NodeRef) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 #[cfg(test)]
-mod test {
-    use web_sys::{window, Element};
+pub(crate) mod test {
+    use super::*;
+    use crate::component::root_render_ctx;
+    use wasm_bindgen_test::*;
+    use web_sys::window;
 
     pub fn container() -> Element {
         window().unwrap().document().unwrap().create_element("div").unwrap()
     }
+
+    fn patch(new: &mut VNode<()>, old: Option<&mut VNode<()>>, parent: &Element) {
+        new.patch(
+            old,
+            parent.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+    }
+
+    fn list_around(middle: VNode<()>) -> VNode<()> {
+        VNode::from(vec![VNode::from("Start"), middle, VNode::from("End")])
+    }
+
+    #[wasm_bindgen_test]
+    fn should_compare_keys_by_type_and_value() {
+        assert!(Key::new((1, "a")) == Key::new((1, "a")));
+        assert!(Key::new((1, "a")) != Key::new((1, "b")));
+        assert!(Key::any(1u8) != Key::any(1u16));
+        assert!(Key::any(vec![1, 2]) == Key::any(vec![1, 2]));
+
+        let mut keys = std::collections::HashSet::new();
+        keys.insert(Key::new((1, "a")));
+        assert!(keys.contains(&Key::new((1, "a"))));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_hold_the_place_of_empty_vnode() {
+        let div = container();
+        let mut empty = list_around(VNode::empty());
+        patch(&mut empty, None, &div);
+        assert_eq!(div.inner_html(), "Start<!---->End");
+
+        let mut filled = list_around(VElement::childless("hr", vec![], vec![]).into());
+        patch(&mut filled, Some(&mut empty), &div);
+        assert_eq!(div.inner_html(), "Start<hr>End");
+
+        let mut emptied = list_around(None::<&str>.into());
+        patch(&mut emptied, Some(&mut filled), &div);
+        assert_eq!(div.inner_html(), "Start<!---->End");
+    }
 }
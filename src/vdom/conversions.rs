@@ -56,3 +56,11 @@ impl<RCTX> From<Vec<VNode<RCTX>>> for VNode<RCTX> {
         VNode::from(VList::from(value))
     }
 }
+
+/// Renders an empty placeholder for `None`, so that an optional markup like
+/// `{ if cond { Some(..) } else { None } }` can be used in html!.
+impl<RCTX, T: Into<VNode<RCTX>>> From<Option<T>> for VNode<RCTX> {
+    fn from(value: Option<T>) -> VNode<RCTX> {
+        value.map_or_else(VNode::empty, Into::into)
+    }
+}
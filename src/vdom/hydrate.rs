@@ -0,0 +1,154 @@
+//! Attaching to the DOM rendered on the server instead of creating it anew.
+//!
+//! The VDOM is walked along with the existing DOM nodes, adopting them as they
+//! are while attaching the event listeners and populating the node refs. When
+//! the existing DOM does not match the first render, the mismatched subtree is
+//! rendered afresh with a warning in the debug builds.
+
+use crate::{dom::DOMPatch, MessageSender, Shared};
+use wasm_bindgen::prelude::JsValue;
+use web_sys::Node;
+
+/// Trait to adopt the existing DOM as the DOM of the VDOM.
+pub(crate) trait Hydrate {
+    /// The render context of the VDOM.
+    type RenderContext;
+
+    /// Walks the VDOM along with the existing nodes starting from `node`,
+    /// adopting them and doing the lifecycle bits of the components. Returns
+    /// the node next to the ones which were adopted.
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue>;
+}
+
+/// Renders the VDOM afresh in place of the node which does not match it.
+/// Returns the node next to the replaced one.
+pub(crate) fn rerender<T: DOMPatch<Node = Node>>(
+    vdom: &mut T,
+    expected: &str,
+    parent: &Node,
+    node: Option<Node>,
+    render_ctx: Shared<T::RenderContext>,
+    rx_sender: MessageSender,
+) -> Result<Option<Node>, JsValue> {
+    warn_mismatch(expected, node.as_ref());
+    vdom.patch(
+        None,
+        parent,
+        node.as_ref(),
+        render_ctx.clone(),
+        rx_sender.clone(),
+    )?;
+    vdom.render_walk(parent, node.as_ref(), render_ctx, rx_sender)?;
+    replace(parent, node)
+}
+
+/// Removes the mismatched node which was replaced. Returns the node next to
+/// it.
+pub(crate) fn replace(parent: &Node, node: Option<Node>) -> Result<Option<Node>, JsValue> {
+    match node {
+        Some(node) => {
+            let next = node.next_sibling();
+            parent.remove_child(&node)?;
+            Ok(next)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Removes the nodes starting from `node` which were left over after all of
+/// the VDOM was adopted.
+pub(crate) fn remove_rest(parent: &Node, mut node: Option<Node>) -> Result<(), JsValue> {
+    while let Some(extra) = node {
+        warn_mismatch("nothing", Some(&extra));
+        node = extra.next_sibling();
+        parent.remove_child(&extra)?;
+    }
+    Ok(())
+}
+
+/// Warns that the server rendered DOM differs from the first render.
+#[cfg(debug_assertions)]
+pub(crate) fn warn_mismatch(expected: &str, found: Option<&Node>) {
+    let found = found.map_or_else(|| "nothing".to_string(), |node| node.node_name());
+    web_sys::console::warn_1(
+        &format!(
+            "Hydration mismatch: expected {} but found {}.",
+            expected, found
+        )
+        .into(),
+    );
+}
+
+/// The mismatches are fixed silently in the release builds.
+#[cfg(not(debug_assertions))]
+pub(crate) fn warn_mismatch(_: &str, _: Option<&Node>) {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        vdom::{
+            test::container,
+            velement::{Attribute, VElement},
+            vlist::VList,
+            vtext::VText,
+            VNode,
+        },
+    };
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_adopt_the_existing_dom() {
+        let div = container();
+        div.set_inner_html(r#"<p class="greet">Hello World!</p>"#);
+        let existing = div.first_child().unwrap();
+
+        let mut vnode: VNode<()> = VElement::new(
+            "p",
+            vec![Attribute::new("class", "greet")],
+            vec![],
+            VList::from(vec![VText::text("Hello ").into(), VText::text("World!").into()]).into(),
+        )
+        .into();
+        let rest = vnode
+            .hydrate_walk(
+                div.as_ref(),
+                div.first_child(),
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To hydrate the div");
+
+        assert!(rest.is_none());
+        assert!(vnode.node().unwrap().is_same_node(Some(&existing)));
+        assert_eq!(div.inner_html(), r#"<p class="greet">Hello World!</p>"#);
+        assert!(existing.first_child().unwrap().next_sibling().is_some());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_rerender_the_mismatched_dom() {
+        let div = container();
+        div.set_inner_html("<span>Old</span><i>Extra</i>");
+
+        let mut vnode: VNode<()> =
+            VElement::new("button", vec![], vec![], VText::text("New").into()).into();
+        let rest = vnode
+            .hydrate_walk(
+                div.as_ref(),
+                div.first_child(),
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To hydrate the div");
+        remove_rest(div.as_ref(), rest).expect("To remove the extra nodes");
+
+        assert_eq!(div.inner_html(), "<button>New</button>");
+    }
+}
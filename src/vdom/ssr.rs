@@ -0,0 +1,101 @@
+//! Rendering the components to an HTML string on the server side.
+//!
+//! Nothing touches the browser DOM while rendering to a string, so it works on
+//! the non-WASM targets as well. The event listeners as well as the lifecycle
+//! hooks of the components are skipped.
+//!
+//! # Example
+//! ```ignore
+//! let html = render_to_string::<MyApp>();
+//! ```
+
+use crate::{
+    component::{Render, RootParent},
+    vdom::vcomponent::{ComponentManager, ComponentWrapper},
+    Shared,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    /// Whether the nested components are marked by their boundaries while
+    /// writing a snapshot, and whether their markup is written as well.
+    static COMPONENT_MARKERS: Cell<Option<bool>> = Cell::new(None);
+}
+
+/// Marks the boundaries of the nested components written while running `f`,
+/// writing their markup within only when `deep`.
+pub(crate) fn with_component_markers<T>(deep: bool, f: impl FnOnce() -> T) -> T {
+    let previous = COMPONENT_MARKERS.with(|current| current.replace(Some(deep)));
+    let result = f();
+    COMPONENT_MARKERS.with(|current| current.set(previous));
+    result
+}
+
+/// Whether the components are to be marked, and if so whether they are to
+/// be written deeply.
+pub(crate) fn component_markers() -> Option<bool> {
+    COMPONENT_MARKERS.with(Cell::get)
+}
+
+/// The name of the component without its module path, e.g. `Button`.
+pub(crate) fn component_name<COMP>() -> &'static str {
+    let path = std::any::type_name::<COMP>();
+    let path = path.split('<').next().unwrap_or(path);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Renders the component along with its nested components to an HTML string.
+pub fn render_to_string<COMP>() -> String
+where
+    COMP: Render<Props = (), Events = ()>,
+{
+    let mut manager = ComponentWrapper::<COMP, RootParent>::new((), ());
+    let mut html = String::new();
+    manager.to_html(&mut html, Rc::new(RefCell::new(())));
+    html
+}
+
+/// Trait to write the VDOM as an HTML string.
+pub(crate) trait ToHtml {
+    /// The render context of the VDOM.
+    type RenderContext;
+
+    /// Writes the HTML of the VDOM into the string, rendering the nested
+    /// components along the way.
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>);
+}
+
+/// The elements which do not have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Whether the element is written without a closing tag.
+pub(crate) fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag)
+}
+
+/// Whether the HTML parsers drop a newline right after the opening tag of the
+/// element.
+pub(crate) fn drops_leading_newline(tag: &str) -> bool {
+    tag == "pre" || tag == "textarea" || tag == "listing"
+}
+
+/// Writes the text into the HTML, escaping the characters which have special
+/// meaning in the text content as well as in the quoted attribute values.
+pub(crate) fn escape(text: &str, html: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            _ => html.push(ch),
+        }
+    }
+}
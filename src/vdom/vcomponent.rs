@@ -1,14 +1,64 @@
 //! Component representation in a VDOM.
 
 use crate::{
-    component::{FromEventProps, Render, Status},
-    dom::DOMPatch,
-    vdom::{Shared, VNode},
+    app_message_channel,
+    component::{FromEventProps, Render, RenderError, Status},
+    context::{self, Scope},
+    dom::{self, DOMPatch},
+    vdom::{
+        hydrate::{self, Hydrate},
+        ssr::{self, ToHtml},
+        velement::{self, with_attribute_transform, AttributeTransform},
+        Shared, VNode,
+    },
     MessageSender,
 };
-use std::{any::Any, cell::RefCell, rc::Rc};
-use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt, mem,
+    panic,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Node};
+
+thread_local! {
+    /// The render error which is being propagated to the nearest error
+    /// boundary.
+    static RENDER_ERROR: RefCell<Option<RenderError>> = RefCell::new(None);
+}
+
+/// Raises a render error to be handled by the nearest error boundary. The
+/// returned value is to be propagated up as the error of the patch.
+fn raise(error: RenderError) -> JsValue {
+    let value = JsValue::from_str(error.message());
+    RENDER_ERROR.with(|raised| *raised.borrow_mut() = Some(error));
+    value
+}
+
+/// Takes the render error which was raised, if any.
+fn take_raised() -> Option<RenderError> {
+    RENDER_ERROR.with(|raised| raised.borrow_mut().take())
+}
+
+/// Runs the walk of the nested components, catching their panics so that an
+/// error boundary gets to handle them.
+#[cfg(not(target_arch = "wasm32"))]
+fn catch_panics(
+    walk: impl FnOnce() -> Result<(), JsValue>,
+) -> Result<Result<(), JsValue>, Box<dyn Any + Send>> {
+    panic::catch_unwind(panic::AssertUnwindSafe(walk))
+}
+
+/// Runs the walk of the nested components. The panics abort on wasm32, so
+/// only the render errors reach the error boundaries there.
+#[cfg(target_arch = "wasm32")]
+fn catch_panics(
+    walk: impl FnOnce() -> Result<(), JsValue>,
+) -> Result<Result<(), JsValue>, Box<dyn Any + Send>> {
+    Ok(walk())
+}
 
 /// The representation of a component in a Virtual DOM.
 pub struct VComponent<RCTX>(Box<dyn ComponentManager<RenderContext = RCTX>>);
@@ -22,7 +72,78 @@ impl<RCTX: Render> VComponent<RCTX> {
     where
         COMP::Events: FromEventProps<RCTX>,
     {
-        VComponent(Box::new(ComponentWrapper::<COMP, RCTX>::new(props, events)))
+        VComponent::manage(ComponentWrapper::<COMP, RCTX>::new(props, events))
+    }
+
+    /// Create a new VComponent which is mounted only after the first paint.
+    ///
+    /// Until then, the placeholder is rendered in its place. Useful to defer
+    /// heavy components so that the rest of the app is painted sooner. The
+    /// placeholder is not updated once it is rendered.
+    pub fn deferred<COMP: Render>(
+        props: COMP::Props,
+        events: <COMP::Events as FromEventProps<RCTX>>::From,
+        placeholder: VNode<RCTX>,
+    ) -> VComponent<RCTX>
+    where
+        COMP::Events: FromEventProps<RCTX>,
+    {
+        let mut wrapper = ComponentWrapper::<COMP, RCTX>::new(props, events);
+        wrapper.deferral = Some(Deferral::new(placeholder));
+        VComponent::manage(wrapper)
+    }
+
+    /// Create a new VComponent which is not updated at all when it is passed
+    /// props equal to the previous ones.
+    ///
+    /// As the events cannot be compared, they are not updated either when the
+    /// props are equal.
+    pub fn memo<COMP: Render>(
+        props: COMP::Props,
+        events: <COMP::Events as FromEventProps<RCTX>>::From,
+    ) -> VComponent<RCTX>
+    where
+        COMP::Events: FromEventProps<RCTX>,
+        COMP::Props: Clone + PartialEq,
+    {
+        let mut wrapper = ComponentWrapper::<COMP, RCTX>::new(props, events);
+        wrapper.memo = Some(Memo::new());
+        VComponent::manage(wrapper)
+    }
+
+    /// Manages the component on its own render loop if it is isolated.
+    fn manage<COMP: Render>(wrapper: ComponentWrapper<COMP, RCTX>) -> VComponent<RCTX>
+    where
+        COMP::Events: FromEventProps<RCTX>,
+    {
+        if COMP::ISOLATED {
+            VComponent(Box::new(IsolatedWrapper::new(wrapper)))
+        } else {
+            VComponent(Box::new(wrapper))
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VComponent<RCTX> {
+    /// The name of the component without its module path, e.g. `Button`.
+    pub(crate) fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX: Render> VComponent<RCTX> {
+    /// The props passed to the component, if it is of the type `COMP` and is
+    /// yet to be rendered.
+    pub(crate) fn props_of<COMP: Render>(&self) -> Option<&COMP::Props>
+    where
+        COMP::Events: FromEventProps<RCTX>,
+    {
+        self.0
+            .as_any()
+            .downcast_ref::<ComponentWrapper<COMP, RCTX>>()
+            .and_then(|wrapper| wrapper.props.as_ref())
     }
 }
 
@@ -34,6 +155,112 @@ where
     props: Option<COMP::Props>,
     events: Option<<COMP::Events as FromEventProps<RCTX>>::From>,
     cached_render: Option<VNode<COMP>>,
+    deferral: Option<Deferral<RCTX>>,
+    memo: Option<Memo<COMP::Props>>,
+    old_props: Option<COMP::Props>,
+    scope: Shared<Scope>,
+}
+
+/// The props last passed to a memoized component.
+struct Memo<P> {
+    props: Option<P>,
+    clone: fn(&P) -> P,
+    eq: fn(&P, &P) -> bool,
+}
+
+impl<P: Clone + PartialEq> Memo<P> {
+    fn new() -> Memo<P> {
+        Memo {
+            props: None,
+            clone: P::clone,
+            eq: P::eq,
+        }
+    }
+}
+
+impl<P> Memo<P> {
+    fn remember(&mut self, props: &P) {
+        self.props = Some((self.clone)(props));
+    }
+
+    fn is_same(&self, props: &P) -> bool {
+        self.props.as_ref().map_or(false, |last| (self.eq)(last, props))
+    }
+}
+
+/// The placeholder of a deferred component along with its scheduling status.
+struct Deferral<RCTX> {
+    placeholder: VNode<RCTX>,
+    /// The handle of the animation frame requested along with its callback,
+    /// once scheduled
+    frame: Option<(i32, Closure<dyn FnMut()>)>,
+    is_ready: Shared<bool>,
+}
+
+impl<RCTX> Deferral<RCTX> {
+    /// Cancels the animation frame, if it has not fired yet.
+    fn cancel(&self) {
+        if let Some((handle, _)) = self.frame {
+            let _ = window().unwrap().cancel_animation_frame(handle);
+        }
+    }
+}
+
+impl<RCTX> Drop for Deferral<RCTX> {
+    fn drop(&mut self) {
+        // The callback is dropped along, so it must not fire afterwards.
+        self.cancel();
+    }
+}
+
+impl<RCTX: Render> Deferral<RCTX> {
+    fn new(placeholder: VNode<RCTX>) -> Deferral<RCTX> {
+        Deferral {
+            placeholder,
+            frame: None,
+            is_ready: Rc::new(RefCell::new(false)),
+        }
+    }
+
+    /// Renders the placeholder and schedules the component to be mounted on
+    /// the next animation frame. The rerender requested from within the frame
+    /// is processed only after the browser has painted.
+    fn render_placeholder(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        render_ctx: Shared<RCTX>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        if self.frame.is_some() {
+            return self
+                .placeholder
+                .render_walk(parent, next, render_ctx, rx_sender);
+        }
+        self.placeholder
+            .patch(None, parent, next, render_ctx.clone(), rx_sender.clone())?;
+        self.placeholder
+            .render_walk(parent, next, render_ctx, rx_sender.clone())?;
+        self.schedule(rx_sender)
+    }
+
+    /// Schedules the component to be mounted on the next animation frame.
+    fn schedule(&mut self, rx_sender: MessageSender) -> Result<(), JsValue> {
+        let is_ready = self.is_ready.clone();
+        let closure: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            *is_ready.borrow_mut() = true;
+            rx_sender.do_react();
+        }));
+        let handle = window()
+            .unwrap()
+            .request_animation_frame(closure.as_ref().unchecked_ref())?;
+        self.frame = Some((handle, closure));
+        Ok(())
+    }
+
+    fn is_ready(&self) -> bool {
+        *self.is_ready.borrow()
+    }
 }
 
 impl<COMP: Render, RCTX: Render> ComponentWrapper<COMP, RCTX>
@@ -49,7 +276,234 @@ where
             props: Some(props),
             events: Some(events),
             cached_render: None,
+            deferral: None,
+            memo: None,
+            old_props: None,
+            scope: Rc::new(RefCell::new(Scope::default())),
+        }
+    }
+
+    /// Creates the component with the props and events passed to it.
+    fn create(&mut self, render_ctx: Shared<RCTX>, rx_sender: MessageSender) -> COMP {
+        let props = self.props.take().unwrap();
+        let events = self.events.take().unwrap();
+        if let Some(ref mut memo) = self.memo {
+            memo.remember(&props);
+        }
+        let instance = COMP::init(
+            props,
+            FromEventProps::from(events, render_ctx),
+            Status::new(COMP::State::default(), rx_sender),
+        );
+        instance.created();
+        instance
+    }
+
+    /// Walks the component, rendering it if required, within its own context
+    /// scope.
+    fn walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        render_ctx: Shared<RCTX>,
+        rx_sender: MessageSender,
+        context_changed: bool,
+    ) -> Result<(), JsValue> {
+        if let Some(ref mut deferral) = self.deferral {
+            if !deferral.is_ready() {
+                return deferral.render_placeholder(parent, next, render_ctx, rx_sender);
+            }
+            deferral.placeholder.remove(parent)?;
+        }
+        self.deferral = None;
+
+        let is_first_render = self.component.is_none();
+        let mut old_render = None;
+        let mut needs_patch = false;
+        if is_first_render {
+            let instance = self.create(render_ctx, rx_sender.clone());
+            let initial_render = instance.try_render();
+            self.component = Some(Rc::new(RefCell::new(instance)));
+            self.cached_render = Some(initial_render.map_err(raise)?);
+            needs_patch = true;
+        } else {
+            let comp = self.component.as_ref().unwrap();
+
+            let state_changed = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_state_dirty())
+                .unwrap_or(false);
+            let state_changed = if state_changed {
+                comp.borrow()
+                    .status()
+                    .unwrap()
+                    .borrow_mut()
+                    .set_state_dirty(false);
+                comp.borrow_mut().refresh_state()
+            } else {
+                false
+            };
+
+            let props_changed = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_props_dirty())
+                .unwrap_or(false);
+            if props_changed {
+                comp.borrow()
+                    .status()
+                    .unwrap()
+                    .borrow_mut()
+                    .set_props_dirty(false);
+            }
+
+            let update_forced = comp
+                .borrow()
+                .status()
+                .map_or(false, |s| s.borrow_mut().take_update_forced());
+
+            if state_changed || props_changed || context_changed || update_forced {
+                self.scope.borrow_mut().clear_consumed();
+                let rerender = comp.borrow().try_render().map_err(raise)?;
+                old_render = self.cached_render.replace(rerender);
+                needs_patch = true;
+            }
+        }
+
+        let comp = self.component.clone().unwrap();
+        let depth = context::depth();
+        // Catch the failures of the nested components, so that the component
+        // gets to handle them if it is an error boundary.
+        let walked = {
+            let cached_render = &mut self.cached_render;
+            let old_render = &mut old_render;
+            let rx_sender = rx_sender.clone();
+            catch_panics(move || {
+                if let Some(cached) = cached_render.as_mut() {
+                    if needs_patch {
+                        cached.patch(
+                            old_render.as_mut(),
+                            parent,
+                            next,
+                            comp.clone(),
+                            rx_sender.clone(),
+                        )?;
+                    }
+                    cached.render_walk(parent, next, comp, rx_sender)?;
+                }
+                Ok(())
+            })
+        };
+        let error: Option<Box<dyn Any + Send>> = match walked {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => match take_raised() {
+                Some(error) => Some(Box::new(error)),
+                None => return Err(err),
+            },
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            context::unwind(depth);
+            self.recover(error, old_render, parent, next, rx_sender)?;
+        }
+        if is_first_render {
+            // Only after the nested components are rendered too, the component
+            // is said to be mounted.
+            self.component.as_ref().unwrap().borrow().mounted();
+        }
+        if let Some(old_props) = self.old_props.take() {
+            self.component.as_ref().unwrap().borrow().updated(old_props);
+        }
+        Ok(())
+    }
+
+    /// Renders the component for the first time, adopting the existing DOM
+    /// instead of patching it.
+    fn hydrate(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<RCTX>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        if let Some(ref mut deferral) = self.deferral {
+            let rest = deferral.placeholder.hydrate_walk(
+                parent,
+                node,
+                render_ctx,
+                rx_sender.clone(),
+            )?;
+            deferral.schedule(rx_sender)?;
+            return Ok(rest);
+        }
+
+        let instance = self.create(render_ctx, rx_sender.clone());
+        let initial_render = instance.try_render();
+        let comp = Rc::new(RefCell::new(instance));
+        self.component = Some(comp.clone());
+        self.cached_render = Some(initial_render.map_err(raise)?);
+        let rest = self
+            .cached_render
+            .as_mut()
+            .unwrap()
+            .hydrate_walk(parent, node, comp.clone(), rx_sender)?;
+        comp.borrow().mounted();
+        Ok(rest)
+    }
+
+    /// Replaces the subtree which failed with the fallback of the component
+    /// if it is an error boundary, otherwise propagates the error further up.
+    fn recover(
+        &mut self,
+        error: Box<dyn Any + Send>,
+        old_render: Option<VNode<COMP>>,
+        parent: &Node,
+        next: Option<&Node>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        // The subtree may be left half patched, so remove whatever is left of
+        // it from the DOM.
+        for failed in self.cached_render.take().iter().chain(old_render.iter()) {
+            let _ = catch_panics(|| failed.remove(parent));
         }
+
+        let comp = self.component.clone().unwrap();
+        let fallback = comp.borrow().on_error(error);
+        let mut fallback = match fallback {
+            Ok(fallback) => fallback,
+            Err(error) => match error.downcast::<RenderError>() {
+                // Render errors are propagated without unwinding.
+                Ok(error) => return Err(raise(*error)),
+                Err(error) => panic::resume_unwind(error),
+            },
+        };
+        fallback.patch(None, parent, next, comp.clone(), rx_sender.clone())?;
+        fallback.render_walk(parent, next, comp, rx_sender)?;
+        self.cached_render = Some(fallback);
+        Ok(())
+    }
+}
+
+impl<RCTX: Render> Hydrate for VComponent<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        self.0.hydrate_walk(parent, node, render_ctx, rx_sender)
+    }
+}
+
+impl<RCTX: Render> ToHtml for VComponent<RCTX> {
+    type RenderContext = RCTX;
+
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>) {
+        self.0.to_html(html, render_ctx);
     }
 }
 
@@ -92,7 +546,13 @@ impl<RCTX: Render> DOMPatch for VComponent<RCTX> {
     }
 }
 
-pub(crate) trait ComponentManager: 'static {
+impl<RCTX> fmt::Debug for VComponent<RCTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub(crate) trait ComponentManager: fmt::Debug + 'static {
     type RenderContext;
 
     fn render_walk(
@@ -117,9 +577,44 @@ pub(crate) trait ComponentManager: 'static {
 
     fn node(&self) -> Option<&Node>;
 
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue>;
+
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>);
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn name(&self) -> &'static str;
+
+    fn as_any(&self) -> &dyn Any;
+
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
+impl<COMP: Render, RCTX: Render> fmt::Debug for ComponentWrapper<COMP, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    /// Writes the markup last rendered by the component, or the placeholder
+    /// if it is yet to be mounted.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref deferral) = self.deferral {
+            return f
+                .debug_tuple("Deferred")
+                .field(&deferral.placeholder)
+                .finish();
+        }
+        match self.cached_render {
+            Some(ref rendered) => f.debug_tuple("Component").field(rendered).finish(),
+            None => f.write_str("Component"),
+        }
+    }
+}
+
 impl<COMP: Render, RCTX: Render> ComponentManager for ComponentWrapper<COMP, RCTX>
 where
     COMP::Events: FromEventProps<RCTX>,
@@ -133,81 +628,14 @@ where
         render_ctx: Shared<Self::RenderContext>,
         rx_sender: MessageSender,
     ) -> Result<(), JsValue> {
-        if self.component.is_none() {
-            let props = self.props.take().unwrap();
-            let events = self.events.take().unwrap();
-            let instance = COMP::init(
-                props,
-                FromEventProps::from(events, render_ctx),
-                Status::new(COMP::State::default(), rx_sender.clone()),
-            );
-            instance.created();
-            let mut initial_render = instance.render();
-            let shared_instance = Rc::new(RefCell::new(instance));
-            initial_render.patch(
-                None,
-                parent,
-                next,
-                shared_instance.clone(),
-                rx_sender.clone(),
-            )?;
-            shared_instance.borrow().mounted();
-            self.component = Some(shared_instance);
-            self.cached_render = Some(initial_render);
-        } else {
-            let comp = self.component.as_ref().unwrap();
-
-            let state_changed = comp
-                .borrow()
-                .status()
-                .map(|s| s.borrow().is_state_dirty())
-                .unwrap_or(false);
-            let state_changed = if state_changed {
-                comp.borrow()
-                    .status()
-                    .unwrap()
-                    .borrow_mut()
-                    .set_state_dirty(false);
-                comp.borrow_mut().refresh_state()
-            } else {
-                false
-            };
-
-            let props_changed = comp
-                .borrow()
-                .status()
-                .map(|s| s.borrow().is_props_dirty())
-                .unwrap_or(false);
-            if props_changed {
-                comp.borrow()
-                    .status()
-                    .unwrap()
-                    .borrow_mut()
-                    .set_props_dirty(false);
-            }
-
-            if state_changed || props_changed {
-                let mut rerender = comp.borrow().render();
-                let mut cached_render = self.cached_render.take();
-                rerender.patch(
-                    cached_render.as_mut(),
-                    parent,
-                    next,
-                    comp.clone(),
-                    rx_sender.clone(),
-                )?;
-                self.cached_render = Some(rerender);
-            }
-        }
-        if let Some(ref mut cached) = self.cached_render {
-            cached.render_walk(
-                parent,
-                next,
-                self.component.as_ref().unwrap().clone(),
-                rx_sender,
-            )?;
-        }
-        Ok(())
+        // The contexts are provided by the ancestors, so check for changes
+        // before entering the scope of this component.
+        let context_changed = self.component.is_some() && context::is_stale(&self.scope);
+        self.scope.borrow_mut().set_sender(rx_sender.clone());
+        context::enter(self.scope.clone());
+        let walked = self.walk(parent, next, render_ctx, rx_sender, context_changed);
+        context::exit();
+        walked
     }
 
     fn patch(
@@ -222,17 +650,47 @@ where
                 .as_any_mut()
                 .downcast_mut::<ComponentWrapper<COMP, RCTX>>()
             {
+                Some(ref mut old) if old.component.is_none() => {
+                    // The older one is yet to be mounted, so carry on with its
+                    // placeholder until then.
+                    self.deferral = old.deferral.take();
+                    true
+                }
                 Some(old) => {
                     let comp = old.component.take().unwrap();
+                    self.scope = old.scope.clone();
                     let props = self.props.take().unwrap();
                     let events = self.events.take().unwrap();
 
-                    // Reuse the older component by passing in the newer props.
-                    let old_props = comp
-                        .borrow_mut()
-                        .update(props, FromEventProps::from(events, render_ctx));
-                    if let Some(old_props) = old_props {
-                        comp.borrow().updated(old_props);
+                    let is_memoized = match self.memo {
+                        Some(ref mut memo) => {
+                            let is_same = old
+                                .memo
+                                .as_ref()
+                                .map_or(false, |old| old.is_same(&props));
+                            memo.remember(&props);
+                            is_same
+                        }
+                        None => false,
+                    };
+
+                    if !is_memoized {
+                        let should_update = comp.borrow().should_update(&props);
+
+                        // Reuse the older component by passing in the newer props.
+                        let old_props = comp
+                            .borrow_mut()
+                            .update(props, FromEventProps::from(events, render_ctx));
+                        if let Some(old_props) = old_props {
+                            if !should_update {
+                                // Keep the props but do not rerender for them.
+                                if let Some(status) = comp.borrow().status() {
+                                    status.borrow_mut().set_props_dirty(false);
+                                }
+                            }
+                            // Notify about the update after it is rendered.
+                            self.old_props = Some(old_props);
+                        }
                     }
                     self.component = Some(comp);
 
@@ -252,6 +710,9 @@ where
     }
 
     fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
+        if let Some(ref deferral) = self.deferral {
+            deferral.placeholder.reorder(parent, next)?;
+        }
         if let Some(ref cached_render) = self.cached_render {
             cached_render.reorder(parent, next)?;
         }
@@ -259,27 +720,383 @@ where
     }
 
     fn remove(&self, parent: &Node) -> Result<(), JsValue> {
+        if let Some(ref deferral) = self.deferral {
+            deferral.cancel();
+            deferral.placeholder.remove(parent)?;
+        }
         if let Some(ref cached_render) = self.cached_render {
-            cached_render.remove(parent)?;
             let comp = self.component.as_ref().unwrap();
+            comp.borrow().before_destroy();
+            cached_render.remove(parent)?;
             comp.borrow().destroyed();
         }
         Ok(())
     }
 
     fn node(&self) -> Option<&Node> {
+        if let Some(ref deferral) = self.deferral {
+            return deferral.placeholder.node();
+        }
         self.cached_render.as_ref().and_then(|inner| inner.node())
     }
 
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        self.scope.borrow_mut().set_sender(rx_sender.clone());
+        context::enter(self.scope.clone());
+        let hydrated = self.hydrate(parent, node, render_ctx, rx_sender);
+        context::exit();
+        hydrated
     }
-}
 
-impl<RCTX> From<VComponent<RCTX>> for VNode<RCTX> {
-    fn from(comp: VComponent<RCTX>) -> VNode<RCTX> {
-        VNode::Component(comp)
-    }
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>) {
+        if let Some(ref mut deferral) = self.deferral {
+            return deferral.placeholder.to_html(html, render_ctx);
+        }
+
+        let markers = ssr::component_markers();
+        if let Some(deep) = markers {
+            html.push_str(&format!("<!-- <{}> -->", ssr::component_name::<COMP>()));
+            if !deep {
+                return;
+            }
+        }
+
+        // The component is rendered only once, without invoking any of its
+        // lifecycle hooks.
+        let props = self.props.take().unwrap();
+        let events = self.events.take().unwrap();
+        let instance = COMP::init(
+            props,
+            FromEventProps::from(events, render_ctx),
+            Status::new(COMP::State::default(), MessageSender::inert()),
+        );
+        context::enter(self.scope.clone());
+        let mut markup = instance.render();
+        markup.to_html(html, Rc::new(RefCell::new(instance)));
+        context::exit();
+
+        if markers.is_some() {
+            html.push_str(&format!("<!-- </{}> -->", ssr::component_name::<COMP>()));
+        }
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn name(&self) -> &'static str {
+        ssr::component_name::<COMP>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// An isolated component, which rerenders only itself on its own state
+/// changes apart from the render loop of the app.
+///
+/// Its nodes are kept in between a pair of empty comments, so that it knows
+/// where to rerender itself.
+struct IsolatedWrapper<COMP: Render, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    isolation: Shared<Isolation<COMP, RCTX>>,
+    /// The comment before the nodes of the component.
+    start: Option<Node>,
+}
+
+/// The state of an isolated component shared with its render loop.
+struct Isolation<COMP: Render, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    wrapper: ComponentWrapper<COMP, RCTX>,
+    /// The comment after the nodes of the component.
+    end: Option<Node>,
+    parent: Option<Node>,
+    render_ctx: Option<Shared<RCTX>>,
+    /// The scopes of the ancestors when it was last walked, to look up the
+    /// contexts from while rerendering on its own.
+    scopes: Vec<Shared<Scope>>,
+    attribute_transform: Option<AttributeTransform>,
+    rx_sender: Option<MessageSender>,
+}
+
+impl<COMP: Render, RCTX: Render> IsolatedWrapper<COMP, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    fn new(wrapper: ComponentWrapper<COMP, RCTX>) -> IsolatedWrapper<COMP, RCTX> {
+        IsolatedWrapper {
+            isolation: Rc::new(RefCell::new(Isolation {
+                wrapper,
+                end: None,
+                parent: None,
+                render_ctx: None,
+                scopes: vec![],
+                attribute_transform: None,
+                rx_sender: None,
+            })),
+            start: None,
+        }
+    }
+
+    /// Creates the message channel of the component, which rerenders it for
+    /// as long as it lives.
+    fn listen(&self) -> MessageSender {
+        let (receiver, sender) = app_message_channel();
+        let isolation = Rc::downgrade(&self.isolation);
+        receiver.react_on_message(move || {
+            if let Some(isolation) = isolation.upgrade() {
+                isolation.borrow_mut().rerender().unwrap();
+            }
+        });
+        sender
+    }
+}
+
+impl<COMP: Render, RCTX: Render> Isolation<COMP, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    /// Remembers the surroundings of the component as it is walked along
+    /// with the app.
+    fn remember(&mut self, parent: &Node, render_ctx: Shared<RCTX>) {
+        self.parent = Some(parent.clone());
+        self.render_ctx = Some(render_ctx);
+        self.scopes = context::scopes();
+        self.attribute_transform = velement::attribute_transform();
+    }
+
+    /// Rerenders the component before its end comment, within the scopes of
+    /// its ancestors.
+    fn rerender(&mut self) -> Result<(), JsValue> {
+        let Isolation {
+            ref mut wrapper,
+            ref end,
+            ref parent,
+            ref render_ctx,
+            ref scopes,
+            attribute_transform,
+            ref rx_sender,
+        } = *self;
+        let (parent, render_ctx, rx_sender) = match (parent, render_ctx, rx_sender) {
+            (Some(parent), Some(render_ctx), Some(rx_sender)) => (parent, render_ctx, rx_sender),
+            // It is yet to be walked along with the app.
+            _ => return Ok(()),
+        };
+
+        let depth = context::depth();
+        for scope in scopes {
+            context::enter(scope.clone());
+        }
+        let walked = with_attribute_transform(attribute_transform, || {
+            wrapper.render_walk(parent, end.as_ref(), render_ctx.clone(), rx_sender.clone())
+        });
+        context::unwind(depth);
+        walked
+    }
+}
+
+impl<COMP: Render, RCTX> Drop for Isolation<COMP, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    fn drop(&mut self) {
+        // The component is gone, so is the rerender queued for it.
+        if let Some(ref rx_sender) = self.rx_sender {
+            rx_sender.close();
+        }
+    }
+}
+
+/// Adopts the empty comment at the node as a marker of an isolated component,
+/// or inserts one if it is missing. Returns the marker along with the node
+/// next to it.
+fn adopt_marker(parent: &Node, node: Option<Node>) -> Result<(Node, Option<Node>), JsValue> {
+    match node {
+        Some(node) if node.node_type() == Node::COMMENT_NODE => {
+            let next = node.next_sibling();
+            Ok((node, next))
+        }
+        node => {
+            hydrate::warn_mismatch("a comment", node.as_ref());
+            let marker = create_marker();
+            parent.insert_before(&marker, node.as_ref())?;
+            Ok((marker, node))
+        }
+    }
+}
+
+/// Creates an empty comment to mark the bounds of an isolated component.
+fn create_marker() -> Node {
+    window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .create_comment("")
+        .into()
+}
+
+impl<COMP: Render, RCTX: Render> fmt::Debug for IsolatedWrapper<COMP, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.isolation.borrow().wrapper.fmt(f)
+    }
+}
+
+impl<COMP: Render, RCTX: Render> ComponentManager for IsolatedWrapper<COMP, RCTX>
+where
+    COMP::Events: FromEventProps<RCTX>,
+{
+    type RenderContext = RCTX;
+
+    fn render_walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<(), JsValue> {
+        if self.start.is_none() {
+            let start = create_marker();
+            parent.insert_before(&start, next)?;
+            self.start = Some(start);
+        }
+        let rx_sender = match self.isolation.borrow().rx_sender {
+            Some(ref rx_sender) => rx_sender.clone(),
+            None => self.listen(),
+        };
+
+        let mut isolation = self.isolation.borrow_mut();
+        if isolation.end.is_none() {
+            let end = create_marker();
+            parent.insert_before(&end, next)?;
+            isolation.end = Some(end);
+        }
+        isolation.rx_sender = Some(rx_sender.clone());
+        isolation.remember(parent, render_ctx.clone());
+
+        let Isolation {
+            ref mut wrapper,
+            ref end,
+            ..
+        } = *isolation;
+        wrapper.render_walk(parent, end.as_ref(), render_ctx, rx_sender)
+    }
+
+    fn patch(
+        &mut self,
+        old: Option<&mut dyn ComponentManager<RenderContext = Self::RenderContext>>,
+        parent: &Node,
+        next: Option<&Node>,
+        render_ctx: Shared<Self::RenderContext>,
+    ) -> Result<(), JsValue> {
+        if let Some(old) = old {
+            match old
+                .as_any_mut()
+                .downcast_mut::<IsolatedWrapper<COMP, RCTX>>()
+            {
+                Some(old) => {
+                    // Carry on with the older isolation along with its render
+                    // loop, passing in the newer props.
+                    mem::swap(&mut self.isolation, &mut old.isolation);
+                    self.start = old.start.take();
+                    let mut isolation = self.isolation.borrow_mut();
+                    let mut fresh = old.isolation.borrow_mut();
+                    mem::swap(&mut isolation.wrapper, &mut fresh.wrapper);
+                    isolation
+                        .wrapper
+                        .patch(Some(&mut fresh.wrapper), parent, next, render_ctx)?;
+                }
+                None => old.remove(parent)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
+        if let Some(ref start) = self.start {
+            parent.insert_before(start, next)?;
+        }
+        let isolation = self.isolation.borrow();
+        isolation.wrapper.reorder(parent, next)?;
+        if let Some(ref end) = isolation.end {
+            parent.insert_before(end, next)?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, parent: &Node) -> Result<(), JsValue> {
+        let isolation = self.isolation.borrow();
+        isolation.wrapper.remove(parent)?;
+        for marker in self.start.iter().chain(isolation.end.iter()) {
+            dom::remove_child(parent, marker)?;
+        }
+        Ok(())
+    }
+
+    fn node(&self) -> Option<&Node> {
+        self.start.as_ref()
+    }
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        let (start, node) = adopt_marker(parent, node)?;
+        self.start = Some(start);
+        let rx_sender = self.listen();
+
+        let mut isolation = self.isolation.borrow_mut();
+        isolation.rx_sender = Some(rx_sender.clone());
+        isolation.remember(parent, render_ctx.clone());
+        let rest = isolation
+            .wrapper
+            .hydrate_walk(parent, node, render_ctx, rx_sender)?;
+        let (end, rest) = adopt_marker(parent, rest)?;
+        isolation.end = Some(end);
+        Ok(rest)
+    }
+
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>) {
+        html.push_str("<!---->");
+        self.isolation.borrow_mut().wrapper.to_html(html, render_ctx);
+        html.push_str("<!---->");
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn name(&self) -> &'static str {
+        ssr::component_name::<COMP>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<RCTX> From<VComponent<RCTX>> for VNode<RCTX> {
+    fn from(comp: VComponent<RCTX>) -> VNode<RCTX> {
+        VNode::Component(comp)
+    }
 }
 
 #[cfg(test)]
@@ -288,10 +1105,16 @@ pub mod test {
     use crate::{
         component::*,
         prelude::*,
-        vdom::{test::container, velement::*, vtext::*, VNode},
+        vdom::{test::container, velement::*, vlist::VList, vtext::*, Key, VNode},
         Shared,
     };
+    use fnv::FnvBuildHasher;
+    use futures::Future;
+    use indexmap::IndexMap;
+    use js_sys::Promise;
+    use wasm_bindgen_futures::JsFuture;
     use wasm_bindgen_test::*;
+    use web_sys::Element;
 
     struct Button {
         disabled: bool,
@@ -302,7 +1125,19 @@ pub mod test {
         disabled: bool,
     }
 
-    impl Lifecycle for Button {}
+    impl Lifecycle for Button {
+        fn updated(&self, old_props: Self::Props) {
+            UPDATED_LOG.with(|updated| {
+                if let Some((container, log)) = updated.borrow_mut().as_mut() {
+                    log.push((old_props.disabled, container.inner_html()));
+                }
+            });
+        }
+
+        fn before_destroy(&self) {
+            DESTROY_LOG.with(|log| log.borrow_mut().push("button"));
+        }
+    }
 
     impl Component for Button {
         type Props = ButtonProps;
@@ -348,65 +1183,953 @@ pub mod test {
         }
     }
 
-    #[wasm_bindgen_test]
-    fn should_patch_container_with_component() {
-        let mut vcomp = VComponent::new::<Button>(ButtonProps { disabled: false }, ());
-        let div = container();
-        vcomp
-            .render_walk(
-                div.as_ref(),
-                None,
-                root_render_ctx(),
-                crate::message_sender(),
-            )
-            .expect("To patch div");
+    struct FrozenButton {
+        disabled: bool,
+        __status: Shared<Status<()>>,
+    }
 
-        assert_eq!(
-            div.inner_html(),
-            r#"<button disabled="false">Click</button>"#
-        );
+    impl Lifecycle for FrozenButton {
+        fn should_update(&self, _: &Self::Props) -> bool {
+            false
+        }
     }
 
-    #[wasm_bindgen_test]
-    fn should_patch_container_with_component_update() {
-        let mut vcomp = VComponent::new::<Button>(ButtonProps { disabled: false }, ());
-        let div = container();
-        vcomp
-            .render_walk(
-                div.as_ref(),
-                None,
-                root_render_ctx(),
-                crate::message_sender(),
-            )
-            .expect("To patch div");
+    impl Component for FrozenButton {
+        type Props = ButtonProps;
+        type Events = ();
+        type State = ();
 
-        assert_eq!(
-            div.inner_html(),
-            r#"<button disabled="false">Click</button>"#
-        );
+        fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            FrozenButton {
+                disabled: props.disabled,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
 
-        let mut patched = VComponent::new::<Button>(ButtonProps { disabled: true }, ());
-        patched
-            .patch(
-                Some(&mut vcomp),
-                div.as_ref(),
-                None,
-                root_render_ctx(),
-                crate::message_sender(),
-            )
-            .unwrap();
-        patched
-            .render_walk(
-                div.as_ref(),
-                None,
-                root_render_ctx(),
-                crate::message_sender(),
-            )
-            .expect("To patch div");
+        fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            if self.disabled != props.disabled {
+                self.disabled = props.disabled;
+                self.__status.borrow_mut().set_props_dirty(true);
+                Some(ButtonProps {
+                    disabled: !self.disabled,
+                })
+            } else {
+                None
+            }
+        }
 
-        assert_eq!(
-            div.inner_html(),
-            r#"<button disabled="true">Click</button>"#
-        );
+        fn refresh_state(&mut self) -> bool {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Render for FrozenButton {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "button",
+                vec![Attribute::new("disabled", self.disabled.to_string())],
+                vec![],
+                VNode::from(VText::text("Click")),
+            ))
+        }
+    }
+
+    thread_local! {
+        static MOUNTED_HTML: RefCell<Option<(Element, String)>> = RefCell::new(None);
+        static DESTROY_LOG: RefCell<Vec<&'static str>> = RefCell::new(vec![]);
+        static UPDATED_LOG: RefCell<Option<(Element, Vec<(bool, String)>)>> = RefCell::new(None);
+    }
+
+    struct Toolbar;
+
+    impl Lifecycle for Toolbar {
+        fn mounted(&self) {
+            MOUNTED_HTML.with(|mounted| {
+                let mut mounted = mounted.borrow_mut();
+                let (container, html) = mounted.as_mut().unwrap();
+                *html = container.inner_html();
+            });
+        }
+
+        fn before_destroy(&self) {
+            DESTROY_LOG.with(|log| log.borrow_mut().push("toolbar"));
+        }
+    }
+
+    impl Component for Toolbar {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Toolbar
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Toolbar {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "div",
+                vec![],
+                vec![],
+                VNode::from(VComponent::new::<Button>(ButtonProps { disabled: false }, ())),
+            ))
+        }
+    }
+
+    struct Broken;
+
+    impl Lifecycle for Broken {}
+
+    impl Component for Broken {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Broken
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl TryRender for Broken {
+        fn render(&self) -> Result<Markup<Self>, RenderError> {
+            Err(RenderError::new("No data"))
+        }
+    }
+
+    struct Boundary;
+
+    impl Lifecycle for Boundary {
+        fn on_error(&self, error: Box<dyn Any + Send>) -> Result<Markup<Self>, Box<dyn Any + Send>> {
+            let error = error.downcast::<RenderError>()?;
+            Ok(VNode::from(VText::text(format!(
+                "Failed: {}",
+                error.message()
+            ))))
+        }
+    }
+
+    impl Component for Boundary {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Boundary
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Boundary {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "div",
+                vec![],
+                vec![],
+                VNode::from(VComponent::new::<Broken>((), ())),
+            ))
+        }
+    }
+
+    /// Not an error boundary, so the errors of its children pass through it.
+    struct Passthrough;
+
+    impl Lifecycle for Passthrough {}
+
+    impl Component for Passthrough {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Passthrough
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Passthrough {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "span",
+                vec![],
+                vec![],
+                VNode::from(VComponent::new::<Broken>((), ())),
+            ))
+        }
+    }
+
+    /// An error boundary with the failing component nested deeper within.
+    struct OuterBoundary;
+
+    impl Lifecycle for OuterBoundary {
+        fn on_error(&self, error: Box<dyn Any + Send>) -> Result<Markup<Self>, Box<dyn Any + Send>> {
+            let error = error.downcast::<RenderError>()?;
+            Ok(VNode::from(VText::text(format!(
+                "Caught: {}",
+                error.message()
+            ))))
+        }
+    }
+
+    impl Component for OuterBoundary {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            OuterBoundary
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for OuterBoundary {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VElement::new(
+                "div",
+                vec![],
+                vec![],
+                VNode::from(VComponent::new::<Passthrough>((), ())),
+            ))
+        }
+    }
+
+    thread_local! {
+        static LABEL_RENDERS: RefCell<usize> = RefCell::new(0);
+    }
+
+    struct Label {
+        text: &'static str,
+        __status: Shared<Status<()>>,
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct LabelProps {
+        text: &'static str,
+    }
+
+    impl Lifecycle for Label {}
+
+    impl Component for Label {
+        type Props = LabelProps;
+        type Events = ();
+        type State = ();
+
+        fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Label {
+                text: props.text,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            // Always rerenders, unless it is memoized.
+            let old_text = self.text;
+            self.text = props.text;
+            self.__status.borrow_mut().set_props_dirty(true);
+            Some(LabelProps { text: old_text })
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Render for Label {
+        fn render(&self) -> Markup<Self> {
+            LABEL_RENDERS.with(|renders| *renders.borrow_mut() += 1);
+            VNode::from(VText::text(self.text))
+        }
+    }
+
+    thread_local! {
+        static SWAP_LOG: RefCell<Vec<&'static str>> = RefCell::new(vec![]);
+    }
+
+    struct Ticker;
+
+    impl Lifecycle for Ticker {
+        fn mounted(&self) {
+            SWAP_LOG.with(|log| log.borrow_mut().push("ticker:start"));
+        }
+
+        fn before_destroy(&self) {
+            SWAP_LOG.with(|log| log.borrow_mut().push("ticker:stop"));
+        }
+    }
+
+    impl Component for Ticker {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Ticker
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Ticker {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text("Tick"))
+        }
+    }
+
+    struct Stopwatch;
+
+    impl Lifecycle for Stopwatch {
+        fn created(&self) {
+            SWAP_LOG.with(|log| log.borrow_mut().push("stopwatch:created"));
+        }
+    }
+
+    impl Component for Stopwatch {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            Stopwatch
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Stopwatch {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text("Watch"))
+        }
+    }
+
+    thread_local! {
+        static TALLY_INITS: RefCell<usize> = RefCell::new(0);
+    }
+
+    /// Remembers the order in which it was created, to tell apart a reused
+    /// instance from a newer one.
+    struct Tally {
+        id: usize,
+    }
+
+    impl Lifecycle for Tally {}
+
+    impl Component for Tally {
+        type Props = ();
+        type Events = ();
+        type State = ();
+
+        fn init(_: Self::Props, _: Self::Events, _: Status<Self::State>) -> Self {
+            let id = TALLY_INITS.with(|inits| {
+                let mut inits = inits.borrow_mut();
+                *inits += 1;
+                *inits
+            });
+            Tally { id }
+        }
+
+        fn update(&mut self, _: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            None
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            false
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            None
+        }
+    }
+
+    impl Render for Tally {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text(format!("Tally {}", self.id)))
+        }
+    }
+
+    fn mixed(rows: Vec<(i32, Option<&'static str>)>) -> VNode<()> {
+        VNode::from(VList::from(
+            rows.into_iter()
+                .map(|(key, text)| {
+                    let row = match text {
+                        Some(text) => VNode::from(VElement::new(
+                            "p",
+                            vec![],
+                            vec![],
+                            VText::text(text).into(),
+                        )),
+                        None => VNode::from(VComponent::new::<Tally>((), ())),
+                    };
+                    (Key::new(key), row)
+                })
+                .collect::<IndexMap<_, _, FnvBuildHasher>>(),
+        ))
+    }
+
+    fn keyed(key: i32, vcomp: VComponent<()>) -> VNode<()> {
+        let mut map = IndexMap::with_hasher(FnvBuildHasher::default());
+        map.insert(Key::new(key), VNode::from(vcomp));
+        VNode::from(VList::from(map))
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_component() {
+        let mut vcomp = VComponent::new::<Button>(ButtonProps { disabled: false }, ());
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<button disabled="false">Click</button>"#
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_component_update() {
+        let mut vcomp = VComponent::new::<Button>(ButtonProps { disabled: false }, ());
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<button disabled="false">Click</button>"#
+        );
+
+        let mut patched = VComponent::new::<Button>(ButtonProps { disabled: true }, ());
+        patched
+            .patch(
+                Some(&mut vcomp),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .unwrap();
+        patched
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<button disabled="true">Click</button>"#
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_rerender_when_should_update_is_false() {
+        let mut vcomp = VComponent::new::<FrozenButton>(ButtonProps { disabled: false }, ());
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let mut patched = VComponent::new::<FrozenButton>(ButtonProps { disabled: true }, ());
+        patched
+            .patch(
+                Some(&mut vcomp),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .unwrap();
+        patched
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<button disabled="false">Click</button>"#
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_invoke_mounted_after_nested_components_are_rendered() {
+        let div = container();
+        MOUNTED_HTML.with(|mounted| *mounted.borrow_mut() = Some((div.clone(), String::new())));
+
+        let mut vcomp = VComponent::new::<Toolbar>((), ());
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let html = MOUNTED_HTML.with(|mounted| mounted.borrow().as_ref().unwrap().1.clone());
+        assert_eq!(html, r#"<div><button disabled="false">Click</button></div>"#);
+    }
+
+    /// A future which resolves on the next animation frame.
+    fn next_frame() -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window().unwrap().request_animation_frame(&resolve).unwrap();
+        }))
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_mount_deferred_component_after_a_frame() -> impl Future<Item = (), Error = JsValue> {
+        let mut wrapper = ComponentWrapper::<Button, ()>::new(ButtonProps { disabled: false }, ());
+        wrapper.deferral = Some(Deferral::new(VNode::from(VText::text("Loading..."))));
+        let div = container();
+        wrapper
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "Loading...");
+
+        next_frame().map(move |_| {
+            // The rerender the frame requested.
+            wrapper
+                .render_walk(
+                    div.as_ref(),
+                    None,
+                    root_render_ctx(),
+                    crate::message_sender(),
+                )
+                .expect("To patch div");
+
+            assert_eq!(
+                div.inner_html(),
+                r#"<button disabled="false">Click</button>"#
+            );
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_not_mount_removed_deferred_component() -> impl Future<Item = (), Error = JsValue> {
+        let mut wrapper = ComponentWrapper::<Button, ()>::new(ButtonProps { disabled: false }, ());
+        wrapper.deferral = Some(Deferral::new(VNode::from(VText::text("Loading..."))));
+        let div = container();
+        wrapper
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        wrapper.remove(div.as_ref()).expect("To remove");
+        let is_ready = wrapper.deferral.as_ref().unwrap().is_ready.clone();
+
+        next_frame().map(move |_| {
+            assert!(!*is_ready.borrow());
+            assert_eq!(div.inner_html(), "");
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn should_invoke_before_destroy_from_parent_to_child() {
+        let div = container();
+        MOUNTED_HTML.with(|mounted| *mounted.borrow_mut() = Some((div.clone(), String::new())));
+        DESTROY_LOG.with(|log| log.borrow_mut().clear());
+
+        let mut vcomp = VComponent::new::<Toolbar>((), ());
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        vcomp.remove(div.as_ref()).expect("To remove from div");
+
+        assert_eq!(div.inner_html(), "");
+        DESTROY_LOG.with(|log| assert_eq!(*log.borrow(), vec!["toolbar", "button"]));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_invoke_updated_with_old_props_after_rerender() {
+        let div = container();
+        UPDATED_LOG.with(|updated| *updated.borrow_mut() = Some((div.clone(), vec![])));
+
+        let mut vcomp = VComponent::new::<Button>(ButtonProps { disabled: false }, ());
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let mut patched = VComponent::new::<Button>(ButtonProps { disabled: true }, ());
+        patched
+            .patch(
+                Some(&mut vcomp),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .unwrap();
+        patched
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let log = UPDATED_LOG.with(|updated| updated.borrow_mut().take().unwrap().1);
+        assert_eq!(
+            log,
+            vec![(false, r#"<button disabled="true">Click</button>"#.to_string())]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_propagate_errors_by_default() {
+        let button = Button::init(
+            ButtonProps { disabled: false },
+            (),
+            Status::new((), crate::message_sender()),
+        );
+
+        let error = button.on_error(Box::new("Failed to render")).err().unwrap();
+        assert_eq!(error.downcast_ref::<&str>(), Some(&"Failed to render"));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_render_fallback_of_boundary_on_render_error() {
+        let mut vcomp = VComponent::new::<Boundary>((), ());
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "Failed: No data");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_pass_render_error_through_non_boundaries_to_nearest_boundary() {
+        let mut vcomp = VComponent::new::<OuterBoundary>((), ());
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        // The div & span of the failed subtree are replaced by the fallback.
+        assert_eq!(div.inner_html(), "Caught: No data");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_fail_on_render_error_without_boundary() {
+        let mut vcomp = VComponent::new::<Broken>((), ());
+        let div = container();
+        let walked = vcomp.render_walk(
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        );
+
+        assert!(walked.is_err());
+        assert_eq!(div.inner_html(), "");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_rerender_memoized_component_with_equal_props() {
+        LABEL_RENDERS.with(|renders| *renders.borrow_mut() = 0);
+        let div = container();
+        let mut vcomp = VComponent::memo::<Label>(LabelProps { text: "Hello" }, ());
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let mut same = VComponent::memo::<Label>(LabelProps { text: "Hello" }, ());
+        same.patch(
+            Some(&mut vcomp),
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .unwrap();
+        same.render_walk(
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+        assert_eq!(LABEL_RENDERS.with(|renders| *renders.borrow()), 1);
+
+        let mut changed = VComponent::memo::<Label>(LabelProps { text: "World" }, ());
+        changed
+            .patch(
+                Some(&mut same),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .unwrap();
+        changed
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        assert_eq!(LABEL_RENDERS.with(|renders| *renders.borrow()), 2);
+        assert_eq!(div.inner_html(), "World");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_tear_down_old_keyed_component_before_new_one_is_created() {
+        SWAP_LOG.with(|log| log.borrow_mut().clear());
+        let div = container();
+        let mut vlist = keyed(1, VComponent::new::<Ticker>((), ()));
+        vlist
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        vlist
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let mut swapped = keyed(1, VComponent::new::<Stopwatch>((), ()));
+        swapped
+            .patch(
+                Some(&mut vlist),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        swapped
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "Watch");
+        SWAP_LOG.with(|log| {
+            assert_eq!(
+                *log.borrow(),
+                vec!["ticker:start", "ticker:stop", "stopwatch:created"]
+            );
+        });
+    }
+
+    #[wasm_bindgen_test]
+    fn should_reorder_mixed_keyed_elements_and_components() {
+        TALLY_INITS.with(|inits| *inits.borrow_mut() = 0);
+        let div = container();
+        let mut vlist = mixed(vec![(1, Some("A")), (2, None), (3, Some("C"))]);
+        vlist
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        vlist
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        assert_eq!(div.inner_html(), "<p>A</p>Tally 1<p>C</p>");
+
+        let mut reordered = mixed(vec![(3, Some("C")), (2, None), (1, Some("A"))]);
+        reordered
+            .patch(
+                Some(&mut vlist),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        reordered
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        // The component is moved along with its key instead of being created
+        // anew.
+        assert_eq!(div.inner_html(), "<p>C</p>Tally 1<p>A</p>");
+
+        let mut replaced = mixed(vec![(3, None), (2, None), (1, Some("A"))]);
+        replaced
+            .patch(
+                Some(&mut reordered),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        replaced
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        // Only the key whose type changed is replaced.
+        assert_eq!(div.inner_html(), "Tally 2Tally 1<p>A</p>");
     }
 }
@@ -1,10 +1,63 @@
 //! Element representation in a VDOM.
 
-use crate::{component::Render, dom::DOMPatch, vdom::VNode, MessageSender, Shared};
-use indexmap::IndexMap;
-use std::{borrow::Cow, cell::RefCell, rc::Rc};
+use crate::{
+    component::Render,
+    dom::{self, DOMPatch},
+    expect::ExpectMsg,
+    vdom::{
+        hydrate::{self, Hydrate},
+        ssr::{self, ToHtml},
+        vlist::VList,
+        NodeRef, VNode,
+    },
+    MessageSender, Shared,
+};
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt, mem,
+    rc::Rc,
+};
 use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{window, Element, Event, EventTarget, Node};
+use web_sys::{
+    window, Element, Event, EventTarget, HtmlElement, HtmlInputElement, HtmlSelectElement,
+    HtmlTextAreaElement, Node,
+};
+
+/// Transforms the value of an attribute, given its name, before it is set on
+/// the element.
+pub type AttributeTransform = fn(name: &str, value: &str) -> String;
+
+thread_local! {
+    /// The attribute transform of the App which is being rendered, if any.
+    static ATTRIBUTE_TRANSFORM: Cell<Option<AttributeTransform>> = Cell::new(None);
+}
+
+/// Applies the transform on all the attributes set while running `f`.
+pub(crate) fn with_attribute_transform<T>(
+    transform: Option<AttributeTransform>,
+    f: impl FnOnce() -> T,
+) -> T {
+    let previous = ATTRIBUTE_TRANSFORM.with(|current| current.replace(transform));
+    let result = f();
+    ATTRIBUTE_TRANSFORM.with(|current| current.set(previous));
+    result
+}
+
+/// The attribute transform of the App which is being rendered, if any.
+pub(crate) fn attribute_transform() -> Option<AttributeTransform> {
+    ATTRIBUTE_TRANSFORM.with(Cell::get)
+}
+
+/// The namespace of the SVG elements.
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+/// The namespace of the `xlink:*` attributes.
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+/// The namespace of the `xml:*` attributes.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+/// The namespace of the HTML elements.
+const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
 
 /// The representation of an element in virtual DOM.
 pub struct VElement<RCTX> {
@@ -16,12 +69,21 @@ pub struct VElement<RCTX> {
     event_listeners: EventListeners<RCTX>,
     /// The child node of the given element
     child: Box<VNode<RCTX>>,
+    /// Whether the child is the list made of the children appended with
+    /// `child`
+    appended: bool,
+    /// The namespace of the element, if it is not the one of its parent
+    namespace: Option<&'static str>,
+    /// The raw HTML set as the content of the element instead of its child
+    inner_html: Option<String>,
     /// Element reference to the DOM
     node: Option<Element>,
+    /// Reference to the element which is shared with the user
+    node_ref: Option<NodeRef>,
 }
 
-/// A list of attributes.
-struct Attributes(IndexMap<&'static str, AttributeValue>);
+/// A list of attributes along with their namespaces.
+struct Attributes(IndexMap<&'static str, (Option<&'static str>, AttributeValue)>);
 
 /// The key, value pair of the attributes on an element.
 pub struct Attribute {
@@ -29,6 +91,8 @@ pub struct Attribute {
     key: &'static str,
     /// The value pair of the attribute key
     value: AttributeValue,
+    /// The namespace of the attribute, if any
+    namespace: Option<&'static str>,
 }
 
 /// Either a string or a bool
@@ -37,10 +101,48 @@ pub enum AttributeValue {
     String(String),
     /// A boolean attribute value
     Bool(bool),
+    /// The properties of a `style` attribute, which are patched one by one
+    Style(CssProperties),
+    /// The classes of a `class` attribute, which are toggled one by one
+    Classes(ClassList),
     /// An optional attribute value
     None,
 }
 
+/// The properties of an inline style, to be passed to the `style` attribute
+/// in html! macro.
+///
+/// Unlike a string, only the properties which changed are set on the element
+/// and the ones which are gone are cleared, so the properties set on it by
+/// other means, e.g. an animation, are left alone.
+///
+/// # Example
+/// ```ignore
+/// html! {
+///     <div style={CssProperties::new().color("red").font_size("14px")}></div>
+/// }
+/// ```
+#[derive(Clone, Default, PartialEq)]
+pub struct CssProperties(IndexMap<String, String>);
+
+/// The classes of an element, to be passed to the `class` attribute in html!
+/// macro.
+///
+/// Unlike a string, only the classes which changed are added to or removed
+/// from the element, so the CSS transitions of the classes which stay are
+/// not restarted.
+///
+/// # Example
+/// ```ignore
+/// html! {
+///     <button class={ClassList::new().add("btn").add_if("active", self.active)}>
+///         "Save"
+///     </button>
+/// }
+/// ```
+#[derive(Clone, Default, PartialEq)]
+pub struct ClassList(IndexSet<String>);
+
 struct EventListeners<RCTX>(Vec<Box<dyn EventManager<RenderContext = RCTX>>>);
 
 /// Event listener to be invoked on a DOM event.
@@ -48,6 +150,10 @@ pub struct EventListener<RCTX> {
     type_: &'static str,
     listener: Option<Box<dyn Fn(&RCTX, Event)>>,
     dom_listener: Option<Closure<dyn Fn(Event)>>,
+    /// Whether to call `preventDefault()` on the event before the listener
+    prevent_default: bool,
+    /// Whether to call `stopPropagation()` on the event before the listener
+    stop_propagation: bool,
 }
 
 impl<RCTX: Render> VElement<RCTX> {
@@ -73,7 +179,11 @@ impl<RCTX: Render> VElement<RCTX> {
                     .collect(),
             ),
             child: Box::new(child),
+            appended: false,
+            namespace: None,
+            inner_html: None,
             node: None,
+            node_ref: None,
         }
     }
 
@@ -98,8 +208,180 @@ impl<RCTX: Render> VElement<RCTX> {
                     .collect(),
             ),
             child: Box::new(VNode::None),
+            appended: false,
+            namespace: None,
+            inner_html: None,
             node: None,
+            node_ref: None,
+        }
+    }
+
+    /// Create a VElement within a namespace, like the `<svg>` element in the
+    /// SVG namespace. Its descendants are created within the same namespace
+    /// unless specified otherwise.
+    pub fn namespaced(
+        namespace: &'static str,
+        tag: &'static str,
+        attributes: Vec<Attribute>,
+        event_listeners: Vec<EventListener<RCTX>>,
+        child: VNode<RCTX>,
+    ) -> VElement<RCTX> {
+        let mut el = VElement::new(tag, attributes, event_listeners, child);
+        el.namespace = Some(namespace);
+        el
+    }
+
+    /// Sets a NodeRef which is populated with the DOM element once it is
+    /// attached to the DOM.
+    pub fn with_node_ref(mut self, node_ref: NodeRef) -> VElement<RCTX> {
+        self.node_ref = Some(node_ref);
+        self
+    }
+
+    /// Sets the raw HTML as the content of the element, instead of its child.
+    ///
+    /// The HTML is set as is, so it must be sanitized beforehand if it is not
+    /// trusted. It is set again only when it changes, as setting it resets the
+    /// scroll position and the text input within.
+    pub fn dangerous_inner_html(mut self, html: impl Into<String>) -> VElement<RCTX> {
+        self.inner_html = Some(html.into());
+        self
+    }
+
+    /// Create a VElement with only a tag, to be built fluently.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let el = VElement::from_tag("button")
+    ///     .attr("class", "primary")
+    ///     .on("click", |this: &MyComponent, _: MouseEvent| this.submit())
+    ///     .child(VText::text("Submit"));
+    /// ```
+    pub fn from_tag(tag: &'static str) -> VElement<RCTX> {
+        VElement::childless(tag, vec![], vec![])
+    }
+
+    /// Sets an attribute on the element.
+    pub fn attr(mut self, key: &'static str, value: impl Into<AttributeValue>) -> VElement<RCTX> {
+        self.attributes.0.insert(key, (None, value.into()));
+        self
+    }
+
+    /// Adds a listener to the DOM event on the element.
+    pub fn on<E: JsCast + 'static>(
+        mut self,
+        type_: &'static str,
+        listener: impl Fn(&RCTX, E) + 'static,
+    ) -> VElement<RCTX> {
+        self.event_listeners
+            .0
+            .push(Box::new(EventListener::new(type_, Box::new(listener))));
+        self
+    }
+
+    /// Appends a child to the element. The children appended after the first
+    /// are keyed by their index.
+    pub fn child(mut self, child: impl Into<VNode<RCTX>>) -> VElement<RCTX> {
+        let child = child.into();
+        let current = mem::replace(&mut *self.child, VNode::None);
+        *self.child = match current {
+            VNode::None => child,
+            VNode::List(mut list) if self.appended => {
+                list.push(child);
+                VNode::List(list)
+            }
+            first => {
+                self.appended = true;
+                VNode::List(VList::from(vec![first, child]))
+            }
+        };
+        self
+    }
+
+    /// The tag of the element.
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// The value of the attribute, if it is set on the element.
+    pub fn attribute(&self, key: &str) -> Option<&AttributeValue> {
+        self.attributes.0.get(key).map(|(_, value)| value)
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VElement<RCTX> {
+    /// Collects the nodes in the subtree in document order.
+    pub(crate) fn collect_nodes<'a>(&'a self, nodes: &mut Vec<&'a VNode<RCTX>>) {
+        self.child.collect_nodes(nodes);
+    }
+
+    /// Writes the element deterministically, with its attributes sorted by
+    /// their names.
+    pub(crate) fn write_test_html(&self, html: &mut String) {
+        let mut attributes: Vec<_> = self
+            .attributes
+            .0
+            .iter()
+            .map(|(key, (_, value))| (initial_only(key).unwrap_or(*key), value))
+            .collect();
+        attributes.sort_by_key(|(key, _)| *key);
+
+        html.push('<');
+        html.push_str(self.tag);
+        let mut text_value = None;
+        for (key, value) in attributes {
+            if self.tag == "textarea" && key == "value" {
+                if let AttributeValue::String(val) = value {
+                    text_value = Some(val);
+                }
+                continue;
+            }
+            match value {
+                AttributeValue::String(val) => push_attribute(html, key, val),
+                AttributeValue::Style(props) => push_attribute(html, key, &props.to_string()),
+                AttributeValue::Classes(classes) => {
+                    push_attribute(html, key, &classes.to_string());
+                }
+                AttributeValue::Bool(true) => {
+                    html.push(' ');
+                    html.push_str(key);
+                }
+                AttributeValue::Bool(false) | AttributeValue::None => {}
+            }
+        }
+        html.push('>');
+        if ssr::is_void_element(self.tag) {
+            return;
+        }
+        match (text_value, &self.inner_html) {
+            (Some(value), _) => ssr::escape(value, html),
+            (None, Some(inner_html)) => html.push_str(inner_html),
+            (None, None) => self.child.write_test_html(html),
+        }
+        html.push_str("</");
+        html.push_str(self.tag);
+        html.push('>');
+    }
+
+    /// Finds the first element in the subtree, in document order, which
+    /// listens to the event type.
+    pub(crate) fn find_listening(&self, type_: &str) -> Option<&VElement<RCTX>> {
+        if self.event_listeners.0.iter().any(|l| l.listens_to(type_)) {
+            Some(self)
+        } else {
+            self.child.find_listening(type_)
+        }
+    }
+
+    /// Invokes the listeners of the event type, which are not yet attached to
+    /// the DOM. Returns whether any of them was invoked.
+    pub(crate) fn trigger(&self, type_: &str, render_ctx: &RCTX, event: Event) -> bool {
+        let mut triggered = false;
+        for listener in self.event_listeners.0.iter() {
+            triggered |= listener.trigger(type_, render_ctx, event.clone());
         }
+        triggered
     }
 }
 
@@ -109,19 +391,97 @@ impl Attribute {
         Attribute {
             key,
             value: value.into(),
+            namespace: None,
+        }
+    }
+
+    /// Create a namespaced Attribute for a VElement. The key is the qualified
+    /// name of the attribute. Eg: `xlink:href`.
+    pub fn namespaced(
+        namespace: &'static str,
+        key: &'static str,
+        value: impl Into<AttributeValue>,
+    ) -> Attribute {
+        Attribute {
+            key,
+            value: value.into(),
+            namespace: Some(namespace),
         }
     }
 }
 
 impl<RCTX> EventListener<RCTX> {
-    /// Create a EventListener.
-    pub fn new(type_: &'static str, listener: Box<dyn Fn(&RCTX, Event)>) -> EventListener<RCTX> {
+    /// Create a EventListener which receives the event as the type `E`, such
+    /// as a `MouseEvent` for a `click`.
+    ///
+    /// The event is not checked to be of the type `E`, so it must be the type
+    /// the browsers dispatch for the event type.
+    pub fn new<E: JsCast + 'static>(
+        type_: &'static str,
+        listener: Box<dyn Fn(&RCTX, E)>,
+    ) -> EventListener<RCTX>
+    where
+        RCTX: 'static,
+    {
         EventListener {
             type_,
-            listener: Some(listener),
+            listener: Some(Box::new(move |render_ctx, event: Event| {
+                listener(render_ctx, event.unchecked_into())
+            })),
             dom_listener: None,
+            prevent_default: false,
+            stop_propagation: false,
         }
     }
+
+    /// Prevents the default action of the event, such as following a link,
+    /// before the listener is invoked.
+    pub fn prevent_default(mut self) -> EventListener<RCTX> {
+        self.prevent_default = true;
+        self
+    }
+
+    /// Stops the event from propagating to the ancestors of the element
+    /// before the listener is invoked.
+    pub fn stop_propagation(mut self) -> EventListener<RCTX> {
+        self.stop_propagation = true;
+        self
+    }
+}
+
+#[cfg(feature = "async-events")]
+impl<RCTX: 'static> EventListener<RCTX> {
+    /// Create a EventListener which returns a future, spawned to run along
+    /// with the rest of the app. The future cannot borrow the component, so
+    /// use a `StateSetter` to change the state once it resolves, which is
+    /// then rendered as usual.
+    ///
+    /// Requires the `async-events` feature.
+    pub fn new_async<E, F>(
+        type_: &'static str,
+        listener: Box<dyn Fn(&RCTX, E) -> F>,
+    ) -> EventListener<RCTX>
+    where
+        E: JsCast + 'static,
+        F: futures::Future<Item = (), Error = ()> + 'static,
+    {
+        EventListener::new(
+            type_,
+            Box::new(move |render_ctx: &RCTX, event: E| {
+                wasm_bindgen_futures::spawn_local(listener(render_ctx, event))
+            }),
+        )
+    }
+}
+
+/// Applies the modifiers of an event listener on the event.
+fn apply_modifiers(prevent_default: bool, stop_propagation: bool, event: &Event) {
+    if prevent_default {
+        event.prevent_default();
+    }
+    if stop_propagation {
+        event.stop_propagation();
+    }
 }
 
 impl<RCTX> From<VElement<RCTX>> for VNode<RCTX> {
@@ -130,6 +490,61 @@ impl<RCTX> From<VElement<RCTX>> for VNode<RCTX> {
     }
 }
 
+impl<RCTX> VElement<RCTX> {
+    /// Whether it is a form control whose value is set but never updated
+    /// from what the user enters, as there is no listener to do so.
+    #[cfg(any(debug_assertions, test))]
+    fn has_read_only_value(&self) -> bool {
+        const UPDATING_EVENTS: &[&str] = &["input", "change"];
+        const VALUELESS_TYPES: &[&str] = &[
+            "button", "checkbox", "hidden", "image", "radio", "reset", "submit",
+        ];
+        let is_set = |key: &str| match self.attributes.0.get(key) {
+            Some((_, AttributeValue::None)) | Some((_, AttributeValue::Bool(false))) | None => {
+                false
+            }
+            _ => true,
+        };
+        let is_valueless = match self.attributes.0.get("type") {
+            Some((_, AttributeValue::String(type_))) => VALUELESS_TYPES.contains(&type_.as_str()),
+            _ => false,
+        };
+        let is_updated = self
+            .event_listeners
+            .0
+            .iter()
+            .any(|listener| UPDATING_EVENTS.contains(&listener.type_()));
+
+        ["input", "textarea", "select"].contains(&self.tag)
+            && is_set("value")
+            && !is_valueless
+            && !is_updated
+            && !is_set("readonly")
+            && !is_set("disabled")
+    }
+
+    /// Warns about a form control which the user cannot change, as it is
+    /// likely missing an `@input` listener.
+    #[cfg(debug_assertions)]
+    fn warn_read_only_value(&self) {
+        if self.has_read_only_value() {
+            web_sys::console::warn_1(
+                &format!(
+                    "The <{}> has a value but no @input or @change listener to update it, \
+                     so the user cannot change it. Add a listener, or use `default-value` \
+                     to only set it initially.",
+                    self.tag
+                )
+                .into(),
+            );
+        }
+    }
+
+    /// The form controls are not checked in the release builds.
+    #[cfg(not(debug_assertions))]
+    fn warn_read_only_value(&self) {}
+}
+
 impl<RCTX: Render> VElement<RCTX> {
     fn patch_new(
         &mut self,
@@ -138,11 +553,16 @@ impl<RCTX: Render> VElement<RCTX> {
         render_ctx: Shared<RCTX>,
         rx_sender: MessageSender,
     ) -> Result<(), JsValue> {
-        let el = window()
-            .unwrap()
-            .document()
-            .unwrap()
-            .create_element(&self.tag)?;
+        self.warn_read_only_value();
+        let document = window().unwrap().document().unwrap();
+        let el = match self
+            .namespace
+            .map(String::from)
+            .or_else(|| namespace_within(parent))
+        {
+            Some(namespace) => document.create_element_ns(Some(&namespace), &self.tag)?,
+            None => document.create_element(&self.tag)?,
+        };
         self.attributes.patch(
             None,
             &el,
@@ -154,12 +574,54 @@ impl<RCTX: Render> VElement<RCTX> {
             .patch(None, &el, None, render_ctx.clone(), rx_sender.clone())?;
         self.child
             .patch(None, el.as_ref(), None, render_ctx, rx_sender)?;
+        if let Some(ref html) = self.inner_html {
+            el.set_inner_html(html);
+        }
         parent.insert_before(el.as_ref(), next)?;
+        if let Some(ref node_ref) = self.node_ref {
+            node_ref.set(Some(el.clone()));
+        }
         self.node = Some(el);
         Ok(())
     }
 }
 
+impl<RCTX> fmt::Debug for VElement<RCTX> {
+    /// Writes the element like a struct named by its tag, with the
+    /// attributes, the event listeners and the child as its fields.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut el = f.debug_struct(self.tag);
+        for (key, (_, value)) in self.attributes.0.iter() {
+            el.field(key, value);
+        }
+        for listener in self.event_listeners.0.iter() {
+            el.field(&format!("@{}", listener.type_()), &format_args!(".."));
+        }
+        if let Some(ref html) = self.inner_html {
+            el.field("inner_html", html);
+        }
+        match *self.child {
+            VNode::None => {}
+            ref child => {
+                el.field("child", child);
+            }
+        }
+        el.finish()
+    }
+}
+
+impl fmt::Debug for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::String(ref value) => value.fmt(f),
+            AttributeValue::Bool(value) => value.fmt(f),
+            AttributeValue::Style(props) => fmt::Debug::fmt(props, f),
+            AttributeValue::Classes(classes) => fmt::Debug::fmt(classes, f),
+            AttributeValue::None => f.write_str("None"),
+        }
+    }
+}
+
 impl<RCTX: Render> DOMPatch for VElement<RCTX> {
     type RenderContext = RCTX;
     type Node = Node;
@@ -174,7 +636,7 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
         let node = self
             .node
             .as_ref()
-            .expect("The element itself must be patched before rendering the child");
+            .expect_msg("The element itself must be patched before rendering the child");
         self.child
             .render_walk(node.as_ref(), None, render_ctx, rx_sender)
     }
@@ -192,7 +654,7 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
                 let old_el = old
                     .node
                     .as_ref()
-                    .expect("The old node is expected to be attached to the DOM");
+                    .expect_msg("The old node is expected to be attached to the DOM");
                 self.attributes.patch(
                     Some(&mut old.attributes),
                     &old_el,
@@ -207,14 +669,41 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
                     render_ctx.clone(),
                     rx_sender.clone(),
                 )?;
-                self.child.patch(
-                    Some(&mut *old.child),
-                    old_el.as_ref(),
-                    None,
-                    render_ctx.clone(),
-                    rx_sender,
-                )?;
+                let inner_html_changed = self.inner_html != old.inner_html;
+                if inner_html_changed && old.inner_html.is_some() {
+                    old_el.set_inner_html("");
+                }
+                if self.child.renders_nothing()
+                    && !old.child.renders_nothing()
+                    && old.inner_html.is_none()
+                {
+                    // Clearing all of the children at once is faster than
+                    // removing them one by one.
+                    old_el.set_text_content(None);
+                    dom::with_detached(true, || old.child.remove(old_el.as_ref()))?;
+                } else {
+                    self.child.patch(
+                        Some(&mut *old.child),
+                        old_el.as_ref(),
+                        None,
+                        render_ctx.clone(),
+                        rx_sender,
+                    )?;
+                }
+                if inner_html_changed {
+                    if let Some(ref html) = self.inner_html {
+                        old_el.set_inner_html(html);
+                    }
+                }
 
+                if let Some(ref old_ref) = old.node_ref {
+                    if self.node_ref.as_ref() != Some(old_ref) {
+                        old_ref.set(None);
+                    }
+                }
+                if let Some(ref node_ref) = self.node_ref {
+                    node_ref.set(Some(old_el.clone()));
+                }
                 self.node = Some(old_el.clone());
                 Ok(())
             } else {
@@ -236,10 +725,16 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
         let el = self
             .node
             .as_ref()
-            .expect("The old node is expected to be attached to the DOM");
+            .expect_msg("The old node is expected to be attached to the DOM");
         self.child.remove(el.as_ref())?;
         self.attributes.remove(&el)?;
-        parent.remove_child(el.as_ref())?;
+        // Stop listening before the listeners are dropped along with the
+        // element, as the DOM node may still be reachable.
+        self.event_listeners.remove(&el)?;
+        dom::remove_child(parent, el.as_ref())?;
+        if let Some(ref node_ref) = self.node_ref {
+            node_ref.set(None);
+        }
         Ok(())
     }
 
@@ -271,30 +766,52 @@ impl DOMPatch for Attributes {
         _: MessageSender,
     ) -> Result<(), JsValue> {
         debug_assert!(next.is_none());
-        for (k, v) in self.0.iter() {
+        let is_created = old.is_none();
+        for (k, (ns, v)) in self.0.iter() {
             // Remove the key from old as it exists in the newer.
-            let existed = if let Some(ref mut old) = old {
-                match old.0.swap_remove(k) {
-                    Some(AttributeValue::None) | None => false,
-                    _ => true,
-                }
-            } else {
-                false
+            let old_value = match old {
+                Some(ref mut old) => old.0.swap_remove(k).map(|(_, v)| v),
+                None => None,
+            };
+            let existed = match old_value {
+                Some(AttributeValue::None) | None => false,
+                _ => true,
+            };
+            let k = match initial_only(k) {
+                // Set only once, so that the user is free to change it after.
+                Some(_) if !is_created => continue,
+                Some(initial) => initial,
+                None => *k,
             };
             match v {
                 AttributeValue::String(val) => {
-                    parent.set_attribute(&k, &val)?;
+                    set_attribute(parent, *ns, k, val)?;
+                    if k == "value" {
+                        set_value_property(parent, val);
+                    }
                 }
+                AttributeValue::Style(props) => match old_value {
+                    Some(AttributeValue::Style(ref old_props)) => {
+                        props.patch(old_props, parent)?;
+                    }
+                    _ => set_attribute(parent, *ns, k, &props.to_string())?,
+                },
+                AttributeValue::Classes(classes) => match old_value {
+                    Some(AttributeValue::Classes(ref old_classes)) => {
+                        classes.patch(old_classes, parent)?;
+                    }
+                    _ => set_attribute(parent, *ns, k, &classes.to_string())?,
+                },
                 AttributeValue::Bool(truthy) => {
                     if *truthy {
-                        parent.set_attribute(&k, "")?;
+                        set_attribute(parent, *ns, k, "")?;
                     } else if existed {
-                        parent.remove_attribute(&k)?;
+                        remove_attribute(parent, *ns, k)?;
                     }
                 }
                 AttributeValue::None => {
                     if existed {
-                        parent.remove_attribute(&k)?;
+                        remove_attribute(parent, *ns, k)?;
                     }
                 }
             }
@@ -311,8 +828,11 @@ impl DOMPatch for Attributes {
     }
 
     fn remove(&self, parent: &Element) -> Result<(), JsValue> {
-        for (k, _) in self.0.iter() {
-            parent.remove_attribute(&k)?;
+        for (k, (ns, _)) in self.0.iter() {
+            // Left as the user has changed it, as it was set only once.
+            if initial_only(k).is_none() {
+                remove_attribute(parent, *ns, k)?;
+            }
         }
         Ok(())
     }
@@ -322,6 +842,95 @@ impl DOMPatch for Attributes {
     }
 }
 
+/// Writes the attribute with its value escaped, when rendered to a string.
+fn push_attribute(html: &mut String, key: &str, value: &str) {
+    html.push(' ');
+    html.push_str(key);
+    html.push_str("=\"");
+    ssr::escape(value, html);
+    html.push('"');
+}
+
+/// The `default-checked`, `default-selected` & `default-value` attributes are
+/// set only when the element is created, like `default-checked` on a radio,
+/// which sets the `checked` attribute without fighting with the user changes
+/// afterwards. Returns the name of the attribute to be set, if it is one of
+/// those.
+fn initial_only(key: &str) -> Option<&str> {
+    match key {
+        "default-checked" => Some("checked"),
+        "default-selected" => Some("selected"),
+        "default-value" => Some("value"),
+        _ => None,
+    }
+}
+
+/// The namespace in which the children of the parent are created. The
+/// descendants of an `<svg>` are in the SVG namespace too, except the ones
+/// within a `<foreignObject>`.
+fn namespace_within(parent: &Node) -> Option<String> {
+    let parent = parent.dyn_ref::<Element>()?;
+    let namespace = parent.namespace_uri()?;
+    if namespace == HTML_NAMESPACE || parent.local_name() == "foreignObject" {
+        None
+    } else {
+        Some(namespace)
+    }
+}
+
+/// Sets the attribute on the element, within the namespace if given.
+fn set_attribute(
+    el: &Element,
+    namespace: Option<&str>,
+    key: &str,
+    value: &str,
+) -> Result<(), JsValue> {
+    let transformed = ATTRIBUTE_TRANSFORM
+        .with(|transform| transform.get())
+        .map(|transform| transform(key, value));
+    let value = transformed.as_ref().map_or(value, String::as_str);
+    match namespace {
+        Some(ns) => el.set_attribute_ns(Some(ns), key, value),
+        None => el.set_attribute(key, value),
+    }
+}
+
+/// Sets the `value` property of a form control, which is what it shows. The
+/// `value` attribute only sets its initial value, which is no longer shown once
+/// the user has edited it, or at all in case of a `<textarea>`.
+///
+/// It is left alone when it is already the same, so that the cursor is not
+/// moved while the user is typing.
+fn set_value_property(el: &Element, value: &str) {
+    if let Some(input) = el.dyn_ref::<HtmlInputElement>() {
+        if input.value() != value {
+            input.set_value(value);
+        }
+    } else if let Some(textarea) = el.dyn_ref::<HtmlTextAreaElement>() {
+        if textarea.value() != value {
+            textarea.set_value(value);
+        }
+    } else if let Some(select) = el.dyn_ref::<HtmlSelectElement>() {
+        if select.value() != value {
+            select.set_value(value);
+        }
+    }
+}
+
+/// Removes the attribute from the element, within the namespace if given.
+///
+/// The namespaced attributes are removed by their local name i.e. `href` in
+/// case of `xlink:href`.
+fn remove_attribute(el: &Element, namespace: Option<&str>, key: &str) -> Result<(), JsValue> {
+    match namespace {
+        Some(ns) => {
+            let local_name = key.rsplit(':').next().unwrap_or(key);
+            el.remove_attribute_ns(Some(ns), local_name)
+        }
+        None => el.remove_attribute(key),
+    }
+}
+
 impl<RCTX: Render> DOMPatch for EventListeners<RCTX> {
     type RenderContext = RCTX;
     type Node = Element;
@@ -379,6 +988,18 @@ trait EventManager {
     ) -> Result<(), JsValue>;
 
     fn stop_listening(&self, parent: &EventTarget) -> Result<(), JsValue>;
+
+    /// The event type it listens to.
+    fn type_(&self) -> &str;
+
+    /// Whether it listens to the event type.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn listens_to(&self, type_: &str) -> bool;
+
+    /// Invokes the listener if it listens to the event type and is not yet
+    /// attached to the DOM. Returns whether it was invoked.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn trigger(&self, type_: &str, render_ctx: &Self::RenderContext, event: Event) -> bool;
 }
 
 impl<RCTX: Render> EventManager for EventListener<RCTX> {
@@ -390,7 +1011,9 @@ impl<RCTX: Render> EventManager for EventListener<RCTX> {
         render_ctx: Shared<Self::RenderContext>,
     ) -> Result<(), JsValue> {
         let listener = self.listener.take().unwrap();
-        let js_closure: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |event| {
+        let (prevent_default, stop_propagation) = (self.prevent_default, self.stop_propagation);
+        let js_closure: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |event: Event| {
+            apply_modifiers(prevent_default, stop_propagation, &event);
             listener(&*render_ctx.borrow(), event)
         }));
         parent
@@ -408,90 +1031,474 @@ impl<RCTX: Render> EventManager for EventListener<RCTX> {
         }
         Ok(())
     }
-}
 
-impl From<bool> for AttributeValue {
-    fn from(val: bool) -> AttributeValue {
-        AttributeValue::Bool(val)
+    fn type_(&self) -> &str {
+        self.type_
     }
-}
 
-impl<'a> From<&'a str> for AttributeValue {
-    fn from(val: &'a str) -> AttributeValue {
-        AttributeValue::String(val.to_string())
+    #[cfg(any(test, feature = "test-utils"))]
+    fn listens_to(&self, type_: &str) -> bool {
+        self.type_ == type_
     }
-}
 
-impl From<String> for AttributeValue {
-    fn from(val: String) -> AttributeValue {
-        AttributeValue::String(val)
+    #[cfg(any(test, feature = "test-utils"))]
+    fn trigger(&self, type_: &str, render_ctx: &Self::RenderContext, event: Event) -> bool {
+        match self.listener {
+            Some(ref listener) if self.type_ == type_ => {
+                apply_modifiers(self.prevent_default, self.stop_propagation, &event);
+                listener(render_ctx, event);
+                true
+            }
+            _ => false,
+        }
     }
 }
 
-impl<'a> From<Cow<'a, str>> for AttributeValue {
-    fn from(val: Cow<'a, str>) -> AttributeValue {
-        AttributeValue::String(val.into())
+impl<RCTX: Render> Hydrate for VElement<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        let tag = self.tag;
+        let existing = node
+            .as_ref()
+            .and_then(|node| node.dyn_ref::<Element>())
+            .filter(|el| el.tag_name().eq_ignore_ascii_case(tag))
+            .cloned();
+        let el = match existing {
+            Some(el) => el,
+            None => return hydrate::rerender(self, tag, parent, node, render_ctx, rx_sender),
+        };
+        // Setting the attributes again is cheap and fixes any that mismatch.
+        self.attributes.patch(
+            None,
+            &el,
+            None,
+            Rc::new(RefCell::new(())),
+            rx_sender.clone(),
+        )?;
+        self.event_listeners
+            .patch(None, &el, None, render_ctx.clone(), rx_sender.clone())?;
+        match self.inner_html {
+            Some(ref html) => {
+                // The browser may serialize the same HTML differently, so it
+                // is not warned about.
+                if el.inner_html() != *html {
+                    el.set_inner_html(html);
+                }
+            }
+            None => {
+                let rest = self.child.hydrate_walk(
+                    el.as_ref(),
+                    el.first_child(),
+                    render_ctx,
+                    rx_sender,
+                )?;
+                hydrate::remove_rest(el.as_ref(), rest)?;
+            }
+        }
+        if let Some(ref node_ref) = self.node_ref {
+            node_ref.set(Some(el.clone()));
+        }
+        let next = el.next_sibling();
+        self.node = Some(el);
+        Ok(next)
     }
 }
 
-impl<'a, T: Into<AttributeValue>> From<Option<T>> for AttributeValue {
-    fn from(val: Option<T>) -> AttributeValue {
-        match val {
-            Some(val) => val.into(),
-            None => AttributeValue::None,
+impl<RCTX: Render> ToHtml for VElement<RCTX> {
+    type RenderContext = RCTX;
+
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>) {
+        html.push('<');
+        html.push_str(self.tag);
+        // The value of a textarea is its text content.
+        let mut text_value = None;
+        for (key, (_, value)) in self.attributes.0.iter() {
+            let key = initial_only(key).unwrap_or(*key);
+            if self.tag == "textarea" && key == "value" {
+                if let AttributeValue::String(val) = value {
+                    text_value = Some(val);
+                }
+                continue;
+            }
+            match value {
+                AttributeValue::String(val) => push_attribute(html, key, val),
+                AttributeValue::Style(props) => push_attribute(html, key, &props.to_string()),
+                AttributeValue::Classes(classes) => {
+                    push_attribute(html, key, &classes.to_string());
+                }
+                AttributeValue::Bool(true) => {
+                    html.push(' ');
+                    html.push_str(key);
+                }
+                AttributeValue::Bool(false) | AttributeValue::None => {}
+            }
+        }
+        html.push('>');
+        if ssr::is_void_element(self.tag) {
+            return;
+        }
+        let content_start = html.len();
+        match (text_value, &self.inner_html) {
+            (Some(value), _) => ssr::escape(value, html),
+            (None, Some(inner_html)) => html.push_str(inner_html),
+            (None, None) => self.child.to_html(html, render_ctx),
+        }
+        // The parsers drop the newline right after the opening tag of these,
+        // so an extra one keeps the leading newline of the content.
+        if ssr::drops_leading_newline(self.tag) && html[content_start..].starts_with('\n') {
+            html.insert(content_start, '\n');
         }
+        html.push_str("</");
+        html.push_str(self.tag);
+        html.push('>');
     }
 }
 
-impl From<Vec<Attribute>> for Attributes {
-    fn from(val: Vec<Attribute>) -> Attributes {
-        let attrs = val.into_iter().map(|attr| (attr.key, attr.value)).collect();
-        Attributes(attrs)
+impl AttributeValue {
+    /// Appends the class name to the value of a `class` attribute, or adds it
+    /// to its `ClassList`, if enabled.
+    ///
+    /// Used by the `class:name={cond}` directives in html! macro.
+    pub fn with_class(self, class: &str, enabled: bool) -> AttributeValue {
+        if !enabled {
+            return self;
+        }
+        match self {
+            AttributeValue::Classes(classes) => AttributeValue::Classes(classes.add(class)),
+            AttributeValue::String(mut classes) => {
+                if !classes.is_empty() {
+                    classes.push(' ');
+                }
+                classes.push_str(class);
+                AttributeValue::String(classes)
+            }
+            _ => AttributeValue::String(class.to_string()),
+        }
+    }
+
+    /// Appends the property declaration to the value of a `style` attribute,
+    /// or sets it on its `CssProperties`. The property is skipped if the value
+    /// is not a string.
+    ///
+    /// Used by the `style:property={value}` directives in html! macro. CSS
+    /// custom properties like `--main-color` are supported as well.
+    pub fn with_style(self, property: &str, value: impl Into<AttributeValue>) -> AttributeValue {
+        let value = match value.into() {
+            AttributeValue::String(value) => value,
+            _ => return self,
+        };
+        let mut style = match self {
+            AttributeValue::Style(props) => {
+                return AttributeValue::Style(props.prop(property, &value));
+            }
+            AttributeValue::String(style) => style.trim_end().trim_end_matches(';').to_string(),
+            _ => String::new(),
+        };
+        if !style.is_empty() {
+            style.push_str("; ");
+        }
+        style.push_str(property);
+        style.push_str(": ");
+        style.push_str(&value);
+        AttributeValue::String(style)
     }
 }
 
-#[cfg(test)]
-pub mod test {
-    use super::*;
-    use crate::{component::root_render_ctx, vdom::test::container};
-    use wasm_bindgen_test::*;
+/// Defines the builder methods of `CssProperties` for the common properties.
+macro_rules! css_properties {
+    ($($method:ident => $property:expr,)*) => {
+        impl CssProperties {
+            $(
+                /// Sets the property the method is named after.
+                pub fn $method(self, value: &str) -> CssProperties {
+                    self.prop($property, value)
+                }
+            )*
+        }
+    };
+}
 
-    #[wasm_bindgen_test]
-    fn should_patch_container_with_button_element() {
-        let mut button_el = VElement::childless("button", vec![], vec![]);
-        let div = container();
-        button_el
-            .patch(
-                None,
-                div.as_ref(),
-                None,
-                root_render_ctx(),
-                crate::message_sender(),
-            )
-            .expect("To patch div");
+css_properties! {
+    color => "color",
+    background => "background",
+    background_color => "background-color",
+    font_size => "font-size",
+    font_weight => "font-weight",
+    font_family => "font-family",
+    text_align => "text-align",
+    display => "display",
+    position => "position",
+    top => "top",
+    right => "right",
+    bottom => "bottom",
+    left => "left",
+    width => "width",
+    height => "height",
+    margin => "margin",
+    padding => "padding",
+    border => "border",
+    border_radius => "border-radius",
+    flex => "flex",
+    flex_direction => "flex-direction",
+    flex_wrap => "flex-wrap",
+    justify_content => "justify-content",
+    align_items => "align-items",
+    gap => "gap",
+    overflow => "overflow",
+    opacity => "opacity",
+    transform => "transform",
+    transition => "transition",
+    cursor => "cursor",
+    z_index => "z-index",
+}
 
-        assert_eq!(div.inner_html(), "<button></button>");
+impl CssProperties {
+    /// Creates an empty inline style.
+    pub fn new() -> CssProperties {
+        CssProperties::default()
     }
 
-    #[wasm_bindgen_test]
-    fn should_patch_container_with_button_with_attrs() {
-        let mut button_el = VElement::childless(
-            "button",
-            vec![
-                Attribute::new("disabled", "true"),
-                Attribute::new("class", "bg-white txt-black"),
-            ],
-            vec![],
-        );
-        let div = container();
-        button_el
-            .patch(
-                None,
-                div.as_ref(),
-                None,
-                root_render_ctx(),
-                crate::message_sender(),
-            )
+    /// Sets any property, including the CSS custom properties like
+    /// `--main-color`. Setting a property again overrides its value.
+    pub fn prop(mut self, name: &str, value: &str) -> CssProperties {
+        self.0.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Gets the value of the property, if set.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Sets the properties which changed since the older ones on the element
+    /// and clears the ones which are gone.
+    fn patch(&self, old: &CssProperties, el: &Element) -> Result<(), JsValue> {
+        let style = match el.dyn_ref::<HtmlElement>() {
+            Some(el) => el.style(),
+            // Only the HTML elements expose their inline style here.
+            None => return set_attribute(el, None, "style", &self.to_string()),
+        };
+        for (name, value) in self.0.iter() {
+            if old.0.get(name) != Some(value) {
+                style.set_property(name, value)?;
+            }
+        }
+        for name in old.0.keys() {
+            if !self.0.contains_key(name) {
+                style.remove_property(name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CssProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (name, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}: {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CssProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), f)
+    }
+}
+
+impl ClassList {
+    /// Creates an empty list of classes.
+    pub fn new() -> ClassList {
+        ClassList::default()
+    }
+
+    /// Adds the class. A class is added only once.
+    pub fn add(mut self, class: &str) -> ClassList {
+        self.0.insert(class.to_string());
+        self
+    }
+
+    /// Adds the class, if enabled.
+    pub fn add_if(self, class: &str, enabled: bool) -> ClassList {
+        if enabled {
+            self.add(class)
+        } else {
+            self
+        }
+    }
+
+    /// Adds all of the classes.
+    pub fn add_many<I>(self, classes: I) -> ClassList
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        classes
+            .into_iter()
+            .fold(self, |list, class| list.add(class.as_ref()))
+    }
+
+    /// Whether the class is in the list.
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.contains(class)
+    }
+
+    /// Adds the classes which are new since the older ones to the element
+    /// and removes the ones which are gone.
+    fn patch(&self, old: &ClassList, el: &Element) -> Result<(), JsValue> {
+        let class_list = el.class_list();
+        for class in self.0.iter() {
+            if !old.0.contains(class) {
+                class_list.add_1(class)?;
+            }
+        }
+        for class in old.0.iter() {
+            if !self.0.contains(class) {
+                class_list.remove_1(class)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a str> for ClassList {
+    /// Creates a list of the whitespace separated classes.
+    fn from(classes: &'a str) -> ClassList {
+        ClassList::new().add_many(classes.split_whitespace())
+    }
+}
+
+impl fmt::Display for ClassList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, class) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ClassList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), f)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(val: bool) -> AttributeValue {
+        AttributeValue::Bool(val)
+    }
+}
+
+impl From<CssProperties> for AttributeValue {
+    fn from(val: CssProperties) -> AttributeValue {
+        AttributeValue::Style(val)
+    }
+}
+
+impl From<ClassList> for AttributeValue {
+    fn from(val: ClassList) -> AttributeValue {
+        AttributeValue::Classes(val)
+    }
+}
+
+impl<'a> From<&'a str> for AttributeValue {
+    fn from(val: &'a str) -> AttributeValue {
+        AttributeValue::String(val.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(val: String) -> AttributeValue {
+        AttributeValue::String(val)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for AttributeValue {
+    fn from(val: Cow<'a, str>) -> AttributeValue {
+        AttributeValue::String(val.into())
+    }
+}
+
+impl<'a, T: Into<AttributeValue>> From<Option<T>> for AttributeValue {
+    fn from(val: Option<T>) -> AttributeValue {
+        match val {
+            Some(val) => val.into(),
+            None => AttributeValue::None,
+        }
+    }
+}
+
+impl From<Vec<Attribute>> for Attributes {
+    fn from(val: Vec<Attribute>) -> Attributes {
+        let attrs = val
+            .into_iter()
+            .map(|attr| (attr.key, (attr.namespace, attr.value)))
+            .collect();
+        Attributes(attrs)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        vdom::{test::container, vtext::VText, Key},
+        web_api::MouseEvent,
+    };
+    use fnv::FnvBuildHasher;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_button_element() {
+        let mut button_el = VElement::childless("button", vec![], vec![]);
+        let div = container();
+        button_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "<button></button>");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_button_with_attrs() {
+        let mut button_el = VElement::childless(
+            "button",
+            vec![
+                Attribute::new("disabled", "true"),
+                Attribute::new("class", "bg-white txt-black"),
+            ],
+            vec![],
+        );
+        let div = container();
+        button_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
             .expect("To patch div");
 
         assert_eq!(
@@ -599,4 +1606,769 @@ pub mod test {
             r#"<div class="bg-white txt-black" id="main"></div>"#
         )
     }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_namespaced_attribute() {
+        const XLINK: &str = "http://www.w3.org/1999/xlink";
+        let mut use_el = VElement::childless(
+            "use",
+            vec![Attribute::namespaced(XLINK, "xlink:href", "#icon")],
+            vec![],
+        );
+        let div = container();
+        use_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let el = div.first_element_child().unwrap();
+        assert_eq!(el.get_attribute_ns(Some(XLINK), "href"), Some("#icon".to_string()));
+
+        let mut use_diff = VElement::childless("use", vec![], vec![]);
+        use_diff
+            .patch(
+                Some(&mut use_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(el.get_attribute_ns(Some(XLINK), "href"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_toggled_classes() {
+        let mut div_el = VElement::childless(
+            "div",
+            vec![Attribute::new(
+                "class",
+                AttributeValue::from("btn")
+                    .with_class("active", true)
+                    .with_class("hidden", false),
+            )],
+            vec![],
+        );
+        let div = container();
+        div_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<div class="btn active"></div>"#);
+
+        let mut div_diff = VElement::childless(
+            "div",
+            vec![Attribute::new(
+                "class",
+                AttributeValue::from("btn")
+                    .with_class("active", false)
+                    .with_class("hidden", true),
+            )],
+            vec![],
+        );
+        div_diff
+            .patch(
+                Some(&mut div_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<div class="btn hidden"></div>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_style_properties() {
+        let mut div_el = VElement::childless(
+            "div",
+            vec![Attribute::new(
+                "style",
+                AttributeValue::from("margin: 0;")
+                    .with_style("color", "red")
+                    .with_style("--main-color", "blue"),
+            )],
+            vec![],
+        );
+        let div = container();
+        div_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<div style="margin: 0; color: red; --main-color: blue"></div>"#
+        );
+
+        let mut div_diff = VElement::childless(
+            "div",
+            vec![Attribute::new(
+                "style",
+                AttributeValue::None
+                    .with_style("color", None::<&str>)
+                    .with_style("--main-color", "green"),
+            )],
+            vec![],
+        );
+        div_diff
+            .patch(
+                Some(&mut div_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<div style="--main-color: green"></div>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_only_the_changed_css_properties() {
+        let mut div_el = VElement::childless(
+            "div",
+            vec![Attribute::new(
+                "style",
+                CssProperties::new().color("red").font_size("14px"),
+            )],
+            vec![],
+        );
+        let div = container();
+        div_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<div style="color: red; font-size: 14px"></div>"#
+        );
+
+        let style = div_el
+            .node()
+            .unwrap()
+            .dyn_ref::<HtmlElement>()
+            .unwrap()
+            .style();
+        // Set by something else, like an animation.
+        style.set_property("opacity", "0.5").unwrap();
+
+        let mut div_diff = VElement::childless(
+            "div",
+            vec![Attribute::new(
+                "style",
+                AttributeValue::from(CssProperties::new().color("blue")).with_style("top", "0"),
+            )],
+            vec![],
+        );
+        div_diff
+            .patch(
+                Some(&mut div_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(style.get_property_value("color").unwrap(), "blue");
+        assert_eq!(style.get_property_value("top").unwrap(), "0px");
+        assert_eq!(style.get_property_value("font-size").unwrap(), "");
+        assert_eq!(style.get_property_value("opacity").unwrap(), "0.5");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_toggle_only_the_changed_classes() {
+        let mut button_el = VElement::childless(
+            "button",
+            vec![Attribute::new(
+                "class",
+                ClassList::from("btn").add_if("active", true).add_if("hidden", false),
+            )],
+            vec![],
+        );
+        let div = container();
+        button_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<button class="btn active"></button>"#);
+
+        let el = button_el.node().unwrap().dyn_ref::<Element>().unwrap().clone();
+        // Added by something else, like a transition.
+        el.class_list().add_1("fade").unwrap();
+
+        let mut button_diff = VElement::childless(
+            "button",
+            vec![Attribute::new(
+                "class",
+                AttributeValue::from(ClassList::new().add_many(&["btn", "large"]))
+                    .with_class("active", false),
+            )],
+            vec![],
+        );
+        button_diff
+            .patch(
+                Some(&mut button_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(el.class_name(), "btn fade large");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_warn_about_a_value_without_an_input_listener() {
+        let input =
+            VElement::<()>::childless("input", vec![Attribute::new("value", "Ruukh")], vec![]);
+
+        assert!(input.has_read_only_value());
+    }
+
+    #[cfg(debug_assertions)]
+    #[wasm_bindgen_test]
+    fn should_log_the_warning_when_patching_a_read_only_value() {
+        use js_sys::Reflect;
+
+        let console = Reflect::get(&js_sys::global(), &"console".into()).unwrap();
+        let warn = Reflect::get(&console, &"warn".into()).unwrap();
+        let warnings = Rc::new(RefCell::new(vec![]));
+        let record: Closure<dyn Fn(JsValue)> = {
+            let warnings = warnings.clone();
+            Closure::wrap(Box::new(move |message: JsValue| {
+                warnings.borrow_mut().push(message.as_string().unwrap());
+            }))
+        };
+        Reflect::set(&console, &"warn".into(), record.as_ref()).unwrap();
+
+        let mut input_el =
+            VElement::childless("input", vec![Attribute::new("value", "Ruukh")], vec![]);
+        let div = container();
+        let patched = input_el.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        );
+        Reflect::set(&console, &"warn".into(), &warn).unwrap();
+        patched.expect("To patch div");
+
+        let warnings = warnings.borrow();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("The <input> has a value but no @input"));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_warn_about_a_value_with_an_input_listener() {
+        let input = VElement::<()>::childless(
+            "input",
+            vec![Attribute::new("value", "Ruukh")],
+            vec![EventListener::new("input", Box::new(|_, _: Event| {}))],
+        );
+        let initial = VElement::<()>::childless(
+            "input",
+            vec![Attribute::new("default-value", "Ruukh")],
+            vec![],
+        );
+        let checkbox = VElement::<()>::childless(
+            "input",
+            vec![
+                Attribute::new("type", "checkbox"),
+                Attribute::new("value", "on"),
+            ],
+            vec![],
+        );
+
+        assert!(!input.has_read_only_value());
+        assert!(!initial.has_read_only_value());
+        assert!(!checkbox.has_read_only_value());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_populate_node_ref_while_attached() {
+        let node_ref = NodeRef::new();
+        let mut input_el =
+            VElement::childless("input", vec![], vec![]).with_node_ref(node_ref.clone());
+        let div = container();
+        input_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(node_ref.get().unwrap().tag_name(), "INPUT");
+
+        input_el.remove(div.as_ref()).expect("To remove from div");
+
+        assert!(node_ref.get().is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_stop_listening_when_removed() {
+        let clicks = Rc::new(RefCell::new(0));
+        let clicks_clone = clicks.clone();
+        let mut button = VElement::<()>::childless(
+            "button",
+            vec![],
+            vec![EventListener::new(
+                "click",
+                Box::new(move |_, _: Event| *clicks_clone.borrow_mut() += 1),
+            )],
+        );
+        let div = container();
+        button
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        let el = button.node().unwrap().clone();
+
+        button.remove(div.as_ref()).expect("To remove from div");
+        el.dispatch_event(&Event::new("click").unwrap()).unwrap();
+
+        assert_eq!(*clicks.borrow(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_clear_all_the_children_in_one_operation() {
+        use web_sys::{MutationObserver, MutationObserverInit};
+
+        let items: Vec<VNode<()>> = (0..100)
+            .map(|i| VText::text(i.to_string()).into())
+            .collect();
+        let mut list = VElement::new("ul", vec![], vec![], VList::from(items).into());
+        let div = container();
+        list.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+        let ul = list.node().unwrap().clone();
+
+        let callback = Closure::wrap(Box::new(|_, _| {}) as Box<dyn FnMut(JsValue, JsValue)>);
+        let observer = MutationObserver::new(callback.as_ref().unchecked_ref()).unwrap();
+        observer
+            .observe_with_options(ul.as_ref(), MutationObserverInit::new().child_list(true))
+            .unwrap();
+
+        let mut cleared = VElement::new("ul", vec![], vec![], VNode::None);
+        cleared
+            .patch(
+                Some(&mut list),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(observer.take_records().length(), 1);
+        assert_eq!(ul.inner_html(), "");
+        observer.disconnect();
+    }
+
+    #[wasm_bindgen_test]
+    fn should_apply_event_listener_modifiers() {
+        let outer_clicks = Rc::new(RefCell::new(0));
+        let outer_clicks_clone = outer_clicks.clone();
+        let prevented = Rc::new(RefCell::new(false));
+        let prevented_clone = prevented.clone();
+        let mut el = VElement::<()>::new(
+            "div",
+            vec![],
+            vec![EventListener::new(
+                "click",
+                Box::new(move |_, _: Event| *outer_clicks_clone.borrow_mut() += 1),
+            )],
+            VElement::childless(
+                "a",
+                vec![Attribute::new("href", "#away")],
+                vec![EventListener::new(
+                    "click",
+                    Box::new(move |_, event: Event| {
+                        *prevented_clone.borrow_mut() = event.default_prevented()
+                    }),
+                )
+                .prevent_default()
+                .stop_propagation()],
+            )
+            .into(),
+        );
+        let div = container();
+        el.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        div.query_selector("a")
+            .unwrap()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+
+        assert!(*prevented.borrow());
+        assert_eq!(*outer_clicks.borrow(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_fluently_built_element() {
+        let clicks = Rc::new(RefCell::new(0));
+        let clicks_clone = clicks.clone();
+        let mut button = VElement::<()>::from_tag("button")
+            .attr("class", "primary")
+            .attr("disabled", false)
+            .on("click", move |_, _: MouseEvent| *clicks_clone.borrow_mut() += 1)
+            .child(VText::text("Save"))
+            .child(VElement::from_tag("i"));
+        let div = container();
+        button
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<button class="primary">Save<i></i></button>"#
+        );
+
+        let el = button.node().unwrap().clone();
+        el.dispatch_event(&Event::new("click").unwrap()).unwrap();
+        assert_eq!(*clicks.borrow(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_the_keyed_children_of_a_list_appended_as_the_first_child() {
+        let list = VList::from(
+            vec![(5u32, "Five"), (2, "Two")]
+                .into_iter()
+                .map(|(key, text)| (Key::new(key), VNode::from(VText::text(text))))
+                .collect::<IndexMap<_, _, FnvBuildHasher>>(),
+        );
+        let mut ul = VElement::<()>::from_tag("ul")
+            .child(list)
+            .child(VText::text("One"))
+            .child(VText::text("Zero"));
+        let div = container();
+        ul.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "<ul>FiveTwoOneZero</ul>");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_svg_elements_in_svg_namespace() {
+        let mut svg = VElement::<()>::namespaced(
+            SVG_NAMESPACE,
+            "svg",
+            vec![],
+            vec![],
+            VList::from(vec![
+                VElement::childless("circle", vec![Attribute::new("r", "4")], vec![]).into(),
+                VElement::new(
+                    "foreignObject",
+                    vec![],
+                    vec![],
+                    VElement::childless("p", vec![], vec![]).into(),
+                )
+                .into(),
+            ])
+            .into(),
+        );
+        let div = container();
+        svg.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        let namespace_of = |selector: &str| {
+            div.query_selector(selector)
+                .unwrap()
+                .unwrap()
+                .namespace_uri()
+        };
+        assert_eq!(namespace_of("svg"), Some(SVG_NAMESPACE.to_string()));
+        assert_eq!(namespace_of("circle"), Some(SVG_NAMESPACE.to_string()));
+        assert_eq!(namespace_of("p"), Some(HTML_NAMESPACE.to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_respect_user_changes_on_default_checked_radio() {
+        fn radios() -> VNode<()> {
+            let radio = |value: &'static str, checked: bool| {
+                VElement::childless(
+                    "input",
+                    vec![
+                        Attribute::new("type", "radio"),
+                        Attribute::new("name", "size"),
+                        Attribute::new("value", value),
+                        Attribute::new("default-checked", checked),
+                    ],
+                    vec![],
+                )
+                .into()
+            };
+            VList::from(vec![radio("small", true), radio("large", false)]).into()
+        }
+
+        let div = container();
+        let mut radio_group = radios();
+        radio_group
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        let checked = || {
+            div.query_selector("input:checked")
+                .unwrap()
+                .and_then(|el| el.get_attribute("value"))
+        };
+        assert_eq!(checked(), Some("small".to_string()));
+
+        div.query_selector(r#"input[value="large"]"#)
+            .unwrap()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+        let mut rerendered = radios();
+        rerendered
+            .patch(
+                Some(&mut radio_group),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(checked(), Some("large".to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_leave_the_attribute_once_default_checked_is_removed() {
+        let div = container();
+        let mut checkbox = VElement::<()>::childless(
+            "input",
+            vec![
+                Attribute::new("type", "checkbox"),
+                Attribute::new("default-checked", true),
+            ],
+            vec![],
+        );
+        checkbox
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        assert_eq!(div.inner_html(), r#"<input type="checkbox" checked="">"#);
+
+        let mut unchecked =
+            VElement::childless("input", vec![Attribute::new("type", "checkbox")], vec![]);
+        unchecked
+            .patch(
+                Some(&mut checkbox),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<input type="checkbox" checked="">"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_updating_the_other_attributes_prefixed_with_default() {
+        let div = container();
+        let mut card =
+            VElement::<()>::childless("div", vec![Attribute::new("default-theme", "dark")], vec![]);
+        card.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+        assert_eq!(div.inner_html(), r#"<div default-theme="dark"></div>"#);
+
+        let mut light =
+            VElement::childless("div", vec![Attribute::new("default-theme", "light")], vec![]);
+        light
+            .patch(
+                Some(&mut card),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<div default-theme="light"></div>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_set_inner_html_only_when_changed() {
+        let mut article = VElement::childless("article", vec![], vec![])
+            .dangerous_inner_html("<p>Hello</p>");
+        let div = container();
+        article
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "<article><p>Hello</p></article>");
+
+        let para = div.query_selector("p").unwrap().unwrap();
+        para.set_attribute("class", "seen").unwrap();
+        let mut same = VElement::childless("article", vec![], vec![])
+            .dangerous_inner_html("<p>Hello</p>");
+        same.patch(
+            Some(&mut article),
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<article><p class="seen">Hello</p></article>"#
+        );
+
+        let mut changed = VElement::childless("article", vec![], vec![])
+            .dangerous_inner_html("<p>World</p>");
+        changed
+            .patch(
+                Some(&mut same),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "<article><p>World</p></article>");
+    }
+
+    #[cfg(feature = "async-events")]
+    #[wasm_bindgen_test(async)]
+    fn should_spawn_the_future_of_async_listener(
+    ) -> impl futures::Future<Item = (), Error = JsValue> {
+        use futures::{future, sync::oneshot, Future};
+
+        let (tx, rx) = oneshot::channel();
+        let tx = RefCell::new(Some(tx));
+        let mut button = VElement::<()>::childless(
+            "button",
+            vec![],
+            vec![EventListener::new_async(
+                "click",
+                Box::new(move |_, _: Event| {
+                    let tx = tx.borrow_mut().take();
+                    future::lazy(move || {
+                        if let Some(tx) = tx {
+                            tx.send("clicked").unwrap();
+                        }
+                        Ok(())
+                    })
+                }),
+            )],
+        );
+        let div = container();
+        button
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        button
+            .node()
+            .unwrap()
+            .dispatch_event(&Event::new("click").unwrap())
+            .unwrap();
+
+        rx.map(|msg| assert_eq!(msg, "clicked"))
+            .map_err(|_| JsValue::from_str("The listener was dropped"))
+    }
 }
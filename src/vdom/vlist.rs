@@ -3,12 +3,12 @@
 use crate::{
     component::Render,
     dom::DOMPatch,
-    vdom::{Key, VNode},
+    vdom::{hydrate::Hydrate, ssr::ToHtml, Key, VNode},
     MessageSender, Shared,
 };
 use fnv::FnvBuildHasher;
 use indexmap::IndexMap;
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt};
 use wasm_bindgen::prelude::JsValue;
 use web_sys::Node;
 
@@ -39,6 +39,57 @@ impl<RCTX> From<IndexMap<Key, VNode<RCTX>, FnvBuildHasher>> for VList<RCTX> {
     }
 }
 
+impl<RCTX> VList<RCTX> {
+    /// Appends a vnode keyed by its index.
+    pub(crate) fn push(&mut self, vnode: VNode<RCTX>) {
+        let key = Key::new(self.0.len() as u32);
+        self.0.insert(key, vnode);
+    }
+}
+
+impl<RCTX> VList<RCTX> {
+    /// Whether none of the vnodes in the list render a node.
+    pub(crate) fn renders_nothing(&self) -> bool {
+        self.0.values().all(VNode::renders_nothing)
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VList<RCTX> {
+    /// Collects the nodes in the list along with their descendants in
+    /// document order.
+    pub(crate) fn collect_nodes<'a>(&'a self, nodes: &mut Vec<&'a VNode<RCTX>>) {
+        for vnode in self.0.values() {
+            vnode.collect_nodes(nodes);
+        }
+    }
+
+    /// Writes the nodes in the list deterministically.
+    pub(crate) fn write_test_html(&self, html: &mut String) {
+        for vnode in self.0.values() {
+            vnode.write_test_html(html);
+        }
+    }
+
+    /// Finds the first element in the list, in document order, which listens
+    /// to the event type.
+    pub(crate) fn find_listening(
+        &self,
+        type_: &str,
+    ) -> Option<&crate::vdom::velement::VElement<RCTX>> {
+        self.0
+            .values()
+            .filter_map(|vnode| vnode.find_listening(type_))
+            .next()
+    }
+}
+
+impl<RCTX> fmt::Debug for VList<RCTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.values()).finish()
+    }
+}
+
 impl<RCTX: Render> DOMPatch for VList<RCTX> {
     type RenderContext = RCTX;
     type Node = Node;
@@ -53,7 +104,8 @@ impl<RCTX: Render> DOMPatch for VList<RCTX> {
         let mut next = next;
         for (_, vnode) in self.0.iter_mut().rev() {
             vnode.render_walk(parent, next, render_ctx.clone(), rx_sender.clone())?;
-            next = vnode.node();
+            // A component which rendered nothing has no node to insert before.
+            next = vnode.node().or(next);
         }
         Ok(())
     }
@@ -71,9 +123,16 @@ impl<RCTX: Render> DOMPatch for VList<RCTX> {
             // Collect the keys of alive nodes from old vlist.
             let mut alive_keys = HashSet::with_hasher(FnvBuildHasher::default());
 
+            let old_indices: Vec<_> = self
+                .0
+                .keys()
+                .map(|key| old.0.get_full(key).map(|(old_index, _, _)| old_index))
+                .collect();
+            let in_order = in_order(&old_indices);
+
             for (index, (key, vnode)) in self.0.iter_mut().enumerate().rev() {
                 // Patch the old vnode if found.
-                if let Some((old_index, _, old)) = old.0.get_full_mut(key) {
+                if let Some(old) = old.0.get_mut(key) {
                     vnode.patch(
                         Some(old),
                         parent,
@@ -83,7 +142,7 @@ impl<RCTX: Render> DOMPatch for VList<RCTX> {
                     )?;
 
                     // If the order changed, update it in the DOM.
-                    if index != old_index {
+                    if !in_order[index] {
                         vnode.reorder(parent, next)?;
                     }
 
@@ -129,6 +188,70 @@ impl<RCTX: Render> DOMPatch for VList<RCTX> {
     }
 }
 
+/// Finds the nodes which are already in order, i.e. the longest subsequence
+/// of their old indices which is increasing. Only the rest of the nodes need
+/// to be moved in the DOM, which is the least number of moves possible.
+fn in_order(old_indices: &[Option<usize>]) -> Vec<bool> {
+    // The old index and the index of the last node of the increasing
+    // subsequences of each length.
+    let mut tails: Vec<(usize, usize)> = vec![];
+    // The index of the node preceding each node in its subsequence.
+    let mut preceding = vec![None; old_indices.len()];
+
+    for (index, old_index) in old_indices.iter().enumerate() {
+        let old_index = match old_index {
+            Some(old_index) => *old_index,
+            None => continue,
+        };
+        let len = match tails.binary_search_by_key(&old_index, |&(tail, _)| tail) {
+            Ok(len) | Err(len) => len,
+        };
+        if len > 0 {
+            preceding[index] = Some(tails[len - 1].1);
+        }
+        if len == tails.len() {
+            tails.push((old_index, index));
+        } else {
+            tails[len] = (old_index, index);
+        }
+    }
+
+    let mut in_order = vec![false; old_indices.len()];
+    let mut current = tails.last().map(|&(_, index)| index);
+    while let Some(index) = current {
+        in_order[index] = true;
+        current = preceding[index];
+    }
+    in_order
+}
+
+impl<RCTX: Render> Hydrate for VList<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        mut node: Option<Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        for vnode in self.0.values_mut() {
+            node = vnode.hydrate_walk(parent, node, render_ctx.clone(), rx_sender.clone())?;
+        }
+        Ok(node)
+    }
+}
+
+impl<RCTX: Render> ToHtml for VList<RCTX> {
+    type RenderContext = RCTX;
+
+    fn to_html(&mut self, html: &mut String, render_ctx: Shared<Self::RenderContext>) {
+        for vnode in self.0.values_mut() {
+            vnode.to_html(html, render_ctx.clone());
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
@@ -192,4 +315,115 @@ pub mod test {
 
         assert_eq!(div.inner_html(), "<div></div>Hello World!How are you?");
     }
+
+    fn keyed(keys: &[&str]) -> VList<()> {
+        VList::from(
+            keys.iter()
+                .map(|key| (Key::new(key.to_string()), VNode::from(VText::text(*key))))
+                .collect::<IndexMap<_, _, FnvBuildHasher>>(),
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_reordered_keyed_list() {
+        let mut list = keyed(&["a", "b", "c", "d"]);
+        let div = container();
+        list.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+        let node_c = list.0.get(&Key::new("c".to_string())).unwrap().node().cloned();
+
+        let mut new_list = keyed(&["e", "c", "a", "b"]);
+        new_list
+            .patch(
+                Some(&mut list),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "ecab");
+        let moved_c = new_list.0.get(&Key::new("c".to_string())).unwrap().node();
+        assert!(moved_c.unwrap().is_same_node(node_c.as_ref()));
+    }
+
+    fn reorder_keyed<K: Into<Key> + Clone>(old: Vec<(K, &str)>, new: Vec<(K, &str)>) -> String {
+        let keyed = |rows: Vec<(K, &str)>| -> VList<()> {
+            VList::from(
+                rows.into_iter()
+                    .map(|(key, text)| (key.into(), VNode::from(VText::text(text))))
+                    .collect::<IndexMap<_, _, FnvBuildHasher>>(),
+            )
+        };
+        let first: Key = new[0].0.clone().into();
+        let mut list = keyed(old);
+        let div = container();
+        list.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+        let node = list.0.get(&first).unwrap().node().cloned();
+
+        let mut new_list = keyed(new);
+        new_list
+            .patch(
+                Some(&mut list),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let moved = new_list.0.get(&first).unwrap().node();
+        assert!(moved.unwrap().is_same_node(node.as_ref()));
+        div.inner_html()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_reordered_list_of_any_keys() {
+        assert_eq!(
+            reorder_keyed(vec![(1, "a"), (2, "b"), (3, "c")], vec![(3, "c"), (1, "a")]),
+            "ca"
+        );
+        assert_eq!(
+            reorder_keyed(
+                vec![("x", "a"), ("y", "b"), ("z", "c")],
+                vec![("z", "c"), ("y", "b"), ("x", "a")]
+            ),
+            "cba"
+        );
+        assert_eq!(
+            reorder_keyed(
+                vec![((1, "x"), "a"), ((1, "y"), "b"), ((2, "x"), "c")],
+                vec![((2, "x"), "c"), ((1, "x"), "a"), ((1, "y"), "b")]
+            ),
+            "cab"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_find_the_nodes_which_are_in_order() {
+        assert_eq!(
+            in_order(&[None, Some(2), Some(0), Some(1)]),
+            vec![false, false, true, true]
+        );
+        assert_eq!(
+            in_order(&[Some(3), Some(0), Some(1), Some(2)]),
+            vec![false, true, true, true]
+        );
+        assert_eq!(in_order(&[Some(1), Some(0)]), vec![false, true]);
+        assert_eq!(in_order(&[]), Vec::<bool>::new());
+    }
 }
@@ -0,0 +1,204 @@
+//! Portal representation in a VDOM.
+
+use crate::{
+    component::Render,
+    dom::{self, DOMPatch},
+    vdom::VNode,
+    MessageSender, Shared,
+};
+use std::fmt;
+use wasm_bindgen::prelude::JsValue;
+use web_sys::{Element, Node};
+
+/// The representation of a portal in a Virtual DOM.
+///
+/// A portal renders its child into a target element instead of its parent,
+/// so that things like modals and tooltips escape the clipping of their
+/// ancestors. Nothing is rendered in place of the portal itself.
+pub struct VPortal<RCTX> {
+    /// The element to render the child into
+    target: Element,
+    /// The child of the portal
+    child: Box<VNode<RCTX>>,
+}
+
+impl<RCTX> VPortal<RCTX> {
+    /// Create a new VPortal which renders the child into the target.
+    pub fn new(target: impl Into<Element>, child: VNode<RCTX>) -> VPortal<RCTX> {
+        VPortal {
+            target: target.into(),
+            child: Box::new(child),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VPortal<RCTX> {
+    /// Collects the nodes in the portal in document order.
+    pub(crate) fn collect_nodes<'a>(&'a self, nodes: &mut Vec<&'a VNode<RCTX>>) {
+        self.child.collect_nodes(nodes);
+    }
+
+    /// Finds the first element in the portal, in document order, which
+    /// listens to the event type.
+    pub(crate) fn find_listening(
+        &self,
+        type_: &str,
+    ) -> Option<&crate::vdom::velement::VElement<RCTX>> {
+        self.child.find_listening(type_)
+    }
+}
+
+impl<RCTX> From<VPortal<RCTX>> for VNode<RCTX> {
+    fn from(portal: VPortal<RCTX>) -> VNode<RCTX> {
+        VNode::Portal(portal)
+    }
+}
+
+impl<RCTX> fmt::Debug for VPortal<RCTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Portal").field(&self.child).finish()
+    }
+}
+
+impl<RCTX: Render> DOMPatch for VPortal<RCTX> {
+    type RenderContext = RCTX;
+    type Node = Node;
+
+    fn render_walk(
+        &mut self,
+        _: &Self::Node,
+        _: Option<&Self::Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        self.child
+            .render_walk(self.target.as_ref(), None, render_ctx, rx_sender)
+    }
+
+    fn patch(
+        &mut self,
+        old: Option<&mut Self>,
+        parent: &Self::Node,
+        _: Option<&Self::Node>,
+        render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        if let Some(old) = old {
+            let old_target: &Node = old.target.as_ref();
+            if old_target.is_same_node(Some(self.target.as_ref())) {
+                return self.child.patch(
+                    Some(&mut old.child),
+                    self.target.as_ref(),
+                    None,
+                    render_ctx,
+                    rx_sender,
+                );
+            }
+            old.remove(parent)?;
+        }
+        self.child
+            .patch(None, self.target.as_ref(), None, render_ctx, rx_sender)
+    }
+
+    fn reorder(&self, _: &Self::Node, _: Option<&Self::Node>) -> Result<(), JsValue> {
+        // The child lives in the target, so there is nothing to reorder.
+        Ok(())
+    }
+
+    fn remove(&self, _: &Self::Node) -> Result<(), JsValue> {
+        // The child lives in the target, which is not detached along with the
+        // parent of the portal.
+        dom::with_detached(false, || self.child.remove(self.target.as_ref()))
+    }
+
+    fn node(&self) -> Option<&Node> {
+        // Nothing is rendered in place of the portal.
+        None
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        vdom::{test::container, velement::VElement, vtext::VText},
+    };
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_render_the_child_into_the_target() {
+        let div = container();
+        let target = container();
+        let mut portal = VNode::from(VPortal::<()>::new(
+            target.clone(),
+            VNode::from(VElement::new(
+                "span",
+                vec![],
+                vec![],
+                VNode::from(VText::text("Tooltip")),
+            )),
+        ));
+        portal
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(div.inner_html(), "");
+        assert_eq!(target.inner_html(), "<span>Tooltip</span>");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_the_child_in_the_target() {
+        let div = container();
+        let target = container();
+        let mut portal = VPortal::<()>::new(target.clone(), VNode::from(VText::text("Hello")));
+        portal
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        let mut patched = VPortal::<()>::new(target.clone(), VNode::from(VText::text("World")));
+        patched
+            .patch(
+                Some(&mut portal),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert_eq!(target.inner_html(), "World");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_clean_up_the_target_on_remove() {
+        let div = container();
+        let target = container();
+        let mut portal = VPortal::<()>::new(target.clone(), VNode::from(VText::text("Modal")));
+        portal
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        portal.remove(div.as_ref()).expect("To remove from div");
+
+        assert_eq!(target.inner_html(), "");
+    }
+}
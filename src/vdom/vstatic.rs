@@ -0,0 +1,270 @@
+//! Static subtrees which are constructed once and reused across renders.
+
+use crate::{
+    dom::{self, DOMPatch},
+    vdom::{
+        hydrate::{self, Hydrate},
+        ssr::ToHtml,
+        VNode,
+    },
+    MessageSender, Shared,
+};
+use std::{cell::RefCell, fmt, marker::PhantomData, mem, rc::Rc};
+use wasm_bindgen::prelude::JsValue;
+use web_sys::{window, Element, Node};
+
+/// The markup of a static subtree, shared by all of its renders.
+///
+/// It is rendered once into a detached template, which is then cloned for
+/// every place it is rendered in. Create it with the `html_static!` macro
+/// instead of by hand.
+pub struct StaticMarkup {
+    /// The markup of the subtree
+    vnode: RefCell<VNode<()>>,
+    /// The detached element holding the rendered markup
+    template: RefCell<Option<Element>>,
+}
+
+impl StaticMarkup {
+    /// Create the static markup.
+    pub fn new(vnode: VNode<()>) -> StaticMarkup {
+        StaticMarkup {
+            vnode: RefCell::new(vnode),
+            template: RefCell::new(None),
+        }
+    }
+
+    /// Gets the template, rendering it on the first use.
+    fn template(&self) -> Result<Element, JsValue> {
+        if let Some(ref template) = *self.template.borrow() {
+            return Ok(template.clone());
+        }
+        let template = window().unwrap().document().unwrap().create_element("div")?;
+        let mut vnode = self.vnode.borrow_mut();
+        let render_ctx = Rc::new(RefCell::new(()));
+        vnode.patch(
+            None,
+            template.as_ref(),
+            None,
+            render_ctx.clone(),
+            MessageSender::inert(),
+        )?;
+        vnode.render_walk(template.as_ref(), None, render_ctx, MessageSender::inert())?;
+        *self.template.borrow_mut() = Some(template.clone());
+        Ok(template)
+    }
+}
+
+/// The representation of a static subtree in a VDOM.
+///
+/// The vstatics of the same `html_static!` share their markup, so that
+/// patching one over the other does nothing at all.
+pub struct VStatic<RCTX> {
+    /// The shared markup
+    markup: Rc<StaticMarkup>,
+    /// The nodes cloned from the template
+    nodes: Vec<Node>,
+    /// Render context
+    _phantom: PhantomData<RCTX>,
+}
+
+impl<RCTX> VStatic<RCTX> {
+    /// Create a VStatic of the shared markup.
+    pub fn new(markup: &Rc<StaticMarkup>) -> VStatic<RCTX> {
+        VStatic {
+            markup: markup.clone(),
+            nodes: vec![],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VStatic<RCTX> {
+    /// Writes the shared markup deterministically.
+    pub(crate) fn write_test_html(&self, html: &mut String) {
+        self.markup.vnode.borrow().write_test_html(html);
+    }
+}
+
+impl<RCTX> From<VStatic<RCTX>> for VNode<RCTX> {
+    fn from(vstatic: VStatic<RCTX>) -> VNode<RCTX> {
+        VNode::Static(vstatic)
+    }
+}
+
+impl<RCTX> fmt::Debug for VStatic<RCTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Static")
+            .field(&*self.markup.vnode.borrow())
+            .finish()
+    }
+}
+
+impl<RCTX> DOMPatch for VStatic<RCTX> {
+    type RenderContext = RCTX;
+    type Node = Node;
+
+    fn render_walk(
+        &mut self,
+        _: &Self::Node,
+        _: Option<&Self::Node>,
+        _: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<(), JsValue> {
+        // There are no components to walk on.
+        Ok(())
+    }
+
+    fn patch(
+        &mut self,
+        old: Option<&mut Self>,
+        parent: &Self::Node,
+        next: Option<&Self::Node>,
+        _: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<(), JsValue> {
+        if let Some(old) = old {
+            if Rc::ptr_eq(&self.markup, &old.markup) {
+                // It is the same subtree, so there is nothing to diff.
+                self.nodes = mem::replace(&mut old.nodes, vec![]);
+                return Ok(());
+            }
+            old.remove(parent)?;
+        }
+        let template = self.markup.template()?;
+        let mut child = template.first_child();
+        while let Some(node) = child {
+            let cloned = node.clone_node_with_deep(true)?;
+            parent.insert_before(&cloned, next)?;
+            self.nodes.push(cloned);
+            child = node.next_sibling();
+        }
+        Ok(())
+    }
+
+    fn reorder(&self, parent: &Self::Node, next: Option<&Self::Node>) -> Result<(), JsValue> {
+        for node in self.nodes.iter() {
+            parent.insert_before(node, next)?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, parent: &Self::Node) -> Result<(), JsValue> {
+        for node in self.nodes.iter() {
+            dom::remove_child(parent, node)?;
+        }
+        Ok(())
+    }
+
+    fn node(&self) -> Option<&Node> {
+        self.nodes.first()
+    }
+}
+
+impl<RCTX> Hydrate for VStatic<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        mut node: Option<Node>,
+        _: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        let template = self.markup.template()?;
+        let mut child = template.first_child();
+        while let Some(expected) = child {
+            let existing = node
+                .as_ref()
+                .filter(|node| node.node_name() == expected.node_name())
+                .cloned();
+            match existing {
+                Some(existing) => {
+                    node = existing.next_sibling();
+                    self.nodes.push(existing);
+                }
+                None => {
+                    hydrate::warn_mismatch(&expected.node_name(), node.as_ref());
+                    let cloned = expected.clone_node_with_deep(true)?;
+                    parent.insert_before(&cloned, node.as_ref())?;
+                    self.nodes.push(cloned);
+                    node = hydrate::replace(parent, node)?;
+                }
+            }
+            child = expected.next_sibling();
+        }
+        Ok(node)
+    }
+}
+
+impl<RCTX> ToHtml for VStatic<RCTX> {
+    type RenderContext = RCTX;
+
+    fn to_html(&mut self, html: &mut String, _: Shared<Self::RenderContext>) {
+        self.markup
+            .vnode
+            .borrow_mut()
+            .to_html(html, Rc::new(RefCell::new(())));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        vdom::{test::container, velement::VElement, vtext::VText},
+    };
+    use wasm_bindgen_test::*;
+
+    fn footer() -> Rc<StaticMarkup> {
+        Rc::new(StaticMarkup::new(
+            VElement::new("footer", vec![], vec![], VText::text("Ruukh").into()).into(),
+        ))
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_touch_the_dom_for_the_same_markup() {
+        let markup = footer();
+        let div = container();
+        let mut vstatic = VStatic::<()>::new(&markup);
+        vstatic
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+        assert_eq!(div.inner_html(), "<footer>Ruukh</footer>");
+
+        let rendered = vstatic.node().unwrap().clone();
+        let mut same = VStatic::<()>::new(&markup);
+        same.patch(
+            Some(&mut vstatic),
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        assert!(same.node().unwrap().is_same_node(Some(&rendered)));
+
+        let mut other = VStatic::<()>::new(&footer());
+        other
+            .patch(
+                Some(&mut same),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            )
+            .expect("To patch div");
+
+        assert!(!other.node().unwrap().is_same_node(Some(&rendered)));
+        assert_eq!(div.inner_html(), "<footer>Ruukh</footer>");
+    }
+}
@@ -1,9 +1,19 @@
 //! Representation of text/comment in virtual dom tree.
 
-use crate::{component::Render, dom::DOMPatch, vdom::VNode, MessageSender, Shared};
-use std::marker::PhantomData;
-use wasm_bindgen::prelude::JsValue;
-use web_sys::{window, Node};
+use crate::{
+    component::Render,
+    dom::{self, DOMPatch},
+    expect::ExpectMsg,
+    vdom::{
+        hydrate::{self, Hydrate},
+        ssr::{self, ToHtml},
+        VNode,
+    },
+    MessageSender, Shared,
+};
+use std::{fmt, marker::PhantomData};
+use wasm_bindgen::{prelude::JsValue, JsCast};
+use web_sys::{window, Node, Text};
 
 /// The representation of text/comment in virtual dom tree.
 pub struct VText<RCTX> {
@@ -39,6 +49,33 @@ impl<RCTX> VText<RCTX> {
     }
 }
 
+#[cfg(any(test, feature = "test-utils"))]
+impl<RCTX> VText<RCTX> {
+    /// Writes the text with its runs of whitespace collapsed into a single
+    /// space, or the comment as is.
+    pub(crate) fn write_test_html(&self, html: &mut String) {
+        if self.is_comment {
+            html.push_str("<!--");
+            html.push_str(&self.content);
+            html.push_str("-->");
+            return;
+        }
+        let mut in_whitespace = false;
+        for ch in self.content.chars() {
+            if ch.is_whitespace() {
+                if !in_whitespace {
+                    html.push(' ');
+                }
+                in_whitespace = true;
+            } else {
+                in_whitespace = false;
+                let mut buf = [0; 4];
+                ssr::escape(ch.encode_utf8(&mut buf), html);
+            }
+        }
+    }
+}
+
 impl<RCTX> VText<RCTX> {
     fn patch_new(&mut self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
         let node: Node = if self.is_comment {
@@ -68,6 +105,13 @@ impl<RCTX> From<VText<RCTX>> for VNode<RCTX> {
     }
 }
 
+impl<RCTX> fmt::Debug for VText<RCTX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.is_comment { "Comment" } else { "Text" };
+        f.debug_tuple(name).field(&self.content).finish()
+    }
+}
+
 impl<RCTX: Render> DOMPatch for VText<RCTX> {
     type RenderContext = RCTX;
     type Node = Node;
@@ -95,7 +139,7 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
                 let old_node = old
                     .node
                     .as_ref()
-                    .expect("The old node is expected to be attached to the DOM");
+                    .expect_msg("The old node is expected to be attached to the DOM");
                 if self.content != old.content {
                     old_node.set_text_content(Some(&self.content));
                 }
@@ -117,12 +161,12 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
     }
 
     fn remove(&self, parent: &Node) -> Result<(), JsValue> {
-        parent.remove_child(
+        dom::remove_child(
+            parent,
             self.node
                 .as_ref()
-                .expect("The old node is expected to be attached to the DOM"),
-        )?;
-        Ok(())
+                .expect_msg("The old node is expected to be attached to the DOM"),
+        )
     }
 
     fn node(&self) -> Option<&Node> {
@@ -130,6 +174,63 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
     }
 }
 
+impl<RCTX: Render> Hydrate for VText<RCTX> {
+    type RenderContext = RCTX;
+
+    fn hydrate_walk(
+        &mut self,
+        parent: &Node,
+        node: Option<Node>,
+        _: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<Option<Node>, JsValue> {
+        if !self.is_comment && self.content.is_empty() {
+            // An empty text leaves no node behind in the server rendered HTML.
+            self.patch_new(parent, node.as_ref())?;
+            return Ok(node);
+        }
+
+        let node_type = if self.is_comment {
+            Node::COMMENT_NODE
+        } else {
+            Node::TEXT_NODE
+        };
+        if let Some(existing) = node.as_ref().filter(|n| n.node_type() == node_type) {
+            let existing_content = existing.text_content().unwrap_or_default();
+            if existing_content == self.content {
+                self.node = Some(existing.clone());
+                return Ok(existing.next_sibling());
+            }
+            if !self.is_comment && existing_content.starts_with(&self.content) {
+                // The adjacent texts are parsed as a single text node, so split
+                // off the text which follows.
+                let len = self.content.encode_utf16().count() as u32;
+                let rest = existing.unchecked_ref::<Text>().split_text(len)?;
+                self.node = Some(existing.clone());
+                return Ok(Some(rest.into()));
+            }
+        }
+        let expected = if self.is_comment { "a comment" } else { "a text" };
+        hydrate::warn_mismatch(expected, node.as_ref());
+        self.patch_new(parent, node.as_ref())?;
+        hydrate::replace(parent, node)
+    }
+}
+
+impl<RCTX> ToHtml for VText<RCTX> {
+    type RenderContext = RCTX;
+
+    fn to_html(&mut self, html: &mut String, _: Shared<Self::RenderContext>) {
+        if self.is_comment {
+            html.push_str("<!--");
+            html.push_str(&self.content);
+            html.push_str("-->");
+        } else {
+            ssr::escape(&self.content, html);
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
@@ -0,0 +1,231 @@
+//! A list which renders only the items visible in its scroll container.
+//!
+//! Rendering thousands of rows at once is slow, even though only a handful of
+//! them are visible at a time. The [VirtualList](struct.VirtualList.html)
+//! renders only the items within its visible window, plus the overscanned
+//! ones on either side, while faking the height of the whole list so that the scrollbar
+//! behaves as if all of them were rendered.
+//!
+//! # Example
+//! ```ignore
+//! let rows = VComponent::new::<VirtualList>(
+//!     VirtualListProps {
+//!         item_count: 10_000,
+//!         item_height: 24.0,
+//!         height: 480.0,
+//!         overscan: 5,
+//!         render_item: Box::new(|index| html! {
+//!             "Row "{ index }
+//!         }),
+//!     },
+//!     (),
+//! );
+//! ```
+
+use crate::{
+    component::{Component, Lifecycle, Render, Status},
+    vdom::{
+        velement::{Attribute, EventListener, VElement},
+        vlist::VList,
+        Key, VNode,
+    },
+    web_api::{EventExt, ScrollEvent},
+    Markup, Shared,
+};
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+/// The props of a [VirtualList](struct.VirtualList.html).
+pub struct VirtualListProps {
+    /// The total number of items in the list.
+    pub item_count: usize,
+    /// The height of each item in pixels. All the items are of the same height.
+    pub item_height: f64,
+    /// The height of the scroll container in pixels.
+    pub height: f64,
+    /// The number of items rendered beyond either edge of the visible window,
+    /// so that fast scrolls do not show blank space.
+    pub overscan: usize,
+    /// Renders the item at the given index.
+    pub render_item: Box<dyn Fn(usize) -> Markup<VirtualList>>,
+}
+
+/// The state of a [VirtualList](struct.VirtualList.html).
+#[derive(Default)]
+pub struct VirtualListState {
+    scroll_top: f64,
+}
+
+/// A list which renders only the items within its visible window.
+pub struct VirtualList {
+    props: VirtualListProps,
+    scroll_top: f64,
+    status: Shared<Status<VirtualListState>>,
+}
+
+impl VirtualList {
+    /// The range of the items to be rendered for the scroll position.
+    fn visible_range(&self) -> Range<usize> {
+        let VirtualListProps {
+            item_count,
+            item_height,
+            height,
+            overscan,
+            ..
+        } = self.props;
+        if item_count == 0 || item_height <= 0.0 {
+            return 0..0;
+        }
+        let first = (self.scroll_top / item_height).floor() as usize;
+        let visible = (height / item_height).ceil() as usize;
+        let start = first.saturating_sub(overscan).min(item_count);
+        let end = (first + visible + overscan).min(item_count);
+        start..end
+    }
+
+    fn on_scroll(&self, event: ScrollEvent) {
+        let scroll_top = match event.target_scroll_top() {
+            Some(scroll_top) => f64::from(scroll_top),
+            None => return,
+        };
+        if scroll_top != self.scroll_top {
+            let mut status = self.status.borrow_mut();
+            status.state_as_mut().scroll_top = scroll_top;
+            status.set_state_dirty(true);
+            status.do_react();
+        }
+    }
+}
+
+impl Component for VirtualList {
+    type Props = VirtualListProps;
+    type Events = ();
+    type State = VirtualListState;
+
+    fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+        VirtualList {
+            props,
+            scroll_top: status.state_as_ref().scroll_top,
+            status: Rc::new(RefCell::new(status)),
+        }
+    }
+
+    fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+        // The render closure cannot be compared, so always rerender.
+        self.status.borrow_mut().set_props_dirty(true);
+        Some(std::mem::replace(&mut self.props, props))
+    }
+
+    fn refresh_state(&mut self) -> bool {
+        let scroll_top = self.status.borrow().state_as_ref().scroll_top;
+        let changed = scroll_top != self.scroll_top;
+        self.scroll_top = scroll_top;
+        changed
+    }
+
+    fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+        Some(&self.status)
+    }
+}
+
+impl Lifecycle for VirtualList {}
+
+impl Render for VirtualList {
+    fn render(&self) -> Markup<Self> {
+        let item_height = self.props.item_height;
+        let items: VList<Self> = self
+            .visible_range()
+            .map(|index| {
+                let style = format!(
+                    "position: absolute; left: 0; right: 0; top: {}px; height: {}px;",
+                    index as f64 * item_height,
+                    item_height
+                );
+                let item = VElement::new(
+                    "div",
+                    vec![Attribute::new("style", style)],
+                    vec![],
+                    (self.props.render_item)(index),
+                );
+                (Key::new(index as u64), VNode::from(item))
+            })
+            .collect::<indexmap::IndexMap<_, _, fnv::FnvBuildHasher>>()
+            .into();
+
+        let content = VElement::new(
+            "div",
+            vec![Attribute::new(
+                "style",
+                format!(
+                    "position: relative; height: {}px;",
+                    self.props.item_count as f64 * item_height
+                ),
+            )],
+            vec![],
+            items.into(),
+        );
+        VElement::new(
+            "div",
+            vec![Attribute::new(
+                "style",
+                format!("overflow-y: auto; height: {}px;", self.props.height),
+            )],
+            vec![EventListener::new(
+                "scroll",
+                Box::new(|this: &VirtualList, event| this.on_scroll(event)),
+            )],
+            content.into(),
+        )
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        dom::DOMPatch,
+        vdom::{test::container, vcomponent::VComponent, vtext::VText},
+    };
+    use wasm_bindgen_test::*;
+
+    fn rows(item_count: usize) -> VNode<()> {
+        VComponent::new::<VirtualList>(
+            VirtualListProps {
+                item_count,
+                item_height: 20.0,
+                height: 100.0,
+                overscan: 2,
+                render_item: Box::new(|index| VText::text(format!("Row {}", index)).into()),
+            },
+            (),
+        )
+        .into()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_render_only_the_visible_items() {
+        let mut list = rows(10_000);
+        let div = container();
+        list.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+        list.render_walk(div.as_ref(), None, root_render_ctx(), crate::message_sender())
+            .expect("To render div");
+
+        let rendered = div
+            .first_element_child()
+            .and_then(|scroller| scroller.first_element_child())
+            .unwrap()
+            .child_element_count();
+        // 5 visible items along with 2 overscanned ones below.
+        assert_eq!(rendered, 7);
+        assert!(div.inner_html().contains("Row 6"));
+        assert!(!div.inner_html().contains("Row 7"));
+    }
+}
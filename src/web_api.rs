@@ -0,0 +1,743 @@
+//! Typed DOM events received by the event listeners.
+//!
+//! The html! macro infers the type of the event from the name of the event
+//! listener, so that `@click={..}` gets a `MouseEvent` and `@keydown={..}` a
+//! `KeyboardEvent`. The events without a specific type get a plain `Event`.
+//!
+//! The events on the `window`, which no element of a component can listen
+//! to, are listened with a [GlobalListener](struct.GlobalListener.html).
+//!
+//! The timers of a component are set with an [Interval](struct.Interval.html)
+//! or a [Timeout](struct.Timeout.html), which are cleared when dropped. The
+//! animations driven by the state are run with [animate](fn.animate.html).
+//! Likewise, the [websocket](websocket/index.html) connections (behind the
+//! `websocket` feature) are closed when dropped.
+//!
+//! The keyboard shortcuts of the whole page are listened with
+//! [use_keyboard_shortcut](keyboard/fn.use_keyboard_shortcut.html), which
+//! match the keys named in [Keys](keyboard/struct.Keys.html).
+//!
+//! The navigations without reloading the page are made with the
+//! [History](history/struct.History.html).
+//!
+//! Whether an element is visible in the viewport is observed with
+//! [use_intersection_observer](intersection/fn.use_intersection_observer.html),
+//! and its size with [use_resize_observer](resize/fn.use_resize_observer.html).
+//! The media queries are matched in `render` with
+//! [use_media_query](media/fn.use_media_query.html), and the visibility of
+//! the page with [use_page_visibility](visibility/fn.use_page_visibility.html).
+//! The location of the device is looked up with
+//! [use_geolocation](geolocation/fn.use_geolocation.html), and its network
+//! connection with [use_network_info](network/fn.use_network_info.html).
+//! The text is spoken out loud with
+//! [use_speech_synthesis](speech/fn.use_speech_synthesis.html).
+//!
+//! The text of the system clipboard is read and written with the
+//! [Clipboard](clipboard/struct.Clipboard.html) (behind the `clipboard`
+//! feature).
+//! An element, or the whole page, is shown in fullscreen with
+//! [use_fullscreen](fullscreen/fn.use_fullscreen.html) (behind the
+//! `fullscreen` feature).
+//! The device is vibrated with [vibrate](fn.vibrate.html) (behind the
+//! `vibration` feature).
+//!
+//! The random values, e.g. to key the items created on the client, are got
+//! from the browser with [random_uuid](crypto/fn.random_uuid.html) and the
+//! like.
+//!
+//! The other tabs & windows of the same origin are messaged over a
+//! [BroadcastChannel](broadcast/struct.BroadcastChannel.html).
+//!
+//! The state which survives the reloads is kept in the `localStorage` with a
+//! [StoredState](storage/struct.StoredState.html) (behind the `serialize`
+//! feature).
+//!
+//! # Example
+//! ```ignore
+//! impl SearchBox {
+//!     fn on_keydown(&self, event: KeyboardEvent) {
+//!         if event.key() == "Enter" && event.ctrl_key() {
+//!             self.search(event.target_value());
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Element, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+pub use web_sys::{
+    DragEvent, Event, FocusEvent, InputEvent, KeyboardEvent, MouseEvent, PointerEvent, Touch,
+    TouchEvent, TouchList, WheelEvent,
+};
+
+pub mod broadcast;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+pub mod crypto;
+#[cfg(feature = "fullscreen")]
+pub mod fullscreen;
+pub mod geolocation;
+pub mod history;
+pub mod intersection;
+pub mod keyboard;
+pub mod media;
+pub mod network;
+pub mod resize;
+pub mod speech;
+#[cfg(feature = "serialize")]
+pub mod storage;
+pub mod visibility;
+#[cfg(feature = "vibration")]
+pub mod vibration;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+#[cfg(feature = "vibration")]
+pub use self::vibration::{use_vibration_support, vibrate, VibrationPattern};
+
+/// The event of a form submission.
+///
+/// The browsers dispatch a plain `Event` on submit, so it is the same as the
+/// `Event`. Remember to prevent its default with `@submit.prevent={..}`.
+pub type SubmitEvent = Event;
+
+/// The event of a scroll.
+///
+/// The browsers dispatch a plain `Event` on scroll, so it is the same as the
+/// `Event`. Read the scroll position with `EventExt::target_scroll_top` &
+/// `EventExt::target_scroll_left`.
+pub type ScrollEvent = Event;
+
+/// The kind of the device behind a `PointerEvent`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerType {
+    /// A mouse, or a trackpad
+    Mouse,
+    /// A finger on a touchscreen
+    Touch,
+    /// A stylus
+    Pen,
+}
+
+impl PointerType {
+    /// Gets the kind of the device which dispatched the event, if the browser
+    /// can tell.
+    ///
+    /// The rest of the details of the pointer are read off the event itself,
+    /// e.g. `pointer_id`, `is_primary`, `pressure`, `client_x` & `client_y`.
+    pub fn of(event: &PointerEvent) -> Option<PointerType> {
+        match event.pointer_type().as_str() {
+            "mouse" => Some(PointerType::Mouse),
+            "touch" => Some(PointerType::Touch),
+            "pen" => Some(PointerType::Pen),
+            _ => None,
+        }
+    }
+}
+
+/// Collects the touches of a `TouchList`, e.g. the `touches`, the
+/// `changed_touches` or the `target_touches` of a `TouchEvent`.
+///
+/// # Example
+/// ```ignore
+/// fn on_touchmove(&self, event: TouchEvent) {
+///     // Pinched with two fingers.
+///     if let [first, second] = event.touches().to_vec().as_slice() {
+///         let dx = first.client_x() - second.client_x();
+///         let dy = first.client_y() - second.client_y();
+///         self.zoom(f64::from(dx).hypot(f64::from(dy)));
+///     }
+/// }
+/// ```
+pub trait TouchListExt {
+    /// The touches in the list, in their order.
+    fn to_vec(&self) -> Vec<Touch>;
+}
+
+impl TouchListExt for TouchList {
+    fn to_vec(&self) -> Vec<Touch> {
+        (0..self.length()).filter_map(|index| self.get(index)).collect()
+    }
+}
+
+/// Ergonomic accessors common to all of the events.
+pub trait EventExt {
+    /// The value of the input, textarea or select element the event is
+    /// targeted at, if any.
+    fn target_value(&self) -> Option<String>;
+
+    /// The number of pixels the element the event is targeted at is scrolled
+    /// vertically, if it is an element.
+    fn target_scroll_top(&self) -> Option<i32>;
+
+    /// The number of pixels the element the event is targeted at is scrolled
+    /// horizontally, if it is an element.
+    fn target_scroll_left(&self) -> Option<i32>;
+}
+
+impl<E: AsRef<Event>> EventExt for E {
+    fn target_value(&self) -> Option<String> {
+        let target = self.as_ref().target()?;
+        if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+            Some(input.value())
+        } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+            Some(textarea.value())
+        } else if let Some(select) = target.dyn_ref::<HtmlSelectElement>() {
+            Some(select.value())
+        } else {
+            None
+        }
+    }
+
+    fn target_scroll_top(&self) -> Option<i32> {
+        let target = self.as_ref().target()?;
+        target.dyn_ref::<Element>().map(Element::scroll_top)
+    }
+
+    fn target_scroll_left(&self) -> Option<i32> {
+        let target = self.as_ref().target()?;
+        target.dyn_ref::<Element>().map(Element::scroll_left)
+    }
+}
+
+/// A listener of an event on the `window`, which stops listening once it is
+/// dropped along with all of its clones.
+///
+/// Keep it in the state of the component, so that it stops listening when
+/// the component is destroyed without any cleanup in the lifecycle hooks.
+/// The listener is then owned by the component, so it should capture any
+/// [Shared](../type.Shared.html) value of the component as a
+/// [WeakShared](../type.WeakShared.html), lest the component is never
+/// dropped.
+///
+/// # Example
+/// ```ignore
+/// #[component]
+/// struct Shortcuts {
+///     #[state]
+///     keydown: Option<GlobalListener>,
+///     #[state]
+///     pressed: Option<Shared<Vec<String>>>,
+/// }
+///
+/// impl Lifecycle for Shortcuts {
+///     fn mounted(&self) {
+///         let pressed = Rc::new(RefCell::new(vec![]));
+///         let weak = Shared::downgrade(&pressed);
+///         let listener = GlobalListener::new("keydown", move |event: KeyboardEvent| {
+///             if let Some(pressed) = weak.upgrade() {
+///                 pressed.borrow_mut().push(event.key());
+///             }
+///         })
+///         .ok();
+///         self.set_state(|state| {
+///             state.keydown = listener.clone();
+///             state.pressed = Some(pressed.clone());
+///         });
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct GlobalListener(Rc<Listening>);
+
+/// The listening of the `GlobalListener` which is shared by its clones.
+struct Listening {
+    type_: String,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl GlobalListener {
+    /// Starts listening to the event type on the `window`, which the listener
+    /// receives as the type `E`.
+    pub fn new<E: JsCast + 'static>(
+        type_: &str,
+        listener: impl Fn(E) + 'static,
+    ) -> Result<GlobalListener, JsValue> {
+        let listener: Closure<dyn Fn(Event)> =
+            Closure::wrap(Box::new(move |event: Event| listener(event.unchecked_into())));
+        window()
+            .unwrap()
+            .add_event_listener_with_callback(type_, listener.as_ref().unchecked_ref())?;
+        Ok(GlobalListener(Rc::new(Listening {
+            type_: type_.to_string(),
+            listener,
+        })))
+    }
+}
+
+impl PartialEq for GlobalListener {
+    /// GlobalListeners are equal only when they are the clones of the same
+    /// GlobalListener.
+    fn eq(&self, other: &GlobalListener) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        // The window is still around, so it does not fail.
+        let _ = window().unwrap().remove_event_listener_with_callback(
+            &self.type_,
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+/// A timer which calls its callback every `ms` milliseconds till it is
+/// dropped along with all of its clones.
+///
+/// Keep it in the state of the component, so that it is cleared when the
+/// component is destroyed instead of firing on a dead component.
+///
+/// # Example
+/// ```ignore
+/// #[component]
+/// struct Clock {
+///     #[state]
+///     seconds: u32,
+///     #[state]
+///     ticker: Option<Interval>,
+/// }
+///
+/// impl Lifecycle for Clock {
+///     fn created(&self) {
+///         let setter = self.state_setter();
+///         let ticker = Interval::new(1000, move || {
+///             setter.set_state(|state| state.seconds += 1);
+///         });
+///         self.set_state(|state| state.ticker = Some(ticker.clone()));
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Interval(Rc<Timer>);
+
+/// A timer which calls its callback once after `ms` milliseconds, unless it
+/// is dropped along with all of its clones before that.
+///
+/// Like an [Interval](struct.Interval.html), keep it in the state of the
+/// component, so that it is cleared when the component is destroyed.
+#[derive(Clone)]
+pub struct Timeout(Rc<Timer>);
+
+/// The timer of an `Interval` or a `Timeout` which is shared by its clones.
+struct Timer {
+    handle: i32,
+    repeats: bool,
+    _callback: Closure<dyn Fn()>,
+}
+
+/// The delay of a timer in the milliseconds the browser takes.
+///
+/// The browsers store the delay as an `i32`, so the delays longer than
+/// `i32::MAX` milliseconds, about 24.8 days, are clamped to it rather than
+/// wrapping around into a negative delay which fires right away.
+fn delay(ms: u32) -> i32 {
+    ms.min(i32::MAX as u32) as i32
+}
+
+impl Interval {
+    /// Starts calling the callback every `ms` milliseconds.
+    ///
+    /// The `ms` is clamped to `i32::MAX`, about 24.8 days.
+    pub fn new(ms: u32, callback: impl Fn() + 'static) -> Interval {
+        let callback: Closure<dyn Fn()> = Closure::wrap(Box::new(callback));
+        let handle = window()
+            .unwrap()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                callback.as_ref().unchecked_ref(),
+                delay(ms),
+            )
+            .unwrap();
+        Interval(Rc::new(Timer {
+            handle,
+            repeats: true,
+            _callback: callback,
+        }))
+    }
+}
+
+impl Timeout {
+    /// Calls the callback once after `ms` milliseconds.
+    ///
+    /// The `ms` is clamped to `i32::MAX`, about 24.8 days.
+    pub fn new(ms: u32, callback: impl FnOnce() + 'static) -> Timeout {
+        let callback = Cell::new(Some(callback));
+        let callback: Closure<dyn Fn()> = Closure::wrap(Box::new(move || {
+            if let Some(callback) = callback.take() {
+                callback();
+            }
+        }));
+        let handle = window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                callback.as_ref().unchecked_ref(),
+                delay(ms),
+            )
+            .unwrap();
+        Timeout(Rc::new(Timer {
+            handle,
+            repeats: false,
+            _callback: callback,
+        }))
+    }
+}
+
+impl PartialEq for Interval {
+    /// Intervals are equal only when they are the clones of the same
+    /// Interval.
+    fn eq(&self, other: &Interval) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialEq for Timeout {
+    /// Timeouts are equal only when they are the clones of the same Timeout.
+    fn eq(&self, other: &Timeout) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        // Clearing a timeout which has already fired does nothing.
+        let window = window().unwrap();
+        if self.repeats {
+            window.clear_interval_with_handle(self.handle);
+        } else {
+            window.clear_timeout_with_handle(self.handle);
+        }
+    }
+}
+
+/// Calls the callback on every animation frame for `duration` milliseconds,
+/// with the progress of the animation from `0.0` to `1.0`. The last call is
+/// always made with `1.0`.
+///
+/// The animation is cancelled once the returned `Animation` is dropped along
+/// with all of its clones, so keep it in the state of the component to cancel
+/// it when the component is destroyed.
+///
+/// # Example
+/// ```ignore
+/// impl Drawer {
+///     fn open(&self) {
+///         let setter = self.state_setter();
+///         let animation = animate(300.0, move |progress| {
+///             setter.set_state(|state| state.offset = 1.0 - progress);
+///         });
+///         self.set_state(|state| state.animation = Some(animation.clone()));
+///     }
+/// }
+/// ```
+pub fn animate(duration: f64, mut on_frame: impl FnMut(f64) + 'static) -> Animation {
+    let start = window().unwrap().performance().unwrap().now();
+    let animating = Rc::new(Animating {
+        frame: Cell::new(None),
+        step: RefCell::new(None),
+    });
+
+    // The step holds the animation weakly, as it is owned by the animation.
+    let weak = Rc::downgrade(&animating);
+    let step: Closure<dyn FnMut(f64)> = Closure::wrap(Box::new(move |timestamp: f64| {
+        let progress = if duration > 0.0 {
+            ((timestamp - start) / duration).max(0.0).min(1.0)
+        } else {
+            1.0
+        };
+        on_frame(progress);
+        if let Some(animating) = weak.upgrade() {
+            if progress < 1.0 {
+                animating.request_frame();
+            } else {
+                animating.frame.set(None);
+            }
+        }
+    }));
+    *animating.step.borrow_mut() = Some(step);
+    animating.request_frame();
+    Animation(animating)
+}
+
+/// An animation run by `animate`, which is cancelled once it is dropped along
+/// with all of its clones.
+#[derive(Clone)]
+pub struct Animation(Rc<Animating>);
+
+/// The animation of an `Animation` which is shared by its clones.
+struct Animating {
+    /// The animation frame requested for the next step, if any
+    frame: Cell<Option<i32>>,
+    /// The step invoked on every frame
+    step: RefCell<Option<Closure<dyn FnMut(f64)>>>,
+}
+
+impl Animating {
+    /// Requests the next frame to invoke the step on.
+    fn request_frame(&self) {
+        if let Some(ref step) = *self.step.borrow() {
+            let frame = window()
+                .unwrap()
+                .request_animation_frame(step.as_ref().unchecked_ref())
+                .ok();
+            self.frame.set(frame);
+        }
+    }
+}
+
+impl Animation {
+    /// Whether the animation is yet to complete.
+    pub fn is_running(&self) -> bool {
+        self.0.frame.get().is_some()
+    }
+}
+
+impl PartialEq for Animation {
+    /// Animations are equal only when they are the clones of the same
+    /// Animation.
+    fn eq(&self, other: &Animation) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Animating {
+    fn drop(&mut self) {
+        if let Some(frame) = self.frame.get() {
+            let _ = window().unwrap().cancel_animation_frame(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        component::root_render_ctx,
+        dom::DOMPatch,
+        vdom::{
+            test::container,
+            velement::{EventListener, VElement},
+        },
+        Shared, WeakShared,
+    };
+    use futures::Future;
+    use js_sys::Promise;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::*;
+    use web_sys::{PointerEventInit, WheelEventInit};
+
+    /// A future which resolves after `ms` milliseconds.
+    fn sleep(ms: i32) -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        }))
+    }
+
+    #[wasm_bindgen_test]
+    fn should_get_the_value_of_the_target() {
+        let div = container();
+        div.set_inner_html(r#"<input value="Ruukh"><p></p>"#);
+        let event = Event::new("input").unwrap();
+        div.first_child()
+            .unwrap()
+            .dispatch_event(&event)
+            .unwrap();
+        assert_eq!(event.target_value(), Some("Ruukh".to_string()));
+
+        let event = Event::new("input").unwrap();
+        div.last_child().unwrap().dispatch_event(&event).unwrap();
+        assert_eq!(event.target_value(), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_receive_the_deltas_of_wheel_event() {
+        let deltas = Rc::new(Cell::new((0.0, 0.0)));
+        let deltas_clone = deltas.clone();
+        let mut el = VElement::<()>::childless(
+            "div",
+            vec![],
+            vec![EventListener::new(
+                "wheel",
+                Box::new(move |_, event: WheelEvent| {
+                    deltas_clone.set((event.delta_x(), event.delta_y()))
+                }),
+            )],
+        );
+        let div = container();
+        el.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        )
+        .expect("To patch div");
+
+        let mut init = WheelEventInit::new();
+        init.delta_x(3.0).delta_y(-5.0);
+        let wheel = WheelEvent::new_with_event_init_dict("wheel", &init).unwrap();
+        div.first_child().unwrap().dispatch_event(&wheel).unwrap();
+
+        assert_eq!(deltas.get(), (3.0, -5.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_get_the_type_of_the_pointer() {
+        let mut init = PointerEventInit::new();
+        init.pointer_id(3).pointer_type("pen").is_primary(true).pressure(0.5);
+        let pen = PointerEvent::new_with_event_init_dict("pointerdown", &init).unwrap();
+
+        assert_eq!(PointerType::of(&pen), Some(PointerType::Pen));
+        assert_eq!(pen.pointer_id(), 3);
+        assert!(pen.is_primary());
+
+        let unknown = PointerEvent::new("pointerdown").unwrap();
+        assert_eq!(PointerType::of(&unknown), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_collect_the_touches() {
+        let touch = TouchEvent::new("touchstart").unwrap();
+        assert!(touch.touches().to_vec().is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_get_the_scroll_position_of_the_target() {
+        let div = container();
+        div.set_inner_html(
+            r#"<div style="height: 50px; overflow: auto"><p style="height: 500px"></p></div>"#,
+        );
+        let scroller = div.first_element_child().unwrap();
+        scroller.set_scroll_top(120);
+        let scroll = Event::new("scroll").unwrap();
+        scroller.dispatch_event(&scroll).unwrap();
+
+        assert_eq!(scroll.target_scroll_top(), Some(120));
+        assert_eq!(scroll.target_scroll_left(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_stop_listening_on_window_when_dropped() {
+        let resizes = Rc::new(Cell::new(0));
+        let resizes_clone = resizes.clone();
+        let listener = GlobalListener::new("resize", move |_: Event| {
+            resizes_clone.set(resizes_clone.get() + 1)
+        })
+        .unwrap();
+        let resize = || {
+            window()
+                .unwrap()
+                .dispatch_event(&Event::new("resize").unwrap())
+                .unwrap();
+        };
+
+        resize();
+        assert_eq!(resizes.get(), 1);
+
+        let clone = listener.clone();
+        drop(listener);
+        resize();
+        assert_eq!(resizes.get(), 2);
+
+        drop(clone);
+        resize();
+        assert_eq!(resizes.get(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_keep_the_captured_value_alive() {
+        let resizes: Shared<i32> = Rc::new(RefCell::new(0));
+        let weak: WeakShared<i32> = Shared::downgrade(&resizes);
+        let _listener = GlobalListener::new("resize", move |_: Event| {
+            if let Some(resizes) = weak.upgrade() {
+                *resizes.borrow_mut() += 1;
+            }
+        })
+        .unwrap();
+        let resize = || {
+            window()
+                .unwrap()
+                .dispatch_event(&Event::new("resize").unwrap())
+                .unwrap();
+        };
+
+        resize();
+        assert_eq!(*resizes.borrow(), 1);
+
+        let dropped = Shared::downgrade(&resizes);
+        drop(resizes);
+        assert!(dropped.upgrade().is_none());
+        // It bails as the value is gone.
+        resize();
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_animate_with_increasing_progress() -> impl Future<Item = (), Error = JsValue> {
+        use futures::sync::oneshot;
+
+        let (done, completed) = oneshot::channel();
+        let mut done = Some(done);
+        let progresses = Rc::new(RefCell::new(vec![]));
+        let progresses_clone = progresses.clone();
+        let animation = animate(50.0, move |progress| {
+            progresses_clone.borrow_mut().push(progress);
+            if progress >= 1.0 {
+                done.take().unwrap().send(()).unwrap();
+            }
+        });
+        assert!(animation.is_running());
+
+        let cancelled = Rc::new(Cell::new(0));
+        let cancelled_clone = cancelled.clone();
+        drop(animate(50.0, move |_| cancelled_clone.set(cancelled_clone.get() + 1)));
+
+        completed.map_err(|_| JsValue::NULL).map(move |_| {
+            let progresses = progresses.borrow();
+            assert!(progresses.len() > 1);
+            assert!(progresses.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert_eq!(progresses.last(), Some(&1.0));
+            assert!(!animation.is_running());
+            assert_eq!(cancelled.get(), 0);
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_clear_the_timers_when_dropped() -> impl Future<Item = (), Error = JsValue> {
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+        let timeout = Timeout::new(0, move || calls_clone.set(calls_clone.get() + 1));
+        let calls_clone = calls.clone();
+        let interval = Interval::new(0, move || calls_clone.set(calls_clone.get() + 10));
+        let calls_clone = calls.clone();
+        drop(Timeout::new(0, move || calls_clone.set(calls_clone.get() + 100)));
+        drop(timeout.clone());
+        drop(interval);
+
+        sleep(20).map(move |_| {
+            assert_eq!(calls.get(), 1);
+            drop(timeout);
+        })
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_clamp_the_delay_of_long_timers() -> impl Future<Item = (), Error = JsValue> {
+        assert_eq!(delay(u32::MAX), i32::MAX);
+        assert_eq!(delay(1000), 1000);
+
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+        let timeout = Timeout::new(u32::MAX, move || calls_clone.set(calls_clone.get() + 1));
+
+        sleep(20).map(move |_| {
+            assert_eq!(calls.get(), 0);
+            drop(timeout);
+        })
+    }
+}
@@ -0,0 +1,137 @@
+//! Messages between the tabs & the windows of the same origin, e.g. to keep a
+//! shopping cart in sync or to log out of all of them at once.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Session {
+//!     #[state]
+//!     logged_in: bool,
+//!     #[state]
+//!     channel: Option<BroadcastChannel>,
+//! }
+//!
+//! impl Lifecycle for Session {
+//!     fn created(&self) {
+//!         let setter = self.state_setter();
+//!         let channel = BroadcastChannel::new("session").ok();
+//!         if let Some(ref channel) = channel {
+//!             channel.on_message(move |message| {
+//!                 if message.as_string().as_ref().map(String::as_str) == Some("logout") {
+//!                     setter.set_state(|state| state.logged_in = false);
+//!                 }
+//!             });
+//!         }
+//!         self.set_state(|state| state.channel = channel.clone());
+//!     }
+//! }
+//!
+//! impl Session {
+//!     fn log_out(&self) {
+//!         if let Some(ref channel) = self.channel {
+//!             let _ = channel.send(&JsValue::from_str("logout"));
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::MessageEvent;
+
+/// A named channel to message the other tabs & windows of the same origin,
+/// which is closed once it is dropped along with all of its clones.
+///
+/// The messages are received by all the other channels of the same name,
+/// though not by the channel which sent them.
+#[derive(Clone)]
+pub struct BroadcastChannel(Rc<Channel>);
+
+/// The channel of a `BroadcastChannel` which is shared by its clones.
+struct Channel {
+    channel: web_sys::BroadcastChannel,
+    on_message: RefCell<Option<Closure<dyn Fn(MessageEvent)>>>,
+}
+
+impl BroadcastChannel {
+    /// Joins the channel of the name.
+    pub fn new(name: &str) -> Result<BroadcastChannel, JsValue> {
+        Ok(BroadcastChannel(Rc::new(Channel {
+            channel: web_sys::BroadcastChannel::new(name)?,
+            on_message: RefCell::new(None),
+        })))
+    }
+
+    /// Sends the message to the other channels of the same name. It is
+    /// copied with the structured clone, so it may be any plain JS value.
+    pub fn send(&self, message: &JsValue) -> Result<(), JsValue> {
+        self.0.channel.post_message(message)
+    }
+
+    /// Receives the messages sent by the other channels of the same name with
+    /// the handler, replacing the previous handler if any.
+    pub fn on_message(&self, handler: impl Fn(JsValue) + 'static) {
+        let on_message: Closure<dyn Fn(MessageEvent)> =
+            Closure::wrap(Box::new(move |event: MessageEvent| handler(event.data())));
+        self.0
+            .channel
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        *self.0.on_message.borrow_mut() = Some(on_message);
+    }
+}
+
+impl PartialEq for BroadcastChannel {
+    /// BroadcastChannels are equal only when they are the clones of the same
+    /// BroadcastChannel.
+    fn eq(&self, other: &BroadcastChannel) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Channel {
+    fn drop(&mut self) {
+        self.channel.set_onmessage(None);
+        self.channel.close();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::Future;
+    use js_sys::Promise;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::*;
+    use web_sys::window;
+
+    /// A future which resolves after `ms` milliseconds.
+    fn sleep(ms: i32) -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        }))
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_receive_the_messages_of_other_channels() -> impl Future<Item = (), Error = JsValue>
+    {
+        let received = Rc::new(RefCell::new(vec![]));
+        let received_clone = received.clone();
+        let receiver = BroadcastChannel::new("ruukh-test").unwrap();
+        receiver.on_message(move |message| {
+            received_clone
+                .borrow_mut()
+                .push(message.as_string().unwrap_or_default())
+        });
+        let sender = BroadcastChannel::new("ruukh-test").unwrap();
+        sender.send(&JsValue::from_str("logout")).unwrap();
+
+        sleep(100).map(move |_| {
+            assert_eq!(*received.borrow(), vec!["logout".to_string()]);
+            drop(sender);
+            drop(receiver);
+        })
+    }
+}
@@ -0,0 +1,129 @@
+//! Reading and writing the text of the system clipboard.
+//!
+//! The clipboard is only available in the secure contexts, i.e. the pages
+//! served over HTTPS or from `localhost`, and the browser may ask the user
+//! for the permission to read it.
+//!
+//! Requires the `clipboard` feature.
+//!
+//! # Example
+//! ```ignore
+//! impl ShareButton {
+//!     fn on_click(&self, _: MouseEvent) {
+//!         let copied = Clipboard::write_text(&self.link)
+//!             .map(|_| log("Copied!"))
+//!             .map_err(|err| log(&format!("Could not copy: {:?}", err)));
+//!         spawn_local(copied);
+//!     }
+//! }
+//! ```
+
+use futures::Future;
+use js_sys::{Array, Function, Promise, Reflect};
+use wasm_bindgen::{prelude::JsValue, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::window;
+
+/// The reason the clipboard could not be read or written.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardError {
+    /// The clipboard is not available, e.g. in an insecure context, or the
+    /// user has denied the permission.
+    NotAllowed,
+    /// There is no text in the clipboard.
+    NotFound,
+    /// Any other error, with its message.
+    Unknown(String),
+}
+
+impl ClipboardError {
+    /// Creates an error out of a JS error, by the name of the `DOMException`.
+    fn from_js(err: JsValue) -> ClipboardError {
+        let property = |name: &str| {
+            Reflect::get(&err, &JsValue::from_str(name))
+                .ok()
+                .and_then(|value| value.as_string())
+        };
+        match property("name").as_ref().map(String::as_str) {
+            Some("NotAllowedError") | Some("SecurityError") => ClipboardError::NotAllowed,
+            Some("NotFoundError") => ClipboardError::NotFound,
+            _ => ClipboardError::Unknown(
+                property("message")
+                    .or_else(|| err.as_string())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// The system clipboard, i.e. `navigator.clipboard`.
+pub struct Clipboard;
+
+impl Clipboard {
+    /// Reads the text in the clipboard.
+    ///
+    /// The future fails right away with `NotAllowed` when the clipboard is not
+    /// available.
+    pub fn read_text() -> impl Future<Item = String, Error = ClipboardError> {
+        futures::future::result(call("readText", &Array::new()))
+            .and_then(|promise| JsFuture::from(promise).map_err(ClipboardError::from_js))
+            .map(|text| text.as_string().unwrap_or_default())
+    }
+
+    /// Writes the text to the clipboard, replacing its content.
+    ///
+    /// The future fails right away with `NotAllowed` when the clipboard is not
+    /// available.
+    pub fn write_text(text: &str) -> impl Future<Item = (), Error = ClipboardError> {
+        futures::future::result(call("writeText", &Array::of1(&JsValue::from_str(text))))
+            .and_then(|promise| JsFuture::from(promise).map_err(ClipboardError::from_js))
+            .map(|_| ())
+    }
+}
+
+/// Calls the method of `navigator.clipboard`, which is `undefined` in the
+/// insecure contexts.
+fn call(method: &str, args: &Array) -> Result<Promise, ClipboardError> {
+    let navigator = window().ok_or(ClipboardError::NotAllowed)?.navigator();
+    let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined() && !clipboard.is_null())
+        .ok_or(ClipboardError::NotAllowed)?;
+    let method: Function = Reflect::get(&clipboard, &JsValue::from_str(method))
+        .ok()
+        .and_then(|method| method.dyn_into().ok())
+        .ok_or(ClipboardError::NotAllowed)?;
+    Reflect::apply(&method, &clipboard, args)
+        .and_then(|promise| promise.dyn_into::<Promise>())
+        .map_err(ClipboardError::from_js)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use js_sys::Object;
+    use wasm_bindgen_test::*;
+
+    fn dom_exception(name: &str, message: &str) -> JsValue {
+        let err = Object::new();
+        Reflect::set(&err, &JsValue::from_str("name"), &JsValue::from_str(name)).unwrap();
+        Reflect::set(&err, &JsValue::from_str("message"), &JsValue::from_str(message)).unwrap();
+        err.into()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_tell_the_errors_by_their_names() {
+        assert_eq!(
+            ClipboardError::from_js(dom_exception("NotAllowedError", "Denied")),
+            ClipboardError::NotAllowed
+        );
+        assert_eq!(
+            ClipboardError::from_js(dom_exception("NotFoundError", "Empty")),
+            ClipboardError::NotFound
+        );
+        assert_eq!(
+            ClipboardError::from_js(dom_exception("DataError", "Bad data")),
+            ClipboardError::Unknown("Bad data".to_string())
+        );
+    }
+}
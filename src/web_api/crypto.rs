@@ -0,0 +1,131 @@
+//! The cryptographically strong random values of the browser, e.g. to key the
+//! items created on the client or to correlate the requests, without a Rust
+//! RNG crate.
+//!
+//! They panic when the `crypto` of the browser is not available, rather than
+//! returning the values which are not random.
+//!
+//! # Example
+//! ```ignore
+//! impl TodoList {
+//!     fn add(&self, title: String) {
+//!         let todo = Todo {
+//!             id: random_uuid(),
+//!             title,
+//!         };
+//!         self.set_state(|state| state.todos.push(todo));
+//!     }
+//! }
+//! ```
+
+use crate::expect::ExpectMsg;
+use js_sys::{Function, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Crypto};
+
+/// The most bytes which are filled by a single `getRandomValues`.
+const MAX_BYTES: usize = 65536;
+
+fn crypto() -> Crypto {
+    window()
+        .and_then(|window| window.crypto().ok())
+        .expect_msg("The crypto of the browser is not available.")
+}
+
+/// Fills the buffer with the random bytes.
+pub fn random_bytes(buf: &mut [u8]) {
+    let crypto = crypto();
+    for chunk in buf.chunks_mut(MAX_BYTES) {
+        crypto
+            .get_random_values_with_u8_array(chunk)
+            .expect_msg("Could not get the random values from the crypto.");
+    }
+}
+
+/// A random `u32`.
+pub fn random_u32() -> u32 {
+    let mut bytes = [0; 4];
+    random_bytes(&mut bytes);
+    u32::from_le_bytes(bytes)
+}
+
+/// A random `f64` from `0.0` up to, but not including, `1.0`.
+pub fn random_f64() -> f64 {
+    let mut bytes = [0; 8];
+    random_bytes(&mut bytes);
+    // The 53 bits which fit in the mantissa.
+    (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A random version 4 UUID, e.g. `"36b8f84d-df4e-4d49-b662-bcde71a8764f"`.
+///
+/// It is made out of the random bytes in the browsers without the
+/// `crypto.randomUUID`, which is only available in the secure contexts.
+pub fn random_uuid() -> String {
+    let crypto = crypto();
+    let random_uuid = Reflect::get(&crypto, &JsValue::from_str("randomUUID"))
+        .ok()
+        .and_then(|method| method.dyn_into::<Function>().ok());
+    if let Some(random_uuid) = random_uuid {
+        if let Some(uuid) = random_uuid
+            .call0(&crypto)
+            .ok()
+            .and_then(|uuid| uuid.as_string())
+        {
+            return uuid;
+        }
+    }
+
+    let mut bytes = [0; 16];
+    random_bytes(&mut bytes);
+    uuid_from_bytes(bytes)
+}
+
+/// Formats the random bytes as a version 4 UUID.
+fn uuid_from_bytes(mut bytes: [u8; 16]) -> String {
+    // The version 4 & the RFC 4122 variant.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        hex[0..4].concat(),
+        hex[4..6].concat(),
+        hex[6..8].concat(),
+        hex[8..10].concat(),
+        hex[10..16].concat()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_format_the_bytes_as_a_version_4_uuid() {
+        assert_eq!(
+            uuid_from_bytes([0xff; 16]),
+            "ffffffff-ffff-4fff-bfff-ffffffffffff"
+        );
+        assert_eq!(
+            uuid_from_bytes([0; 16]),
+            "00000000-0000-4000-8000-000000000000"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_get_the_random_values() {
+        let mut bytes = [0; 32];
+        random_bytes(&mut bytes);
+        assert!(bytes.iter().any(|byte| *byte != 0));
+
+        let float = random_f64();
+        assert!(float >= 0.0 && float < 1.0);
+
+        let uuid = random_uuid();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(uuid, random_uuid());
+    }
+}
@@ -0,0 +1,308 @@
+//! Showing an element, or the whole page, in fullscreen, e.g. to play a video
+//! or to present the slides.
+//!
+//! The prefixed APIs of the older browsers are used when the standard one is
+//! not available.
+//!
+//! Requires the `fullscreen` feature.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Player {
+//!     #[state]
+//!     video_ref: NodeRef,
+//! }
+//!
+//! impl Render for Player {
+//!     fn render(&self) -> Markup<Self> {
+//!         let fullscreen = use_fullscreen(self.video_ref.clone());
+//!         let toggle = if fullscreen.is_fullscreen {
+//!             fullscreen.exit.clone()
+//!         } else {
+//!             fullscreen.enter.clone()
+//!         };
+//!         html! {
+//!             <video ref={self.video_ref.clone()} src="movie.mp4"></video>
+//!             <button @click={move |_| toggle()}>"Fullscreen"</button>
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{context, vdom::NodeRef};
+use futures::Future;
+use js_sys::{Function, Promise, Reflect};
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, Document, Element, Event};
+
+/// The names of the method requesting the fullscreen on an element, the
+/// standard one first.
+const REQUEST: [&str; 4] = [
+    "requestFullscreen",
+    "webkitRequestFullscreen",
+    "mozRequestFullScreen",
+    "msRequestFullscreen",
+];
+/// The names of the method exiting the fullscreen on the document.
+const EXIT: [&str; 4] = [
+    "exitFullscreen",
+    "webkitExitFullscreen",
+    "mozCancelFullScreen",
+    "msExitFullscreen",
+];
+/// The names of the property of the document with the fullscreen element.
+const ELEMENT: [&str; 4] = [
+    "fullscreenElement",
+    "webkitFullscreenElement",
+    "mozFullScreenElement",
+    "msFullscreenElement",
+];
+/// The types of the events fired whenever the fullscreen is entered, exited or
+/// fails to be entered.
+const EVENTS: [&str; 8] = [
+    "fullscreenchange",
+    "webkitfullscreenchange",
+    "mozfullscreenchange",
+    "MSFullscreenChange",
+    "fullscreenerror",
+    "webkitfullscreenerror",
+    "mozfullscreenerror",
+    "MSFullscreenError",
+];
+
+/// The reason the fullscreen could not be entered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FullscreenError {
+    /// The browser does not support the fullscreen.
+    NotSupported,
+    /// The browser has denied the fullscreen, e.g. when it was not requested
+    /// in response to a click of the user.
+    Denied {
+        /// The message of the browser.
+        message: String,
+    },
+}
+
+impl FullscreenError {
+    /// Creates an error out of the reason a request was rejected with.
+    fn from_js(err: JsValue) -> FullscreenError {
+        let message = Reflect::get(&err, &JsValue::from_str("message"))
+            .ok()
+            .and_then(|message| message.as_string())
+            .or_else(|| err.as_string())
+            .unwrap_or_default();
+        FullscreenError::Denied { message }
+    }
+}
+
+/// Shows the element in fullscreen, or the whole page if it is the
+/// `document.documentElement`.
+///
+/// The fullscreen is exited once the returned handle is dropped along with
+/// all of its clones, unless another element has entered it since.
+pub fn request(
+    element: &Element,
+) -> impl Future<Item = FullscreenHandle, Error = FullscreenError> {
+    let element = element.clone();
+    request_fullscreen(&element).map(move |_| FullscreenHandle(Rc::new(Fullscreen { element })))
+}
+
+/// Requests the fullscreen on the element, which resolves once it is shown.
+fn request_fullscreen(element: &Element) -> impl Future<Item = (), Error = FullscreenError> {
+    // The prefixed methods do not return a promise, and are resolved right
+    // away.
+    futures::future::result(call(element, &REQUEST).ok_or(FullscreenError::NotSupported))
+        .and_then(|result| result.map_err(FullscreenError::from_js))
+        .and_then(|result| {
+            JsFuture::from(Promise::resolve(&result)).map_err(FullscreenError::from_js)
+        })
+        .map(|_| ())
+}
+
+/// Exits the fullscreen, whichever element is shown in it.
+fn exit_fullscreen() {
+    if let Some(document) = document() {
+        if let Some(Ok(result)) = call(&document, &EXIT) {
+            // Nothing is to be done when the fullscreen was exited already.
+            if let Ok(promise) = result.dyn_into::<Promise>() {
+                let exited = JsFuture::from(promise).then(|_| Ok::<(), ()>(()));
+                wasm_bindgen_futures::spawn_local(exited);
+            }
+        }
+    }
+}
+
+/// The element shown in fullscreen currently, if any.
+fn fullscreen_element() -> Option<Element> {
+    let document = document()?;
+    ELEMENT.iter().find_map(|name| {
+        Reflect::get(&document, &JsValue::from_str(name))
+            .ok()?
+            .dyn_into()
+            .ok()
+    })
+}
+
+fn document() -> Option<Document> {
+    window()?.document()
+}
+
+/// Calls the first of the methods of the target which is supported, if any.
+fn call(target: &JsValue, names: &[&str]) -> Option<Result<JsValue, JsValue>> {
+    let method: Function = names.iter().find_map(|name| {
+        Reflect::get(target, &JsValue::from_str(name))
+            .ok()?
+            .dyn_into()
+            .ok()
+    })?;
+    Some(method.call0(target))
+}
+
+/// The fullscreen of an element requested with [request](fn.request.html),
+/// which is exited once it is dropped along with all of its clones.
+#[derive(Clone)]
+pub struct FullscreenHandle(Rc<Fullscreen>);
+
+/// The fullscreen of a `FullscreenHandle` which is shared by its clones.
+struct Fullscreen {
+    element: Element,
+}
+
+impl PartialEq for FullscreenHandle {
+    /// FullscreenHandles are equal only when they are the clones of the same
+    /// FullscreenHandle.
+    fn eq(&self, other: &FullscreenHandle) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Fullscreen {
+    fn drop(&mut self) {
+        if fullscreen_element().as_ref() == Some(&self.element) {
+            exit_fullscreen();
+        }
+    }
+}
+
+/// The fullscreen of an element, returned by
+/// [use_fullscreen](fn.use_fullscreen.html).
+#[derive(Clone)]
+pub struct FullscreenState {
+    /// Whether the element is shown in fullscreen.
+    pub is_fullscreen: bool,
+    /// Shows the element in fullscreen. It has to be called in response to
+    /// an action of the user, e.g. a click, or the browser denies it.
+    pub enter: Rc<dyn Fn()>,
+    /// Exits the fullscreen, if the element is shown in it.
+    pub exit: Rc<dyn Fn()>,
+}
+
+/// The fullscreen of the referenced element, or of the whole page while the
+/// `NodeRef` is empty. Must be called from within `render`.
+///
+/// The component is rerendered whenever any element enters or exits the
+/// fullscreen, or fails to enter it. It is not in fullscreen when rendered to
+/// a string.
+pub fn use_fullscreen(element_ref: NodeRef) -> FullscreenState {
+    let is_fullscreen = context::keep("fullscreen", FullscreenListener::new)
+        .map_or(false, |_| in_fullscreen(&element_ref));
+    let enter = {
+        let element_ref = element_ref.clone();
+        move || {
+            if let Some(element) = target(&element_ref) {
+                wasm_bindgen_futures::spawn_local(request_fullscreen(&element).map_err(|_| ()));
+            }
+        }
+    };
+    FullscreenState {
+        is_fullscreen,
+        enter: Rc::new(enter),
+        exit: Rc::new(move || {
+            if in_fullscreen(&element_ref) {
+                exit_fullscreen();
+            }
+        }),
+    }
+}
+
+/// Whether the target of the reference is shown in fullscreen.
+fn in_fullscreen(element_ref: &NodeRef) -> bool {
+    let element = fullscreen_element();
+    element.is_some() && element == target(element_ref)
+}
+
+/// The referenced element, or the root element of the page if there is none.
+fn target(element_ref: &NodeRef) -> Option<Element> {
+    element_ref
+        .get()
+        .or_else(|| document()?.document_element())
+}
+
+/// A listener of the fullscreen of the page, which stops listening once
+/// dropped.
+///
+/// The events fired at the elements bubble up to the document, so they are
+/// all listened there.
+struct FullscreenListener {
+    document: Option<Document>,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl FullscreenListener {
+    /// Starts listening to the fullscreen of the page.
+    fn new(on_change: Rc<dyn Fn()>) -> FullscreenListener {
+        let listener: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |_| on_change()));
+        let document = document();
+        if let Some(ref document) = document {
+            for type_ in EVENTS.iter() {
+                let _ = document
+                    .add_event_listener_with_callback(type_, listener.as_ref().unchecked_ref());
+            }
+        }
+        FullscreenListener { document, listener }
+    }
+}
+
+impl Drop for FullscreenListener {
+    fn drop(&mut self) {
+        if let Some(ref document) = self.document {
+            for type_ in EVENTS.iter() {
+                let _ = document.remove_event_listener_with_callback(
+                    type_,
+                    self.listener.as_ref().unchecked_ref(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vdom::test::container;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test(async)]
+    fn should_deny_without_a_user_action() -> impl Future<Item = (), Error = JsValue> {
+        let div = container();
+        request(&div).then(|result| {
+            match result {
+                Err(FullscreenError::Denied { .. }) => {}
+                Err(err) => panic!("Expected to be denied but got {:?}", err),
+                Ok(_) => panic!("Expected to be denied but entered the fullscreen"),
+            }
+            Ok(())
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_be_fullscreen_outside_of_an_app() {
+        let state = use_fullscreen(NodeRef::new());
+        assert!(!state.is_fullscreen);
+        // Not in fullscreen, so nothing to exit.
+        (state.exit)();
+    }
+}
@@ -0,0 +1,251 @@
+//! The location of the device evaluated in `render`, either once or tracked
+//! live as the device moves.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for Map {
+//!     fn render(&self) -> Markup<Self> {
+//!         match use_geolocation(true) {
+//!             GeolocationState::Ok(coords) => html! {
+//!                 <Marker lat={coords.latitude} lng={coords.longitude}></Marker>
+//!             },
+//!             GeolocationState::Denied { .. } => html! {
+//!                 <p>"Allow the location access to see where you are."</p>
+//!             },
+//!             _ => html! {
+//!                 <p>"Locating..."</p>
+//!             },
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{context, Shared};
+use js_sys::Reflect;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Geolocation};
+
+/// The code of the error when the user has denied the location access.
+const PERMISSION_DENIED: u16 = 1;
+
+/// The location of the device, as reported by the browser.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeolocationState {
+    /// The location is not looked up, e.g. when rendered to a string.
+    Unknown,
+    /// The location is being looked up.
+    Loading,
+    /// The location is found.
+    Ok(Coordinates),
+    /// The user has denied the location access.
+    Denied {
+        /// The message of the error given by the browser.
+        message: String,
+    },
+    /// The location could not be found, or the browser does not support it.
+    Unavailable {
+        /// The message of the error given by the browser.
+        message: String,
+    },
+}
+
+/// The coordinates of the device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coordinates {
+    /// The latitude in degrees.
+    pub latitude: f64,
+    /// The longitude in degrees.
+    pub longitude: f64,
+    /// The accuracy of the latitude and the longitude in meters.
+    pub accuracy: f64,
+    /// The altitude in meters, if the device can tell.
+    pub altitude: Option<f64>,
+    /// The direction of travel in degrees clockwise from the north, if the
+    /// device can tell.
+    pub heading: Option<f64>,
+    /// The speed in meters per second, if the device can tell.
+    pub speed: Option<f64>,
+}
+
+/// Gets the property of the JS object by the name.
+fn get(target: &JsValue, name: &str) -> JsValue {
+    Reflect::get(target, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+}
+
+impl Coordinates {
+    /// Gets the coordinates out of a `GeolocationPosition`.
+    fn from_position(position: &JsValue) -> Coordinates {
+        let coords = get(position, "coords");
+        let number = |name| get(&coords, name).as_f64();
+        Coordinates {
+            latitude: number("latitude").unwrap_or(0.0),
+            longitude: number("longitude").unwrap_or(0.0),
+            accuracy: number("accuracy").unwrap_or(0.0),
+            altitude: number("altitude"),
+            heading: number("heading"),
+            speed: number("speed"),
+        }
+    }
+}
+
+impl GeolocationState {
+    /// Gets the state out of a `GeolocationPositionError`.
+    fn from_error(error: &JsValue) -> GeolocationState {
+        let message = get(error, "message").as_string().unwrap_or_default();
+        if get(error, "code").as_f64() == Some(f64::from(PERMISSION_DENIED)) {
+            GeolocationState::Denied { message }
+        } else {
+            GeolocationState::Unavailable { message }
+        }
+    }
+}
+
+/// The current location of the device. Must be called from within `render`.
+///
+/// The location is looked up once, or tracked as long as the component lives
+/// when `watch` is set. Either way, the component is rerendered whenever the
+/// location is found or fails to be found. It is `Unknown` when rendered to a
+/// string.
+pub fn use_geolocation(watch: bool) -> GeolocationState {
+    context::keep(&format!("geolocation:{}", watch), |rerender| Locating::new(watch, rerender))
+        .map_or(GeolocationState::Unknown, |locating| locating.state.borrow().clone())
+}
+
+/// The lookup of the location, which stops tracking it once dropped.
+struct Locating {
+    state: Shared<GeolocationState>,
+    /// The geolocation along with the id of the tracking, if tracked
+    watching: Option<(Geolocation, i32)>,
+    _callbacks: Option<(Closure<dyn Fn(JsValue)>, Closure<dyn Fn(JsValue)>)>,
+}
+
+impl Locating {
+    /// Starts looking up the location.
+    fn new(watch: bool, rerender: Rc<dyn Fn()>) -> Locating {
+        let geolocation = match window().unwrap().navigator().geolocation() {
+            Ok(geolocation) => geolocation,
+            Err(_) => {
+                return Locating {
+                    state: Rc::new(RefCell::new(GeolocationState::Unavailable {
+                        message: "The geolocation is not supported.".to_string(),
+                    })),
+                    watching: None,
+                    _callbacks: None,
+                };
+            }
+        };
+
+        let state = Rc::new(RefCell::new(GeolocationState::Loading));
+        let on_position: Closure<dyn Fn(JsValue)> = {
+            let state = state.clone();
+            let rerender = rerender.clone();
+            Closure::wrap(Box::new(move |position| {
+                *state.borrow_mut() = GeolocationState::Ok(Coordinates::from_position(&position));
+                rerender();
+            }))
+        };
+        let on_error: Closure<dyn Fn(JsValue)> = {
+            let state = state.clone();
+            Closure::wrap(Box::new(move |error| {
+                *state.borrow_mut() = GeolocationState::from_error(&error);
+                rerender();
+            }))
+        };
+
+        let success = on_position.as_ref().unchecked_ref();
+        let error = Some(on_error.as_ref().unchecked_ref());
+        let watching = if watch {
+            geolocation
+                .watch_position_with_error_callback(success, error)
+                .ok()
+                .map(|id| (geolocation, id))
+        } else {
+            let _ = geolocation.get_current_position_with_error_callback(success, error);
+            None
+        };
+        Locating {
+            state,
+            watching,
+            _callbacks: Some((on_position, on_error)),
+        }
+    }
+}
+
+impl Drop for Locating {
+    fn drop(&mut self) {
+        if let Some((ref geolocation, id)) = self.watching {
+            geolocation.clear_watch(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use js_sys::Object;
+    use wasm_bindgen_test::*;
+
+    fn object(props: &[(&str, JsValue)]) -> JsValue {
+        let object = Object::new();
+        for (name, value) in props {
+            Reflect::set(&object, &JsValue::from_str(name), value).unwrap();
+        }
+        object.into()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_get_the_coordinates_of_the_position() {
+        let coords = object(&[
+            ("latitude", JsValue::from_f64(27.7)),
+            ("longitude", JsValue::from_f64(85.3)),
+            ("accuracy", JsValue::from_f64(10.0)),
+            ("altitude", JsValue::NULL),
+            ("heading", JsValue::NULL),
+            ("speed", JsValue::from_f64(1.5)),
+        ]);
+        let position = object(&[("coords", coords)]);
+
+        assert_eq!(
+            Coordinates::from_position(&position),
+            Coordinates {
+                latitude: 27.7,
+                longitude: 85.3,
+                accuracy: 10.0,
+                altitude: None,
+                heading: None,
+                speed: Some(1.5),
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_tell_the_denial_from_the_other_errors() {
+        let denied = object(&[
+            ("code", JsValue::from_f64(1.0)),
+            ("message", JsValue::from_str("User denied Geolocation")),
+        ]);
+        let timeout = object(&[
+            ("code", JsValue::from_f64(3.0)),
+            ("message", JsValue::from_str("Timeout expired")),
+        ]);
+
+        assert_eq!(
+            GeolocationState::from_error(&denied),
+            GeolocationState::Denied {
+                message: "User denied Geolocation".to_string()
+            }
+        );
+        assert_eq!(
+            GeolocationState::from_error(&timeout),
+            GeolocationState::Unavailable {
+                message: "Timeout expired".to_string()
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_be_unknown_outside_of_an_app() {
+        assert_eq!(use_geolocation(false), GeolocationState::Unknown);
+    }
+}
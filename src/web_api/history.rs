@@ -0,0 +1,258 @@
+//! Navigating the session history without reloading the page, the
+//! foundation of the client-side routing.
+//!
+//! Provide a [History](struct.History.html) to the whole app with
+//! [App::provide](../../struct.App.html#method.provide), so that any
+//! component can navigate with it or listen to the navigations.
+//!
+//! # Example
+//! ```ignore
+//! App::<MyApp>::new().provide(History::new()).mount("app");
+//!
+//! impl Lifecycle for Page {
+//!     fn created(&self) {
+//!         let history = consume_context::<History>().unwrap();
+//!         let setter = self.state_setter();
+//!         let listener = history.listen(move |location| {
+//!             setter.set_state(|state| state.path = location.pathname.clone());
+//!         });
+//!         self.set_state(|state| state.listener = Some(listener.clone()));
+//!     }
+//! }
+//!
+//! impl Link {
+//!     fn on_click(&self, _: MouseEvent) {
+//!         consume_context::<History>().unwrap().push(&self.href);
+//!     }
+//! }
+//! ```
+
+use crate::web_api::GlobalListener;
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::prelude::JsValue;
+use web_sys::{window, Event};
+
+/// The parts of the URL of the current page which change on navigation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    /// The path, e.g. `/users/5`
+    pub pathname: String,
+    /// The query string along with the leading `?`, if any
+    pub search: String,
+    /// The fragment along with the leading `#`, if any
+    pub hash: String,
+}
+
+impl Location {
+    /// The location of the current page.
+    pub fn current() -> Location {
+        let location = window().unwrap().location();
+        Location {
+            pathname: location.pathname().unwrap_or_default(),
+            search: location.search().unwrap_or_default(),
+            hash: location.hash().unwrap_or_default(),
+        }
+    }
+}
+
+/// The session history of the `window`, which notifies its listeners of every
+/// navigation.
+///
+/// The listeners are notified of the navigations made with it as well as of
+/// the ones made by the browser, like by its back button. Clones share the
+/// listeners, and are equal only to each other.
+#[derive(Clone)]
+pub struct History(Rc<Navigation>);
+
+/// The listeners of a `History` which are shared by its clones.
+struct Navigation {
+    listeners: RefCell<Vec<(usize, Rc<dyn Fn(&Location)>)>>,
+    /// The id of the next listener
+    next_id: Cell<usize>,
+    /// The listener of the navigations by the browser
+    popstate: RefCell<Option<GlobalListener>>,
+}
+
+impl History {
+    /// Starts listening to the navigations on the `window`.
+    pub fn new() -> History {
+        let navigation = Rc::new(Navigation {
+            listeners: RefCell::new(vec![]),
+            next_id: Cell::new(0),
+            popstate: RefCell::new(None),
+        });
+
+        // The listener holds the navigation weakly, as it is owned by it.
+        let weak = Rc::downgrade(&navigation);
+        let popstate = GlobalListener::new("popstate", move |_: Event| {
+            if let Some(navigation) = weak.upgrade() {
+                navigation.notify();
+            }
+        })
+        .ok();
+        *navigation.popstate.borrow_mut() = popstate;
+        History(navigation)
+    }
+
+    /// The location of the current page.
+    pub fn location(&self) -> Location {
+        Location::current()
+    }
+
+    /// Registers a handler which is invoked with the new location on every
+    /// navigation, until the returned listener is dropped along with all of
+    /// its clones.
+    pub fn listen(&self, handler: impl Fn(&Location) + 'static) -> HistoryListener {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        self.0.listeners.borrow_mut().push((id, Rc::new(handler)));
+        HistoryListener(Rc::new(Listening {
+            navigation: Rc::downgrade(&self.0),
+            id,
+        }))
+    }
+
+    /// Navigates to the path, adding an entry to the history.
+    pub fn push(&self, path: &str) -> Result<(), JsValue> {
+        history()?.push_state_with_url(&JsValue::NULL, "", Some(path))?;
+        self.0.notify();
+        Ok(())
+    }
+
+    /// Navigates to the path, replacing the current entry of the history.
+    pub fn replace(&self, path: &str) -> Result<(), JsValue> {
+        history()?.replace_state_with_url(&JsValue::NULL, "", Some(path))?;
+        self.0.notify();
+        Ok(())
+    }
+
+    /// Navigates to the previous entry. The listeners are notified once the
+    /// browser navigates.
+    pub fn back(&self) -> Result<(), JsValue> {
+        history()?.back()
+    }
+
+    /// Navigates to the next entry. The listeners are notified once the
+    /// browser navigates.
+    pub fn forward(&self) -> Result<(), JsValue> {
+        history()?.forward()
+    }
+
+    /// Navigates by `delta` entries, backwards if it is negative. The
+    /// listeners are notified once the browser navigates.
+    pub fn go(&self, delta: i32) -> Result<(), JsValue> {
+        history()?.go_with_delta(delta)
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new()
+    }
+}
+
+impl PartialEq for History {
+    /// Histories are equal only when they are the clones of the same History.
+    fn eq(&self, other: &History) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Navigation {
+    /// Notifies the listeners of the current location.
+    fn notify(&self) {
+        let location = Location::current();
+        // The listeners may register more listeners.
+        let listeners = self.listeners.borrow().clone();
+        for (_, listener) in listeners.iter() {
+            listener(&location);
+        }
+    }
+}
+
+/// A handler registered with `History::listen`, which is unregistered once
+/// it is dropped along with all of its clones.
+///
+/// Keep it in the state of the component, so that the handler is unregistered
+/// when the component is destroyed.
+#[derive(Clone)]
+pub struct HistoryListener(Rc<Listening>);
+
+/// The registration of a `HistoryListener` which is shared by its clones.
+struct Listening {
+    navigation: Weak<Navigation>,
+    id: usize,
+}
+
+impl PartialEq for HistoryListener {
+    /// HistoryListeners are equal only when they are the clones of the same
+    /// HistoryListener.
+    fn eq(&self, other: &HistoryListener) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        if let Some(navigation) = self.navigation.upgrade() {
+            navigation
+                .listeners
+                .borrow_mut()
+                .retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+/// The session history of the `window`.
+fn history() -> Result<web_sys::History, JsValue> {
+    window().unwrap().history()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_notify_the_listeners_of_the_navigations() {
+        let original = Location::current();
+        let history = History::new();
+        let visited = Rc::new(RefCell::new(vec![]));
+        let visited_clone = visited.clone();
+        let listener = history.listen(move |location| {
+            visited_clone.borrow_mut().push(location.clone());
+        });
+
+        history.push("/users/5?tab=posts#top").unwrap();
+        assert_eq!(
+            history.location(),
+            Location {
+                pathname: "/users/5".to_string(),
+                search: "?tab=posts".to_string(),
+                hash: "#top".to_string(),
+            }
+        );
+        history.replace("/users/6").unwrap();
+        window()
+            .unwrap()
+            .dispatch_event(&Event::new("popstate").unwrap())
+            .unwrap();
+
+        let paths: Vec<_> = visited
+            .borrow()
+            .iter()
+            .map(|location| location.pathname.clone())
+            .collect();
+        assert_eq!(paths, vec!["/users/5", "/users/6", "/users/6"]);
+
+        drop(listener);
+        history.push("/users/7").unwrap();
+        assert_eq!(visited.borrow().len(), 3);
+
+        let url = format!("{}{}{}", original.pathname, original.search, original.hash);
+        history.replace(&url).unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+//! Observes whether an element is visible in the viewport, e.g. to lazily
+//! load the images, to load more items of an infinite scroll or to record the
+//! impressions.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct LazyImage {
+//!     #[prop]
+//!     src: String,
+//!     #[state]
+//!     visible: bool,
+//!     #[state]
+//!     image_ref: NodeRef,
+//!     #[state]
+//!     observer: Option<IntersectionObserverHandle>,
+//! }
+//!
+//! impl Lifecycle for LazyImage {
+//!     fn mounted(&self) {
+//!         let setter = self.state_setter();
+//!         let observer = use_intersection_observer(
+//!             self.image_ref.clone(),
+//!             IntersectionOptions::default(),
+//!             move |visible| {
+//!                 if visible {
+//!                     setter.set_state(|state| state.visible = true);
+//!                 }
+//!             },
+//!         )
+//!         .ok();
+//!         self.set_state(|state| state.observer = observer.clone());
+//!     }
+//! }
+//! ```
+
+use crate::vdom::NodeRef;
+use js_sys::Array;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+/// The options of the observation of an element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntersectionOptions {
+    /// The ratio of the element, from `0.0` to `1.0`, which is to be visible
+    /// for it to be counted as visible. With `0.0`, a single visible pixel
+    /// counts.
+    pub threshold: f64,
+    /// Grows or shrinks the viewport by the margin, in the CSS syntax of the
+    /// `margin` property, e.g. `"200px 0px"` to see the element coming sooner.
+    pub root_margin: String,
+}
+
+impl Default for IntersectionOptions {
+    fn default() -> Self {
+        IntersectionOptions {
+            threshold: 0.0,
+            root_margin: "0px".to_string(),
+        }
+    }
+}
+
+/// Observes the referenced element, calling the handler with `true` when it
+/// enters the viewport and with `false` when it exits it. The handler is also
+/// called once soon after, with whether the element is visible already.
+///
+/// Call it once the element is mounted, as nothing is observed while the
+/// `NodeRef` is empty. The element is observed till the returned handle is
+/// dropped along with all of its clones, so keep it in the state of the
+/// component.
+pub fn use_intersection_observer(
+    target: NodeRef,
+    options: IntersectionOptions,
+    handler: impl Fn(bool) + 'static,
+) -> Result<IntersectionObserverHandle, JsValue> {
+    let callback: Closure<dyn Fn(Array)> = Closure::wrap(Box::new(move |entries: Array| {
+        entries.for_each(&mut |entry, _, _| {
+            let entry: IntersectionObserverEntry = entry.unchecked_into();
+            handler(entry.is_intersecting());
+        });
+    }));
+    let mut init = IntersectionObserverInit::new();
+    init.threshold(&JsValue::from_f64(options.threshold))
+        .root_margin(&options.root_margin);
+    let observer =
+        IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &init)?;
+    if let Some(element) = target.get() {
+        observer.observe(&element);
+    }
+    Ok(IntersectionObserverHandle(Rc::new(Observing {
+        observer,
+        _callback: callback,
+    })))
+}
+
+/// The observation made by `use_intersection_observer`, which is disconnected
+/// once it is dropped along with all of its clones.
+#[derive(Clone)]
+pub struct IntersectionObserverHandle(Rc<Observing>);
+
+/// The observer of an `IntersectionObserverHandle` which is shared by its
+/// clones.
+struct Observing {
+    observer: IntersectionObserver,
+    _callback: Closure<dyn Fn(Array)>,
+}
+
+impl PartialEq for IntersectionObserverHandle {
+    /// IntersectionObserverHandles are equal only when they are the clones of
+    /// the same IntersectionObserverHandle.
+    fn eq(&self, other: &IntersectionObserverHandle) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Observing {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vdom::test::container;
+    use futures::Future;
+    use js_sys::Promise;
+    use std::cell::RefCell;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::*;
+    use web_sys::{window, Element};
+
+    /// A future which resolves after `ms` milliseconds.
+    fn sleep(ms: i32) -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        }))
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_report_whether_the_element_is_visible() -> impl Future<Item = (), Error = JsValue> {
+        let body: Element = window().unwrap().document().unwrap().body().unwrap().into();
+        let div = container();
+        div.set_inner_html(r#"<p style="height: 10px"></p>"#);
+        body.append_child(&div).unwrap();
+        let target = NodeRef::new();
+        target.set(div.first_element_child());
+
+        let seen = Rc::new(RefCell::new(vec![]));
+        let seen_clone = seen.clone();
+        let handle = use_intersection_observer(
+            target,
+            IntersectionOptions::default(),
+            move |visible| seen_clone.borrow_mut().push(visible),
+        )
+        .unwrap();
+
+        sleep(100).map(move |_| {
+            assert_eq!(*seen.borrow(), vec![true]);
+            drop(handle);
+            body.remove_child(&div).unwrap();
+        })
+    }
+}
@@ -0,0 +1,314 @@
+//! The names of the keys along with the keyboard shortcuts listened on the
+//! `window`.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Editor {
+//!     #[state]
+//!     save: Option<KeyboardShortcutHandle>,
+//! }
+//!
+//! impl Lifecycle for Editor {
+//!     fn created(&self) {
+//!         let setter = self.state_setter();
+//!         let save = use_keyboard_shortcut("s", KeyModifiers::CTRL, move || {
+//!             setter.set_state(|state| state.saving = true);
+//!         });
+//!         self.set_state(|state| state.save = Some(save.clone()));
+//!     }
+//! }
+//!
+//! impl Editor {
+//!     fn on_keydown(&self, event: KeyboardEvent) {
+//!         if event.key() == Keys::ESCAPE {
+//!             self.close();
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::web_api::GlobalListener;
+use std::{cell::RefCell, rc::Rc};
+use web_sys::KeyboardEvent;
+
+/// The names of the non-printable keys, as in `KeyboardEvent.key`.
+pub struct Keys;
+
+macro_rules! keys {
+    ($($name:ident = $key:expr,)*) => {
+        impl Keys {
+            $(
+                #[allow(missing_docs)]
+                pub const $name: &'static str = $key;
+            )*
+        }
+    };
+}
+
+keys! {
+    ENTER = "Enter",
+    TAB = "Tab",
+    SPACE = " ",
+    ESCAPE = "Escape",
+    BACKSPACE = "Backspace",
+    DELETE = "Delete",
+    INSERT = "Insert",
+    ARROW_UP = "ArrowUp",
+    ARROW_DOWN = "ArrowDown",
+    ARROW_LEFT = "ArrowLeft",
+    ARROW_RIGHT = "ArrowRight",
+    HOME = "Home",
+    END = "End",
+    PAGE_UP = "PageUp",
+    PAGE_DOWN = "PageDown",
+    SHIFT = "Shift",
+    CONTROL = "Control",
+    ALT = "Alt",
+    META = "Meta",
+    CAPS_LOCK = "CapsLock",
+    NUM_LOCK = "NumLock",
+    SCROLL_LOCK = "ScrollLock",
+    CONTEXT_MENU = "ContextMenu",
+    PRINT_SCREEN = "PrintScreen",
+    PAUSE = "Pause",
+    F1 = "F1",
+    F2 = "F2",
+    F3 = "F3",
+    F4 = "F4",
+    F5 = "F5",
+    F6 = "F6",
+    F7 = "F7",
+    F8 = "F8",
+    F9 = "F9",
+    F10 = "F10",
+    F11 = "F11",
+    F12 = "F12",
+}
+
+/// The modifier keys held along with a keyboard shortcut.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyModifiers {
+    /// The `Control` key
+    pub ctrl: bool,
+    /// The `Shift` key
+    pub shift: bool,
+    /// The `Alt` key, i.e. `Option` on a Mac
+    pub alt: bool,
+    /// The `Meta` key, i.e. `Command` on a Mac & `Windows` on a PC
+    pub meta: bool,
+}
+
+impl KeyModifiers {
+    /// No modifier keys.
+    pub const NONE: KeyModifiers = KeyModifiers {
+        ctrl: false,
+        shift: false,
+        alt: false,
+        meta: false,
+    };
+    /// Only the `Control` key.
+    pub const CTRL: KeyModifiers = KeyModifiers {
+        ctrl: true,
+        ..KeyModifiers::NONE
+    };
+    /// Only the `Shift` key.
+    pub const SHIFT: KeyModifiers = KeyModifiers {
+        shift: true,
+        ..KeyModifiers::NONE
+    };
+    /// Only the `Alt` key.
+    pub const ALT: KeyModifiers = KeyModifiers {
+        alt: true,
+        ..KeyModifiers::NONE
+    };
+    /// Only the `Meta` key.
+    pub const META: KeyModifiers = KeyModifiers {
+        meta: true,
+        ..KeyModifiers::NONE
+    };
+
+    /// The modifier keys held during the event.
+    fn of(event: &KeyboardEvent) -> KeyModifiers {
+        KeyModifiers {
+            ctrl: event.ctrl_key(),
+            shift: event.shift_key(),
+            alt: event.alt_key(),
+            meta: event.meta_key(),
+        }
+    }
+}
+
+thread_local! {
+    /// The shortcuts registered currently.
+    static SHORTCUTS: RefCell<Shortcuts> = RefCell::new(Shortcuts::default());
+}
+
+/// The registered shortcuts, which are all listened by a single listener.
+#[derive(Default)]
+struct Shortcuts {
+    next_id: usize,
+    /// The shortcuts in the order they were registered
+    registered: Vec<Shortcut>,
+    listener: Option<GlobalListener>,
+}
+
+struct Shortcut {
+    id: usize,
+    key: String,
+    modifiers: KeyModifiers,
+    handler: Rc<dyn Fn()>,
+}
+
+impl Shortcut {
+    /// Whether the shortcut is the key pressed along with exactly the same
+    /// modifier keys. The letters are matched regardless of their case.
+    fn matches(&self, event: &KeyboardEvent) -> bool {
+        self.modifiers == KeyModifiers::of(event) && self.key.eq_ignore_ascii_case(&event.key())
+    }
+}
+
+/// Calls the handler whenever the key is pressed along with exactly the
+/// modifier keys, instead of the default action of the browser.
+///
+/// The key is the `KeyboardEvent.key` value, e.g. `"k"` or `Keys::ENTER`.
+/// The shortcuts of the same key with different modifier keys do not clash.
+/// When the very same shortcut is registered more than once, only the one
+/// registered last is called, until it is dropped.
+///
+/// It is listened till the returned handle is dropped along with all of its
+/// clones, so keep it in the state of the component.
+pub fn use_keyboard_shortcut(
+    key: &str,
+    modifiers: KeyModifiers,
+    handler: impl Fn() + 'static,
+) -> KeyboardShortcutHandle {
+    SHORTCUTS.with(|shortcuts| {
+        let mut shortcuts = shortcuts.borrow_mut();
+        if shortcuts.listener.is_none() {
+            shortcuts.listener = GlobalListener::new("keydown", on_keydown).ok();
+        }
+        let id = shortcuts.next_id;
+        shortcuts.next_id += 1;
+        shortcuts.registered.push(Shortcut {
+            id,
+            key: key.to_string(),
+            modifiers,
+            handler: Rc::new(handler),
+        });
+        KeyboardShortcutHandle(Rc::new(Registration { id }))
+    })
+}
+
+/// Calls the handler of the shortcut registered last which matches the event.
+fn on_keydown(event: KeyboardEvent) {
+    let handler = SHORTCUTS.with(|shortcuts| {
+        shortcuts
+            .borrow()
+            .registered
+            .iter()
+            .rev()
+            .find(|shortcut| shortcut.matches(&event))
+            .map(|shortcut| shortcut.handler.clone())
+    });
+    if let Some(handler) = handler {
+        event.prevent_default();
+        handler();
+    }
+}
+
+/// A keyboard shortcut registered by `use_keyboard_shortcut`, which is
+/// removed once it is dropped along with all of its clones.
+#[derive(Clone)]
+pub struct KeyboardShortcutHandle(Rc<Registration>);
+
+/// The registration of a `KeyboardShortcutHandle` which is shared by its
+/// clones.
+struct Registration {
+    id: usize,
+}
+
+impl PartialEq for KeyboardShortcutHandle {
+    /// KeyboardShortcutHandles are equal only when they are the clones of the
+    /// same KeyboardShortcutHandle.
+    fn eq(&self, other: &KeyboardShortcutHandle) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        // The listener is taken out before it is dropped, so that the
+        // shortcuts are not borrowed while it stops listening.
+        let listener = SHORTCUTS.with(|shortcuts| {
+            let mut shortcuts = shortcuts.borrow_mut();
+            shortcuts.registered.retain(|shortcut| shortcut.id != self.id);
+            if shortcuts.registered.is_empty() {
+                shortcuts.listener.take()
+            } else {
+                None
+            }
+        });
+        drop(listener);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use wasm_bindgen_test::*;
+    use web_sys::{window, KeyboardEventInit};
+
+    fn press(key: &str, ctrl: bool) -> bool {
+        let mut init = KeyboardEventInit::new();
+        init.key(key).ctrl_key(ctrl).cancelable(true);
+        let event = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+        window().unwrap().dispatch_event(&event).unwrap();
+        event.default_prevented()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_tell_the_shortcuts_apart_by_their_modifiers() {
+        let pressed = Rc::new(RefCell::new(vec![]));
+        let pressed_clone = pressed.clone();
+        let submit = use_keyboard_shortcut(Keys::ENTER, KeyModifiers::CTRL, move || {
+            pressed_clone.borrow_mut().push("submit")
+        });
+        let pressed_clone = pressed.clone();
+        let _newline = use_keyboard_shortcut(Keys::ENTER, KeyModifiers::NONE, move || {
+            pressed_clone.borrow_mut().push("newline")
+        });
+
+        assert!(press("Enter", true));
+        assert!(press("Enter", false));
+        assert_eq!(*pressed.borrow(), vec!["submit", "newline"]);
+
+        drop(submit);
+        assert!(!press("Enter", true));
+        assert_eq!(*pressed.borrow(), vec!["submit", "newline"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_call_only_the_shortcut_registered_last() {
+        let calls = Rc::new(Cell::new((0, 0)));
+        let calls_clone = calls.clone();
+        let _first = use_keyboard_shortcut("k", KeyModifiers::META, move || {
+            calls_clone.set((calls_clone.get().0 + 1, calls_clone.get().1))
+        });
+        let calls_clone = calls.clone();
+        let second = use_keyboard_shortcut("k", KeyModifiers::META, move || {
+            calls_clone.set((calls_clone.get().0, calls_clone.get().1 + 1))
+        });
+
+        let mut init = KeyboardEventInit::new();
+        init.key("K").meta_key(true);
+        let event = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+        window().unwrap().dispatch_event(&event).unwrap();
+        assert_eq!(calls.get(), (0, 1));
+
+        drop(second);
+        window().unwrap().dispatch_event(&event).unwrap();
+        assert_eq!(calls.get(), (1, 1));
+    }
+}
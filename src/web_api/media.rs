@@ -0,0 +1,91 @@
+//! Media queries evaluated in `render`, for the components which render
+//! differently by the features of the device, e.g. the preferred color scheme.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for Page {
+//!     fn render(&self) -> Markup<Self> {
+//!         let theme = if use_media_query("(prefers-color-scheme: dark)") {
+//!             "dark"
+//!         } else {
+//!             "light"
+//!         };
+//!         html! {
+//!             <main class={theme}>"Hello"</main>
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::context;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Event, MediaQueryList};
+
+/// Whether the media query matches currently. Must be called from within
+/// `render`.
+///
+/// The query is watched for as long as the component lives, which is
+/// rerendered whenever the query starts or stops matching. It does not match
+/// when rendered to a string, as there is no browser to evaluate it.
+pub fn use_media_query(query: &str) -> bool {
+    context::keep(&format!("media:{}", query), |rerender| MediaQuery::new(query, rerender))
+    .and_then(|media| (*media).as_ref().map(|media| media.list.matches()))
+    .unwrap_or(false)
+}
+
+/// A media query watched for changes, which is stopped once dropped.
+struct MediaQuery {
+    list: MediaQueryList,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl MediaQuery {
+    /// Starts watching the query, if it is valid.
+    fn new(query: &str, on_change: Rc<dyn Fn()>) -> Option<MediaQuery> {
+        let list = window().unwrap().match_media(query).ok()??;
+        let listener: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |_| on_change()));
+        list.add_event_listener_with_callback("change", listener.as_ref().unchecked_ref())
+            .ok()?;
+        Some(MediaQuery { list, listener })
+    }
+}
+
+impl Drop for MediaQuery {
+    fn drop(&mut self) {
+        let _ = self
+            .list
+            .remove_event_listener_with_callback("change", self.listener.as_ref().unchecked_ref());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::context::Scope;
+    use std::cell::RefCell;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_match_the_media_query() {
+        let scope = Rc::new(RefCell::new(Scope::default()));
+        scope.borrow_mut().set_sender(crate::message_sender());
+        context::enter(scope);
+
+        assert!(use_media_query("(min-width: 0px)"));
+        assert!(!use_media_query("(max-width: 0px)"));
+        // Kept from the first call.
+        assert!(use_media_query("(min-width: 0px)"));
+
+        context::exit();
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_match_outside_of_an_app() {
+        assert!(!use_media_query("(min-width: 0px)"));
+
+        context::enter(Rc::new(RefCell::new(Scope::default())));
+        assert!(!use_media_query("(min-width: 0px)"));
+        context::exit();
+    }
+}
@@ -0,0 +1,198 @@
+//! The network connection of the device evaluated in `render`, e.g. to show
+//! that it is offline or to load the lighter images on a slow connection.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for Gallery {
+//!     fn render(&self) -> Markup<Self> {
+//!         let network = use_network_info();
+//!         let is_slow = network
+//!             .effective_type
+//!             .map_or(false, |kind| kind < EffectiveConnectionType::FourG);
+//!         let quality = if network.save_data || is_slow {
+//!             "low"
+//!         } else {
+//!             "high"
+//!         };
+//!         html! {
+//!             <p class:offline={!network.online}>"You are offline."</p>
+//!             <Photos quality={quality}></Photos>
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::context;
+use js_sys::Reflect;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Event, EventTarget};
+
+/// The kind of the connection, by how fast it has been recently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EffectiveConnectionType {
+    /// `slow-2g`
+    Slow2g,
+    /// `2g`
+    TwoG,
+    /// `3g`
+    ThreeG,
+    /// `4g`
+    FourG,
+}
+
+/// The state of the network connection.
+///
+/// The details of the connection are only known in the browsers supporting
+/// the `navigator.connection`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInfo {
+    /// Whether the device is online.
+    pub online: bool,
+    /// The kind of the connection.
+    pub effective_type: Option<EffectiveConnectionType>,
+    /// The estimated bandwidth in megabits per second.
+    pub downlink: Option<f64>,
+    /// The estimated round-trip time in milliseconds.
+    pub rtt: Option<u32>,
+    /// Whether the user has asked to reduce the data usage.
+    pub save_data: bool,
+}
+
+impl Default for NetworkInfo {
+    fn default() -> Self {
+        NetworkInfo {
+            online: true,
+            effective_type: None,
+            downlink: None,
+            rtt: None,
+            save_data: false,
+        }
+    }
+}
+
+/// Gets the property of the JS object by the name.
+fn get(target: &JsValue, name: &str) -> JsValue {
+    Reflect::get(target, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+}
+
+/// The `navigator.connection`, if the browser supports it.
+fn connection() -> Option<EventTarget> {
+    let navigator = window()?.navigator();
+    get(&navigator, "connection").dyn_into().ok()
+}
+
+impl NetworkInfo {
+    /// Gets the current state of the network connection.
+    fn current() -> NetworkInfo {
+        let online = window().unwrap().navigator().on_line();
+        let connection = match connection() {
+            Some(connection) => JsValue::from(connection),
+            None => {
+                return NetworkInfo {
+                    online,
+                    ..NetworkInfo::default()
+                };
+            }
+        };
+        let effective_type = match get(&connection, "effectiveType").as_string() {
+            Some(ref kind) if kind == "slow-2g" => Some(EffectiveConnectionType::Slow2g),
+            Some(ref kind) if kind == "2g" => Some(EffectiveConnectionType::TwoG),
+            Some(ref kind) if kind == "3g" => Some(EffectiveConnectionType::ThreeG),
+            Some(ref kind) if kind == "4g" => Some(EffectiveConnectionType::FourG),
+            _ => None,
+        };
+        NetworkInfo {
+            online,
+            effective_type,
+            downlink: get(&connection, "downlink").as_f64(),
+            rtt: get(&connection, "rtt").as_f64().map(|rtt| rtt as u32),
+            save_data: get(&connection, "saveData").as_bool().unwrap_or(false),
+        }
+    }
+}
+
+/// The current state of the network connection. Must be called from within
+/// `render`.
+///
+/// The connection is watched for as long as the component lives, which is
+/// rerendered whenever the device goes online or offline, or the connection
+/// changes. It is online when rendered to a string.
+pub fn use_network_info() -> NetworkInfo {
+    context::keep("network_info", NetworkListener::new)
+        .map_or_else(NetworkInfo::default, |_| NetworkInfo::current())
+}
+
+/// A listener of the changes of the connection, which stops listening once
+/// dropped.
+struct NetworkListener {
+    /// The targets along with the types of the events listened on them
+    listening: Vec<(EventTarget, &'static str)>,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl NetworkListener {
+    /// Starts listening to the changes of the connection.
+    fn new(on_change: Rc<dyn Fn()>) -> NetworkListener {
+        let listener: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |_| on_change()));
+        let window: EventTarget = window().unwrap().into();
+        let mut targets = vec![(window.clone(), "online"), (window, "offline")];
+        if let Some(connection) = connection() {
+            targets.push((connection, "change"));
+        }
+        let listening = targets
+            .into_iter()
+            .filter(|(target, type_)| {
+                target
+                    .add_event_listener_with_callback(type_, listener.as_ref().unchecked_ref())
+                    .is_ok()
+            })
+            .collect();
+        NetworkListener {
+            listening,
+            listener,
+        }
+    }
+}
+
+impl Drop for NetworkListener {
+    fn drop(&mut self) {
+        for (target, type_) in &self.listening {
+            let _ = target
+                .remove_event_listener_with_callback(type_, self.listener.as_ref().unchecked_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::context::Scope;
+    use std::cell::RefCell;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_rerender_when_the_device_goes_offline() {
+        let scope = Rc::new(RefCell::new(Scope::default()));
+        scope.borrow_mut().set_sender(crate::message_sender());
+        context::enter(scope.clone());
+
+        assert_eq!(
+            use_network_info().online,
+            window().unwrap().navigator().on_line()
+        );
+        context::exit();
+
+        assert!(!context::is_stale(&scope));
+        window()
+            .unwrap()
+            .dispatch_event(&Event::new("offline").unwrap())
+            .unwrap();
+        assert!(context::is_stale(&scope));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_be_online_outside_of_an_app() {
+        assert_eq!(use_network_info(), NetworkInfo::default());
+    }
+}
@@ -0,0 +1,172 @@
+//! Observes the size of an element, for the components which lay themselves
+//! out by their own size rather than by the size of the window, e.g. the
+//! charts.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Chart {
+//!     #[state]
+//!     size: (f64, f64),
+//!     #[state]
+//!     chart_ref: NodeRef,
+//!     #[state]
+//!     observer: Option<ResizeObserverHandle>,
+//! }
+//!
+//! impl Lifecycle for Chart {
+//!     fn mounted(&self) {
+//!         let setter = self.state_setter();
+//!         let observer = use_resize_observer(self.chart_ref.clone(), move |width, height| {
+//!             setter.set_state(|state| state.size = (width, height));
+//!         })
+//!         .ok();
+//!         self.set_state(|state| state.observer = observer.clone());
+//!     }
+//! }
+//! ```
+
+use crate::vdom::NodeRef;
+use js_sys::{Array, Function};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{DomRectReadOnly, Element};
+
+// The `ResizeObserver` is not a part of the web-sys yet.
+#[wasm_bindgen]
+extern "C" {
+    type ResizeObserver;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new(callback: &Function) -> Result<ResizeObserver, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn observe(this: &ResizeObserver, target: &Element);
+
+    #[wasm_bindgen(method)]
+    fn disconnect(this: &ResizeObserver);
+
+    type ResizeObserverEntry;
+
+    #[wasm_bindgen(method, getter, js_name = contentRect)]
+    fn content_rect(this: &ResizeObserverEntry) -> DomRectReadOnly;
+}
+
+/// Observes the referenced element, calling the handler with the width and
+/// the height of its content box in pixels whenever it is resized.
+///
+/// The browser reports the size once as soon as the element is observed, so
+/// the handler is first called with its initial size. The reports of the
+/// same size as the last one are skipped. The handler is called outside of
+/// any event listener, so it sets the state with the
+/// [state_setter](../../component/trait.StateSetter.html) of the component,
+/// which messages the app to rerender it.
+///
+/// Call it once the element is mounted, as nothing is observed while the
+/// `NodeRef` is empty. The element is observed till the returned handle is
+/// dropped along with all of its clones, so keep it in the state of the
+/// component.
+pub fn use_resize_observer(
+    target: NodeRef,
+    handler: impl Fn(f64, f64) + 'static,
+) -> Result<ResizeObserverHandle, JsValue> {
+    let last_size = Cell::new(None);
+    let callback: Closure<dyn Fn(Array)> = Closure::wrap(Box::new(move |entries: Array| {
+        // Only the last size of the element matters.
+        let entry: ResizeObserverEntry = entries.pop().unchecked_into();
+        let rect = entry.content_rect();
+        let size = (rect.width(), rect.height());
+        if last_size.replace(Some(size)) != Some(size) {
+            handler(size.0, size.1);
+        }
+    }));
+    let observer = ResizeObserver::new(callback.as_ref().unchecked_ref())?;
+    if let Some(element) = target.get() {
+        observer.observe(&element);
+    }
+    Ok(ResizeObserverHandle(Rc::new(Observing {
+        observer,
+        _callback: callback,
+    })))
+}
+
+/// The observation made by `use_resize_observer`, which is disconnected once
+/// it is dropped along with all of its clones.
+#[derive(Clone)]
+pub struct ResizeObserverHandle(Rc<Observing>);
+
+/// The observer of a `ResizeObserverHandle` which is shared by its clones.
+struct Observing {
+    observer: ResizeObserver,
+    _callback: Closure<dyn Fn(Array)>,
+}
+
+impl PartialEq for ResizeObserverHandle {
+    /// ResizeObserverHandles are equal only when they are the clones of the
+    /// same ResizeObserverHandle.
+    fn eq(&self, other: &ResizeObserverHandle) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Observing {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vdom::test::container;
+    use futures::Future;
+    use js_sys::Promise;
+    use std::cell::RefCell;
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_test::*;
+    use web_sys::window;
+
+    /// A future which resolves after `ms` milliseconds.
+    fn sleep(ms: i32) -> JsFuture {
+        JsFuture::from(Promise::new(&mut |resolve, _| {
+            window()
+                .unwrap()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .unwrap();
+        }))
+    }
+
+    #[wasm_bindgen_test(async)]
+    fn should_report_the_initial_size_and_the_resizes() -> impl Future<Item = (), Error = JsValue>
+    {
+        let body: Element = window().unwrap().document().unwrap().body().unwrap().into();
+        let div = container();
+        div.set_inner_html(r#"<p style="width: 50px; height: 20px; margin: 0"></p>"#);
+        body.append_child(&div).unwrap();
+        let target = NodeRef::new();
+        target.set(div.first_element_child());
+
+        let sizes = Rc::new(RefCell::new(vec![]));
+        let sizes_clone = sizes.clone();
+        let handle = use_resize_observer(target.clone(), move |width, height| {
+            sizes_clone.borrow_mut().push((width, height))
+        })
+        .unwrap();
+
+        sleep(100)
+            .and_then(move |_| {
+                assert_eq!(*sizes.borrow(), vec![(50.0, 20.0)]);
+                target
+                    .get()
+                    .unwrap()
+                    .set_attribute("style", "width: 80px; height: 20px; margin: 0")
+                    .unwrap();
+                sleep(100).map(move |_| sizes)
+            })
+            .map(move |sizes| {
+                assert_eq!(*sizes.borrow(), vec![(50.0, 20.0), (80.0, 20.0)]);
+                drop(handle);
+                body.remove_child(&div).unwrap();
+            })
+    }
+}
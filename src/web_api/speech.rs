@@ -0,0 +1,295 @@
+//! Speaking the text out loud in `render`, e.g. to read an article to the user
+//! or to announce a notification.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for Article {
+//!     fn render(&self) -> Markup<Self> {
+//!         let speech = use_speech_synthesis();
+//!         let options = SpeechOptions {
+//!             lang: Some("en-US".to_string()),
+//!             ..SpeechOptions::default()
+//!         };
+//!         let text = self.text.clone();
+//!         html! {
+//!             <button @click={move |_| speech.speak(&text, options.clone())}>
+//!                 { if speech.speaking { "Reading..." } else { "Read aloud" } }
+//!             </button>
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::context;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Event, SpeechSynthesis, SpeechSynthesisUtterance, SpeechSynthesisVoice};
+
+/// The events of an utterance which change whether it is speaking or paused.
+const UTTERANCE_EVENTS: [&str; 5] = ["start", "end", "error", "pause", "resume"];
+
+/// How the text is spoken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechOptions {
+    /// The language of the text as a BCP 47 tag, e.g. `en-US`. The language of
+    /// the page is used when `None`.
+    pub lang: Option<String>,
+    /// The name of the voice speaking the text, as in
+    /// [SpeechVoice::name](struct.SpeechVoice.html#structfield.name). The
+    /// default voice is used when `None` or when there is no such voice.
+    pub voice_name: Option<String>,
+    /// The speed, from `0.1` to `10`, where `1` is the normal speed.
+    pub rate: f64,
+    /// The pitch, from `0` to `2`, where `1` is the normal pitch.
+    pub pitch: f64,
+    /// The volume, from `0` to `1`.
+    pub volume: f64,
+}
+
+impl Default for SpeechOptions {
+    fn default() -> Self {
+        SpeechOptions {
+            lang: None,
+            voice_name: None,
+            rate: 1.0,
+            pitch: 1.0,
+            volume: 1.0,
+        }
+    }
+}
+
+/// A voice the text may be spoken with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechVoice {
+    /// The name of the voice, e.g. `Google UK English Female`.
+    pub name: String,
+    /// The language of the voice as a BCP 47 tag.
+    pub lang: String,
+    /// Whether the voice is synthesized on the device, instead of by a remote
+    /// service.
+    pub is_local: bool,
+    /// Whether it is the default voice of the browser.
+    pub is_default: bool,
+}
+
+impl SpeechVoice {
+    fn from_voice(voice: &SpeechSynthesisVoice) -> SpeechVoice {
+        SpeechVoice {
+            name: voice.name(),
+            lang: voice.lang(),
+            is_local: voice.local_service(),
+            is_default: voice.default(),
+        }
+    }
+}
+
+/// Speaks the text with the speech synthesis of the browser, returned by
+/// [use_speech_synthesis](fn.use_speech_synthesis.html).
+///
+/// Does nothing when the browser does not support the speech synthesis or
+/// when rendered to a string.
+#[derive(Clone)]
+pub struct SpeechSynthesisController {
+    /// Whether any text is being spoken, even if paused.
+    pub speaking: bool,
+    /// Whether the speech is paused.
+    pub paused: bool,
+    speech: Option<Rc<Speech>>,
+}
+
+impl SpeechSynthesisController {
+    /// Speaks the text once the text queued before has been spoken.
+    pub fn speak(&self, text: &str, options: SpeechOptions) {
+        if let Some(ref speech) = self.speech {
+            speech.speak(text, &options);
+        }
+    }
+
+    /// Pauses the speech.
+    pub fn pause(&self) {
+        if let Some(synthesis) = self.synthesis() {
+            synthesis.pause();
+        }
+    }
+
+    /// Resumes the paused speech.
+    pub fn resume(&self) {
+        if let Some(synthesis) = self.synthesis() {
+            synthesis.resume();
+        }
+    }
+
+    /// Stops the speech and removes all the text queued.
+    pub fn cancel(&self) {
+        if let Some(synthesis) = self.synthesis() {
+            synthesis.cancel();
+        }
+    }
+
+    /// The voices the text may be spoken with.
+    ///
+    /// Some browsers load the voices lazily, so they may be empty at first.
+    /// The component is rerendered once they are loaded.
+    pub fn voices(&self) -> Vec<SpeechVoice> {
+        self.synthesis().map_or_else(Vec::new, |synthesis| {
+            voices(synthesis)
+                .iter()
+                .map(SpeechVoice::from_voice)
+                .collect()
+        })
+    }
+
+    fn synthesis(&self) -> Option<&SpeechSynthesis> {
+        self.speech
+            .as_ref()
+            .and_then(|speech| speech.synthesis.as_ref())
+    }
+}
+
+/// The speech synthesis of the browser. Must be called from within `render`.
+///
+/// The component is rerendered whenever the speech starts, ends, is paused or
+/// is resumed, as well as when the voices are loaded. The speech is cancelled
+/// once the component is unmounted.
+pub fn use_speech_synthesis() -> SpeechSynthesisController {
+    match context::keep("speech_synthesis", Speech::new) {
+        Some(speech) => {
+            let (speaking, paused) = speech
+                .synthesis
+                .as_ref()
+                .map_or((false, false), |synthesis| {
+                    (synthesis.speaking(), synthesis.paused())
+                });
+            SpeechSynthesisController {
+                speaking,
+                paused,
+                speech: Some(speech),
+            }
+        }
+        None => SpeechSynthesisController {
+            speaking: false,
+            paused: false,
+            speech: None,
+        },
+    }
+}
+
+/// The voices of the speech synthesis.
+fn voices(synthesis: &SpeechSynthesis) -> Vec<SpeechSynthesisVoice> {
+    synthesis
+        .get_voices()
+        .iter()
+        .filter_map(|voice| voice.dyn_into().ok())
+        .collect()
+}
+
+/// The speech of a component, which is cancelled once dropped.
+struct Speech {
+    /// The speech synthesis, if the browser supports it
+    synthesis: Option<SpeechSynthesis>,
+    /// The utterances which have not ended yet, which are kept so that they
+    /// are not garbage collected while being spoken
+    utterances: Rc<RefCell<Vec<SpeechSynthesisUtterance>>>,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl Speech {
+    /// Starts listening to the voices being loaded.
+    fn new(on_change: Rc<dyn Fn()>) -> Speech {
+        let utterances: Rc<RefCell<Vec<SpeechSynthesisUtterance>>> = Rc::default();
+        let listener: Closure<dyn Fn(Event)> = {
+            let utterances = utterances.clone();
+            Closure::wrap(Box::new(move |event: Event| {
+                if event.type_() == "end" || event.type_() == "error" {
+                    let target = event.target().map(JsValue::from);
+                    utterances.borrow_mut().retain(|utterance| {
+                        target.as_ref() != Some::<&JsValue>(utterance.as_ref())
+                    });
+                }
+                on_change();
+            }))
+        };
+        let synthesis = window().unwrap().speech_synthesis().ok();
+        if let Some(ref synthesis) = synthesis {
+            let _ = synthesis.add_event_listener_with_callback(
+                "voiceschanged",
+                listener.as_ref().unchecked_ref(),
+            );
+        }
+        Speech {
+            synthesis,
+            utterances,
+            listener,
+        }
+    }
+
+    /// Queues the text to be spoken.
+    fn speak(&self, text: &str, options: &SpeechOptions) {
+        let synthesis = match self.synthesis {
+            Some(ref synthesis) => synthesis,
+            None => return,
+        };
+        let utterance = match SpeechSynthesisUtterance::new_with_text(text) {
+            Ok(utterance) => utterance,
+            Err(_) => return,
+        };
+        if let Some(ref lang) = options.lang {
+            utterance.set_lang(lang);
+        }
+        if let Some(ref voice_name) = options.voice_name {
+            let voice = voices(synthesis)
+                .into_iter()
+                .find(|voice| voice.name() == *voice_name);
+            utterance.set_voice(voice.as_ref());
+        }
+        utterance.set_rate(options.rate as f32);
+        utterance.set_pitch(options.pitch as f32);
+        utterance.set_volume(options.volume as f32);
+        for type_ in UTTERANCE_EVENTS.iter() {
+            let _ = utterance
+                .add_event_listener_with_callback(type_, self.listener.as_ref().unchecked_ref());
+        }
+        synthesis.speak(&utterance);
+        self.utterances.borrow_mut().push(utterance);
+    }
+}
+
+impl Drop for Speech {
+    fn drop(&mut self) {
+        let listener = self.listener.as_ref().unchecked_ref();
+        // The listeners are removed first, as cancelling fires the `end`
+        // events of the utterances once the listener has been freed.
+        for utterance in self.utterances.borrow().iter() {
+            for type_ in UTTERANCE_EVENTS.iter() {
+                let _ = utterance.remove_event_listener_with_callback(type_, listener);
+            }
+        }
+        if let Some(ref synthesis) = self.synthesis {
+            let _ = synthesis.remove_event_listener_with_callback("voiceschanged", listener);
+            synthesis.cancel();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_speak_at_the_normal_speed_by_default() {
+        let options = SpeechOptions::default();
+        assert_eq!((options.rate, options.pitch, options.volume), (1.0, 1.0, 1.0));
+        assert_eq!(options.voice_name, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_speak_outside_of_an_app() {
+        let speech = use_speech_synthesis();
+        speech.speak("Hello", SpeechOptions::default());
+
+        assert!(!speech.speaking);
+        assert!(!speech.paused);
+        assert!(speech.voices().is_empty());
+    }
+}
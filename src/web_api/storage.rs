@@ -0,0 +1,239 @@
+//! State persisted in the `localStorage` as JSON, which survives the reloads
+//! and is kept in sync across the tabs.
+//!
+//! Requires the `serialize` feature.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct TodoList {
+//!     #[state]
+//!     todos: Vec<Todo>,
+//!     #[state]
+//!     stored: Option<StoredState<Vec<Todo>>>,
+//! }
+//!
+//! impl Lifecycle for TodoList {
+//!     fn created(&self) {
+//!         let setter = self.state_setter();
+//!         let stored = StoredState::new("todos", vec![]).on_change(move |todos| {
+//!             setter.set_state(|state| state.todos = todos.clone());
+//!         });
+//!         let todos = stored.borrow().clone();
+//!         self.set_state(|state| {
+//!             state.todos = todos.clone();
+//!             state.stored = Some(stored.clone());
+//!         });
+//!     }
+//! }
+//! ```
+
+use crate::{expect::ExpectMsg, web_api::GlobalListener, Shared};
+use js_sys::JSON;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    ops::{Deref, DerefMut},
+    rc::{Rc, Weak},
+};
+use web_sys::{window, Storage, StorageEvent};
+
+/// A value persisted in the `localStorage` under a key.
+///
+/// It is read from the storage when created and written back after every
+/// mutation. When another tab writes to the same key, the value is updated
+/// and the `on_change` callback is invoked.
+///
+/// Clones share the same value, and are equal only to each other.
+pub struct StoredState<T>(Rc<Stored<T>>);
+
+/// The value of a `StoredState` which is shared by its clones.
+struct Stored<T> {
+    key: String,
+    value: Shared<T>,
+    on_change: RefCell<Option<Box<dyn Fn(&T)>>>,
+    /// The listener of the writes made by the other tabs
+    listener: RefCell<Option<GlobalListener>>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> StoredState<T> {
+    /// Reads the value stored under the key, falling back to the default if
+    /// there is none or it cannot be deserialized.
+    pub fn new(key: impl Into<String>, default: T) -> StoredState<T> {
+        let key = key.into();
+        let value = local_storage()
+            .and_then(|storage| storage.get_item(&key).ok()?)
+            .and_then(|json| from_json(&json))
+            .unwrap_or(default);
+        let stored = Rc::new(Stored {
+            key,
+            value: Rc::new(RefCell::new(value)),
+            on_change: RefCell::new(None),
+            listener: RefCell::new(None),
+        });
+
+        // The listener holds the value weakly, as it is owned by the value.
+        let weak: Weak<Stored<T>> = Rc::downgrade(&stored);
+        let listener = GlobalListener::new("storage", move |event: StorageEvent| {
+            if let Some(stored) = weak.upgrade() {
+                stored.sync(&event);
+            }
+        })
+        .ok();
+        *stored.listener.borrow_mut() = listener;
+        StoredState(stored)
+    }
+
+    /// Registers a callback which is invoked with the new value whenever it
+    /// is changed by another tab. Use it to update the component state.
+    pub fn on_change(self, callback: impl Fn(&T) + 'static) -> Self {
+        *self.0.on_change.borrow_mut() = Some(Box::new(callback));
+        self
+    }
+
+    /// Borrows the value immutably.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.0.value.borrow()
+    }
+
+    /// Borrows the value mutably, writing it back to the storage once the
+    /// borrow ends.
+    pub fn borrow_mut(&self) -> StoredRefMut<'_, T> {
+        StoredRefMut {
+            key: &self.0.key,
+            value: self.0.value.borrow_mut(),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stored<T> {
+    /// Updates the value if the event is of a write to its key by another
+    /// tab.
+    fn sync(&self, event: &StorageEvent) {
+        if event.key().as_ref() != Some(&self.key) {
+            return;
+        }
+        let is_local = match (event.storage_area(), local_storage()) {
+            (Some(area), Some(storage)) => area == storage,
+            _ => false,
+        };
+        let value = match event.new_value().and_then(|json| from_json(&json)) {
+            Some(value) if is_local => value,
+            _ => return,
+        };
+        *self.value.borrow_mut() = value;
+        if let Some(ref on_change) = *self.on_change.borrow() {
+            on_change(&*self.value.borrow());
+        }
+    }
+}
+
+impl<T> Clone for StoredState<T> {
+    fn clone(&self) -> Self {
+        StoredState(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for StoredState<T> {
+    /// StoredStates are equal only when they are the clones of the same
+    /// StoredState.
+    fn eq(&self, other: &StoredState<T>) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A mutable borrow of a `StoredState`, which writes the value back to the
+/// storage when dropped.
+pub struct StoredRefMut<'a, T: Serialize> {
+    key: &'a str,
+    value: RefMut<'a, T>,
+}
+
+impl<'a, T: Serialize> Deref for StoredRefMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T: Serialize> DerefMut for StoredRefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'a, T: Serialize> Drop for StoredRefMut<'a, T> {
+    fn drop(&mut self) {
+        let json = serde_wasm_bindgen::to_value(&*self.value)
+            .ok()
+            .and_then(|value| JSON::stringify(&value).ok())
+            .and_then(|json| json.as_string())
+            .expect_msg("The stored value is expected to be serializable to JSON.");
+        // The storage may be full or disabled, in which case the value lives
+        // on only in the memory.
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(self.key, &json);
+        }
+    }
+}
+
+/// The `localStorage`, unless it is disabled.
+fn local_storage() -> Option<Storage> {
+    window().unwrap().local_storage().ok()?
+}
+
+/// Deserializes the value from the JSON.
+fn from_json<T: DeserializeOwned>(json: &str) -> Option<T> {
+    let value = JSON::parse(json).ok()?;
+    serde_wasm_bindgen::from_value(value).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use wasm_bindgen_test::*;
+    use web_sys::StorageEventInit;
+
+    #[wasm_bindgen_test]
+    fn should_read_and_write_the_local_storage() {
+        let storage = local_storage().unwrap();
+        storage.set_item("ruukh-count", "5").unwrap();
+        let count = StoredState::new("ruukh-count", 0);
+        assert_eq!(*count.borrow(), 5);
+
+        *count.borrow_mut() += 1;
+        assert_eq!(storage.get_item("ruukh-count").unwrap().unwrap(), "6");
+
+        storage.remove_item("ruukh-missing").unwrap();
+        let missing = StoredState::new("ruukh-missing", vec![1, 2]);
+        assert_eq!(*missing.borrow(), vec![1, 2]);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_sync_the_writes_of_the_other_tabs() {
+        let storage = local_storage().unwrap();
+        storage.remove_item("ruukh-theme").unwrap();
+        let changes = Rc::new(Cell::new(0));
+        let changes_clone = changes.clone();
+        let theme = StoredState::new("ruukh-theme", "light".to_string())
+            .on_change(move |_| changes_clone.set(changes_clone.get() + 1));
+
+        let write = |key: &str, json: &str| {
+            let mut init = StorageEventInit::new();
+            init.key(Some(key))
+                .new_value(Some(json))
+                .storage_area(Some(&storage));
+            let event = StorageEvent::new_with_event_init_dict("storage", &init).unwrap();
+            window().unwrap().dispatch_event(&event).unwrap();
+        };
+
+        write("ruukh-other", r#""dark""#);
+        assert_eq!(*theme.borrow(), "light");
+
+        write("ruukh-theme", r#""dark""#);
+        assert_eq!(*theme.borrow(), "dark");
+        assert_eq!(changes.get(), 1);
+    }
+}
@@ -0,0 +1,118 @@
+//! Vibrating the device, e.g. to emphasize a notification or to give the
+//! feedback on an invalid input.
+//!
+//! Only some of the mobile browsers support it, so nothing vibrates in the
+//! others.
+//!
+//! Requires the `vibration` feature.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for SignUp {
+//!     fn render(&self) -> Markup<Self> {
+//!         let hint = if use_vibration_support() {
+//!             "Your phone will buzz on an invalid input."
+//!         } else {
+//!             ""
+//!         };
+//!         html! {
+//!             <form @submit={Self::on_submit}>"..."</form>
+//!             <p>{hint}</p>
+//!         }
+//!     }
+//! }
+//!
+//! impl SignUp {
+//!     fn on_submit(&self, _: Event) {
+//!         if !self.is_valid() {
+//!             vibrate(VibrationPattern::Alternating(vec![100, 50, 100]));
+//!         }
+//!     }
+//! }
+//! ```
+
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Navigator};
+
+/// How long the device vibrates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VibrationPattern {
+    /// Vibrates for the milliseconds.
+    Single(u32),
+    /// Vibrates & pauses alternately for the milliseconds, starting with a
+    /// vibration.
+    Alternating(Vec<u32>),
+}
+
+impl VibrationPattern {
+    fn to_js(&self) -> JsValue {
+        match self {
+            VibrationPattern::Single(duration) => JsValue::from(*duration),
+            VibrationPattern::Alternating(durations) => {
+                let pattern = Array::new();
+                for duration in durations {
+                    pattern.push(&JsValue::from(*duration));
+                }
+                pattern.into()
+            }
+        }
+    }
+}
+
+/// The `navigator.vibrate` along with the navigator, if the browser supports
+/// it.
+fn vibrate_method() -> Option<(Navigator, Function)> {
+    let navigator = window()?.navigator();
+    let vibrate = Reflect::get(&navigator, &JsValue::from_str("vibrate"))
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    Some((navigator, vibrate))
+}
+
+/// Vibrates the device in the pattern, stopping the vibration in progress if
+/// any. An empty pattern or `Single(0)` only stops it.
+///
+/// Returns `false` when the browser does not support the vibration or
+/// refuses to vibrate, e.g. when the user has not interacted with the page
+/// yet.
+pub fn vibrate(pattern: VibrationPattern) -> bool {
+    vibrate_method()
+        .and_then(|(navigator, vibrate)| vibrate.call1(&navigator, &pattern.to_js()).ok())
+        .and_then(|vibrated| vibrated.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether the browser supports the vibration. Must be called from within
+/// `render`.
+///
+/// It does not change as long as the page lives, so the component is never
+/// rerendered for it.
+pub fn use_vibration_support() -> bool {
+    vibrate_method().is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_convert_the_pattern_to_js() {
+        assert_eq!(VibrationPattern::Single(200).to_js().as_f64(), Some(200.0));
+
+        let pattern: Array = VibrationPattern::Alternating(vec![100, 50, 100])
+            .to_js()
+            .unchecked_into();
+        assert_eq!(pattern.length(), 3);
+        assert_eq!(pattern.get(1).as_f64(), Some(50.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_not_vibrate_without_the_support() {
+        if !use_vibration_support() {
+            assert!(!vibrate(VibrationPattern::Single(100)));
+        }
+    }
+}
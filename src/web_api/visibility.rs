@@ -0,0 +1,119 @@
+//! The visibility of the page evaluated in `render`, for the components which
+//! pause their timers, connections or polling while the tab is hidden.
+//!
+//! # Example
+//! ```ignore
+//! impl Render for Dashboard {
+//!     fn render(&self) -> Markup<Self> {
+//!         // The poller, holding an `Interval`, is dropped while the page is
+//!         // hidden, which clears its timer.
+//!         let poller = if use_page_visibility() == PageVisibility::Hidden {
+//!             html! {}
+//!         } else {
+//!             html! { <Poller></Poller> }
+//!         };
+//!         html! {
+//!             <main>{ poller }</main>
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::context;
+use js_sys::Reflect;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Document, Event};
+
+/// The visibility of the page, as in `document.visibilityState`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageVisibility {
+    /// The page is visible in a foreground tab.
+    Visible,
+    /// The page is in a background tab or a minimized window.
+    Hidden,
+    /// The page is being prerendered, before the user has opened it.
+    Prerender,
+}
+
+impl PageVisibility {
+    /// Gets the visibility of the page from the document.
+    fn of(document: &Document) -> PageVisibility {
+        let state = Reflect::get(document.as_ref(), &JsValue::from_str("visibilityState"))
+            .ok()
+            .and_then(|state| state.as_string());
+        match state.as_ref().map(String::as_str) {
+            Some("hidden") => PageVisibility::Hidden,
+            Some("prerender") => PageVisibility::Prerender,
+            _ => PageVisibility::Visible,
+        }
+    }
+}
+
+/// The current visibility of the page. Must be called from within `render`.
+///
+/// The visibility is watched for as long as the component lives, which is
+/// rerendered whenever it changes. The page is always visible when rendered
+/// to a string.
+pub fn use_page_visibility() -> PageVisibility {
+    context::keep("page_visibility", VisibilityListener::new).map_or(
+        PageVisibility::Visible,
+        |listener| PageVisibility::of(&listener.document),
+    )
+}
+
+/// A listener of the changes of the visibility, which stops listening once
+/// dropped.
+struct VisibilityListener {
+    document: Document,
+    listener: Closure<dyn Fn(Event)>,
+}
+
+impl VisibilityListener {
+    /// Starts listening to the changes of the visibility.
+    fn new(on_change: Rc<dyn Fn()>) -> VisibilityListener {
+        let document = window().unwrap().document().unwrap();
+        let listener: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |_| on_change()));
+        // The document is around as long as the page is, so it does not fail.
+        let _ = document.add_event_listener_with_callback(
+            "visibilitychange",
+            listener.as_ref().unchecked_ref(),
+        );
+        VisibilityListener { document, listener }
+    }
+}
+
+impl Drop for VisibilityListener {
+    fn drop(&mut self) {
+        let _ = self.document.remove_event_listener_with_callback(
+            "visibilitychange",
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::context::Scope;
+    use std::cell::RefCell;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn should_get_the_visibility_of_the_page() {
+        let scope = Rc::new(RefCell::new(Scope::default()));
+        scope.borrow_mut().set_sender(crate::message_sender());
+        context::enter(scope.clone());
+
+        let document = window().unwrap().document().unwrap();
+        assert_eq!(use_page_visibility(), PageVisibility::of(&document));
+        context::exit();
+
+        // The change asks to rerender the component.
+        assert!(!context::is_stale(&scope));
+        document
+            .dispatch_event(&Event::new("visibilitychange").unwrap())
+            .unwrap();
+        assert!(context::is_stale(&scope));
+    }
+}
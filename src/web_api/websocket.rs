@@ -0,0 +1,244 @@
+//! WebSocket connections which are closed along with the component holding
+//! them.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Chat {
+//!     #[state]
+//!     messages: Vec<String>,
+//!     #[state]
+//!     socket: Option<WsHandle>,
+//! }
+//!
+//! impl Lifecycle for Chat {
+//!     fn created(&self) {
+//!         let setter = self.state_setter();
+//!         let socket = WsConnection::new("wss://chat.example.com")
+//!             .on_message(move |message| {
+//!                 if let WsMessage::Text(text) = message {
+//!                     setter.set_state(|state| state.messages.push(text.clone()));
+//!                 }
+//!             })
+//!             .connect()
+//!             .ok();
+//!         self.set_state(|state| state.socket = socket.clone());
+//!     }
+//! }
+//! ```
+
+use js_sys::{ArrayBuffer, Uint8Array};
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
+
+/// The normal closure of a connection, when its purpose is fulfilled.
+const NORMAL_CLOSURE: u16 = 1000;
+
+/// A message received on a WebSocket connection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsMessage {
+    /// A text frame
+    Text(String),
+    /// A binary frame
+    Binary(Vec<u8>),
+}
+
+impl WsMessage {
+    /// Gets the message out of the event, if it is a text or an array buffer.
+    fn from_event(event: &MessageEvent) -> Option<WsMessage> {
+        let data = event.data();
+        if let Some(text) = data.as_string() {
+            Some(WsMessage::Text(text))
+        } else if data.is_instance_of::<ArrayBuffer>() {
+            Some(WsMessage::Binary(Uint8Array::new(&data).to_vec()))
+        } else {
+            None
+        }
+    }
+}
+
+/// A WebSocket connection which is yet to be opened, along with its
+/// callbacks.
+///
+/// The callbacks are invoked with the state setters of the component, so
+/// that every message received rerenders it.
+pub struct WsConnection {
+    url: String,
+    on_message: Option<Box<dyn Fn(WsMessage)>>,
+    on_open: Option<Box<dyn Fn()>>,
+    on_error: Option<Box<dyn Fn()>>,
+    on_close: Option<Box<dyn Fn(u16)>>,
+}
+
+impl WsConnection {
+    /// Create a connection to the `url`.
+    pub fn new(url: impl Into<String>) -> WsConnection {
+        WsConnection {
+            url: url.into(),
+            on_message: None,
+            on_open: None,
+            on_error: None,
+            on_close: None,
+        }
+    }
+
+    /// Invoked on every message received.
+    pub fn on_message(mut self, callback: impl Fn(WsMessage) + 'static) -> Self {
+        self.on_message = Some(Box::new(callback));
+        self
+    }
+
+    /// Invoked when the connection is opened.
+    pub fn on_open(mut self, callback: impl Fn() + 'static) -> Self {
+        self.on_open = Some(Box::new(callback));
+        self
+    }
+
+    /// Invoked when the connection fails.
+    pub fn on_error(mut self, callback: impl Fn() + 'static) -> Self {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Invoked with the close code when the connection is closed by the
+    /// server or by a failure. It is not invoked when the handle is dropped.
+    pub fn on_close(mut self, callback: impl Fn(u16) + 'static) -> Self {
+        self.on_close = Some(Box::new(callback));
+        self
+    }
+
+    /// Opens the connection. Fails if the `url` is invalid.
+    pub fn connect(self) -> Result<WsHandle, JsValue> {
+        let socket = WebSocket::new(&self.url)?;
+        socket.set_binary_type(BinaryType::Arraybuffer);
+
+        let on_message = self.on_message;
+        let on_message: Closure<dyn Fn(MessageEvent)> =
+            Closure::wrap(Box::new(move |event: MessageEvent| {
+                if let (Some(on_message), Some(message)) =
+                    (on_message.as_ref(), WsMessage::from_event(&event))
+                {
+                    on_message(message);
+                }
+            }));
+        let on_open = self.on_open;
+        let on_open: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |_| {
+            if let Some(ref on_open) = on_open {
+                on_open();
+            }
+        }));
+        let on_error = self.on_error;
+        let on_error: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |_| {
+            if let Some(ref on_error) = on_error {
+                on_error();
+            }
+        }));
+        let on_close = self.on_close;
+        let on_close: Closure<dyn Fn(CloseEvent)> =
+            Closure::wrap(Box::new(move |event: CloseEvent| {
+                if let Some(ref on_close) = on_close {
+                    on_close(event.code());
+                }
+            }));
+
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        Ok(WsHandle(Rc::new(Socket {
+            socket,
+            _on_message: on_message,
+            _on_open: on_open,
+            _on_error: on_error,
+            _on_close: on_close,
+        })))
+    }
+}
+
+/// An open WebSocket connection, which is closed once it is dropped along
+/// with all of its clones.
+///
+/// Keep it in the state of the component, so that it is closed when the
+/// component is destroyed without any cleanup in the lifecycle hooks.
+#[derive(Clone)]
+pub struct WsHandle(Rc<Socket>);
+
+/// The socket of a `WsHandle` which is shared by its clones.
+struct Socket {
+    socket: WebSocket,
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+    _on_open: Closure<dyn Fn(Event)>,
+    _on_error: Closure<dyn Fn(Event)>,
+    _on_close: Closure<dyn Fn(CloseEvent)>,
+}
+
+impl WsHandle {
+    /// Sends a binary message. Fails if the connection is not open yet.
+    pub fn send(&self, data: &[u8]) -> Result<(), JsValue> {
+        self.0.socket.send_with_u8_array(data)
+    }
+
+    /// Sends a text message. Fails if the connection is not open yet.
+    pub fn send_text(&self, data: &str) -> Result<(), JsValue> {
+        self.0.socket.send_with_str(data)
+    }
+}
+
+impl PartialEq for WsHandle {
+    /// WsHandles are equal only when they are the clones of the same
+    /// WsHandle.
+    fn eq(&self, other: &WsHandle) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        // The callbacks are dropped along with the component, so they must
+        // not be invoked while the socket is closing.
+        self.socket.set_onmessage(None);
+        self.socket.set_onopen(None);
+        self.socket.set_onerror(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close_with_code(NORMAL_CLOSURE);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use web_sys::MessageEventInit;
+
+    fn message_event(data: &JsValue) -> MessageEvent {
+        let mut init = MessageEventInit::new();
+        init.data(data);
+        MessageEvent::new_with_event_init_dict("message", &init).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn should_get_the_message_out_of_the_event() {
+        let text = message_event(&JsValue::from_str("Hello"));
+        assert_eq!(
+            WsMessage::from_event(&text),
+            Some(WsMessage::Text("Hello".to_string()))
+        );
+
+        let bytes = Uint8Array::new_with_length(3);
+        bytes.copy_from(&[1, 2, 3]);
+        let binary = message_event(&bytes.buffer());
+        assert_eq!(
+            WsMessage::from_event(&binary),
+            Some(WsMessage::Binary(vec![1, 2, 3]))
+        );
+
+        assert_eq!(WsMessage::from_event(&message_event(&JsValue::NULL)), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_fail_to_connect_to_an_invalid_url() {
+        assert!(WsConnection::new("not a url").connect().is_err());
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(target_arch = "wasm32")]
+#![feature(proc_macro_hygiene, decl_macro)]
+
+use futures::Future;
+use js_sys::Promise;
+use ruukh::prelude::*;
+use std::cell::RefCell;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{window, Event, HtmlInputElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static CLEAR: RefCell<Option<<Note as StateSetter>::Setter>> = RefCell::new(None);
+}
+
+#[component]
+struct Note {
+    #[state]
+    text: String,
+}
+
+impl Lifecycle for Note {
+    fn created(&self) {
+        CLEAR.with(|clear| *clear.borrow_mut() = Some(self.state_setter()));
+    }
+}
+
+impl Render for Note {
+    fn render(&self) -> Markup<Self> {
+        html! {
+            <input @bind={self.text}>
+        }
+    }
+}
+
+fn next_frame() -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, _| {
+        window().unwrap().request_animation_frame(&resolve).unwrap();
+    }))
+}
+
+#[wasm_bindgen_test(async)]
+fn should_show_the_bound_state_after_the_user_has_typed() -> impl Future<Item = (), Error = JsValue>
+{
+    let document = window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&container).unwrap();
+    App::<Note>::new().mount(container.clone());
+
+    let input: HtmlInputElement = container
+        .query_selector("input")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+    input.set_value("Buy milk");
+    input.dispatch_event(&Event::new("input").unwrap()).unwrap();
+
+    next_frame()
+        .and_then(|_| {
+            CLEAR.with(|clear| {
+                clear
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .set_state(|state| state.text = String::new());
+            });
+            next_frame()
+        })
+        .map(move |_| {
+            assert_eq!(input.value(), "");
+        })
+}
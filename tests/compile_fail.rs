@@ -0,0 +1,5 @@
+#[test]
+fn should_fail_to_compile_malformed_markup() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
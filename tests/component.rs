@@ -166,3 +166,18 @@ fn should_build_a_component_with_default_option_props() {
     assert_eq!(props.prop_a, Some(false));
     assert_eq!(props.prop_b, Some(3));
 }
+
+#[test]
+fn should_build_an_isolated_component() {
+    #[component(isolated)]
+    struct Clock {
+        #[state]
+        time: String,
+    }
+
+    #[component]
+    struct Calendar;
+
+    assert!(Clock::ISOLATED);
+    assert!(!Calendar::ISOLATED);
+}
@@ -0,0 +1,113 @@
+#![cfg(target_arch = "wasm32")]
+#![feature(proc_macro_hygiene, decl_macro)]
+
+use futures::Future;
+use js_sys::Promise;
+use ruukh::{prelude::*, web_api::MouseEvent};
+use std::cell::RefCell;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{window, Element, HtmlElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+#[derive(Lifecycle)]
+struct Counter {
+    id: u32,
+    #[state]
+    count: i32,
+}
+
+impl Render for Counter {
+    fn render(&self) -> Markup<Self> {
+        html! {
+            <button id={format!("counter-{}", self.id)} @click={Self::increment}>
+                { self.id }":"{ self.count }
+            </button>
+        }
+    }
+}
+
+impl Counter {
+    fn increment(&self, _: MouseEvent) {
+        self.set_state(|state| state.count += 1);
+    }
+}
+
+thread_local! {
+    static SHUFFLE: RefCell<Option<<Counters as StateSetter>::Setter>> = RefCell::new(None);
+}
+
+#[component]
+struct Counters {
+    #[state(default = vec![1, 2, 3])]
+    ids: Vec<u32>,
+}
+
+impl Lifecycle for Counters {
+    fn created(&self) {
+        SHUFFLE.with(|shuffle| *shuffle.borrow_mut() = Some(self.state_setter()));
+    }
+}
+
+impl Render for Counters {
+    fn render(&self) -> Markup<Self> {
+        html! {
+            <div>
+                <Counter ruukh:for={id in self.ids.iter()} key={*id} id={*id}></Counter>
+            </div>
+        }
+    }
+}
+
+fn next_frame() -> JsFuture {
+    JsFuture::from(Promise::new(&mut |resolve, _| {
+        window().unwrap().request_animation_frame(&resolve).unwrap();
+    }))
+}
+
+fn click(container: &Element, id: u32) {
+    container
+        .query_selector(&format!("#counter-{}", id))
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<HtmlElement>()
+        .click();
+}
+
+#[wasm_bindgen_test(async)]
+fn should_keep_the_state_of_keyed_components_on_reorder() -> impl Future<Item = (), Error = JsValue>
+{
+    let document = window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&container).unwrap();
+    App::<Counters>::new().mount(container.clone());
+
+    click(&container, 1);
+    click(&container, 3);
+    click(&container, 3);
+
+    next_frame()
+        .and_then(|_| {
+            SHUFFLE.with(|shuffle| {
+                shuffle
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .set_state(|state| state.ids = vec![3, 1, 2]);
+            });
+            next_frame()
+        })
+        .map(move |_| {
+            assert_eq!(
+                container.inner_html(),
+                "<div>\
+                 <button id=\"counter-3\">3:2</button>\
+                 <button id=\"counter-1\">1:1</button>\
+                 <button id=\"counter-2\">2:0</button>\
+                 </div>"
+            );
+        })
+}
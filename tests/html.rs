@@ -1,7 +1,12 @@
 #![feature(proc_macro_hygiene)]
 
-use ruukh::prelude::*;
-use web_sys::Event;
+use ruukh::{
+    html_attrs::{Method, Target},
+    prelude::*,
+    web_api::{KeyboardEvent, MouseEvent},
+};
+use std::cell::Cell;
+use web_sys::Element;
 
 #[test]
 fn should_expand_single_element() {
@@ -19,6 +24,45 @@ fn should_expand_list_of_elements() {
     };
 }
 
+fn wrap(markup: Markup<()>) -> Markup<()> {
+    html! {
+        <section>{ markup }</section>
+    }
+}
+
+#[test]
+fn should_expand_to_markup_in_any_expression_position() {
+    let markup: Markup<()>;
+    markup = html! {
+        <div>"Assigned"</div>
+    };
+    let _ = markup;
+
+    let _ = wrap(html! {
+        "Hello"
+        <strong>"World"</strong>
+    });
+
+    let count = Some(2);
+    let _ = match count {
+        Some(0) => html!(),
+        Some(count) => html! {
+            <span>{ count }" items"</span>
+        },
+        None => html! {
+            "Loading..."
+        },
+    };
+
+    let _ = if let Some(count) = count {
+        html! {
+            <p>{ count }</p>
+        }
+    } else {
+        html!()
+    };
+}
+
 #[test]
 fn should_expand_text() {
     let _: Markup<()> = html! {
@@ -41,7 +85,7 @@ fn should_expand_element_with_props() {
     };
 }
 
-fn on_click(_: &(), _: Event) {}
+fn on_click(_: &(), _: MouseEvent) {}
 
 #[test]
 fn should_expand_element_with_event_listener() {
@@ -50,6 +94,24 @@ fn should_expand_element_with_event_listener() {
     };
 }
 
+fn on_keydown(_: &(), event: KeyboardEvent) {
+    let _ = event.key();
+}
+
+#[test]
+fn should_expand_element_with_typed_event_listeners() {
+    let _: Markup<()> = html! {
+        <input @click={on_click} @keydown={on_keydown} @scroll={|_: &(), _| {}}>
+    };
+}
+
+#[test]
+fn should_expand_element_with_event_modifiers() {
+    let _: Markup<()> = html! {
+        <a href={"/about"} @click.prevent.stop={on_click}>"About"</a>
+    };
+}
+
 #[test]
 fn should_expand_element_with_attributes() {
     let _: Markup<()> = html! {
@@ -62,3 +124,172 @@ fn should_expand_element_with_attributes() {
         </button>
     };
 }
+
+enum Size {
+    Small,
+    Large,
+}
+
+#[test]
+fn should_expand_element_with_if_and_match_attribute_values() {
+    let open = true;
+    let size = Size::Large;
+    let _: Markup<()> = html! {
+        <button
+            aria-expanded={if open { "true" } else { "false" }}
+            class={match size {
+                Size::Small => "btn-sm",
+                Size::Large => "btn-lg",
+            }}
+            disabled={if open { false } else { true }}
+        >"Menu"
+        </button>
+    };
+}
+
+#[test]
+fn should_expand_element_with_typed_attribute_values() {
+    let _: Markup<()> = html! {
+        <a href="https://example.com" target={Target::Blank}>"Example"</a>
+        <form action="/login" method={Method::Post}></form>
+    };
+}
+
+#[test]
+fn should_expand_element_with_css_properties() {
+    let color = "red";
+    let _: Markup<()> = html! {
+        <div style={CssProperties::new().color(color).prop("--gap", "4px")}></div>
+    };
+}
+
+#[test]
+fn should_expand_element_with_class_list() {
+    let active = true;
+    let _: Markup<()> = html! {
+        <button class={ClassList::new().add("btn").add_if("active", active)}>"Save"</button>
+        <button class={ClassList::from("btn btn-sm")}>"Cancel"</button>
+    };
+}
+
+#[test]
+fn should_expand_keyed_elements() {
+    let _: Markup<()> = html! {
+        <div key={1}>"Hello"</div>
+        <div key={2}>"World"</div>
+        <input key={"name"}>
+    };
+}
+
+#[test]
+fn should_expand_keyed_and_unkeyed_elements() {
+    let _: Markup<()> = html! {
+        <div key={1}>"Hello"</div>
+        <div>"World"</div>
+    };
+}
+
+#[test]
+fn should_expand_repeated_elements() {
+    let names = vec!["Hello", "World"];
+    let _: Markup<()> = html! {
+        <ul>
+            <li ruukh:for={name in names.iter()}>{ *name }</li>
+        </ul>
+        <input ruukh:for={(index, name) in names.iter().enumerate()} key={index} value={*name}>
+    };
+}
+
+#[test]
+fn should_expand_custom_self_closing_elements() {
+    let _: Markup<()> = html! {
+        #![self_closing(my-icon)]
+        <button><my-icon name={"save"}>"Save"</button>
+    };
+}
+
+#[test]
+fn should_expand_element_with_class_directives() {
+    let is_active = true;
+    let _: Markup<()> = html! {
+        <div class={"btn"} class:active={is_active} class:hidden={!is_active}>"Click"</div>
+        <input class:active={is_active}>
+    };
+}
+
+#[test]
+fn should_expand_element_with_style_directives() {
+    let color = "red";
+    let _: Markup<()> = html! {
+        <div style={"margin: 0"} style:color={color} style:--main-color={Some("blue")}>"Hi"</div>
+    };
+}
+
+#[test]
+fn should_expand_element_with_node_ref() {
+    let node_ref = NodeRef::new();
+    let _: Markup<()> = html! {
+        <div ref={node_ref.clone()}>
+            <input ref={node_ref}>
+        </div>
+    };
+}
+
+fn portal_markup(target: Element) -> Markup<()> {
+    html! {
+        <div>
+            <Portal target={target}>
+                <span>"Tooltip"</span>
+            </Portal>
+        </div>
+    }
+}
+
+#[test]
+fn should_expand_portal() {
+    // Elements cannot be created outside the browser, so only check that it
+    // expands.
+    let _: fn(Element) -> Markup<()> = portal_markup;
+}
+
+#[test]
+fn should_expand_svg_elements() {
+    let _: Markup<()> = html! {
+        <svg width={"10"} height={"10"}>
+            <circle cx={"5"} cy={"5"} r={"4"}></circle>
+            <image xlink:href={"icon.png"}></image>
+        </svg>
+    };
+}
+
+#[test]
+fn should_expand_element_with_inner_html() {
+    let _: Markup<()> = html! {
+        <article class={"post"} inner-html={"<p>Hello</p>"}></article>
+    };
+}
+
+thread_local! {
+    static COPYRIGHT_CALLS: Cell<usize> = Cell::new(0);
+}
+
+fn copyright() -> &'static str {
+    COPYRIGHT_CALLS.with(|calls| calls.set(calls.get() + 1));
+    "2018"
+}
+
+fn footer() -> Markup<()> {
+    html_static! {
+        <footer>"Made with Ruukh, "{ copyright() }</footer>
+    }
+}
+
+#[test]
+fn should_construct_static_markup_only_once() {
+    let _ = footer();
+    let _: Markup<()> = html! {
+        <main>"Hello"</main>
+        { footer() }
+    };
+    assert_eq!(COPYRIGHT_CALLS.with(|calls| calls.get()), 1);
+}
@@ -0,0 +1,212 @@
+#![feature(proc_macro_hygiene, decl_macro)]
+
+use ruukh::{prelude::*, vdom::ssr::render_to_string};
+
+#[test]
+fn should_render_elements_to_string() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page;
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <div class="page" hidden={true}>
+                    <input name="title" disabled={false}>
+                    "Hello World!"
+                </div>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        r#"<div class="page" hidden><input name="title">Hello World!</div>"#
+    );
+}
+
+#[test]
+fn should_escape_text_and_attributes() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page;
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <a title={r#"Say "Hi" & 'Bye'"#}>"<b>Tom & Jerry</b>"</a>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        "<a title=\"Say &quot;Hi&quot; &amp; &#39;Bye&#39;\">\
+         &lt;b&gt;Tom &amp; Jerry&lt;/b&gt;</a>"
+    );
+}
+
+#[test]
+fn should_render_nested_components_to_string() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page {
+        #[state(default = 3)]
+        count: i32,
+    }
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <main>
+                    <Greeting name={"Ruukh".to_string()}></Greeting>
+                    <span>{ self.count }</span>
+                </main>
+            }
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Greeting {
+        name: String,
+    }
+
+    impl Render for Greeting {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <p>"Hello, "{ &self.name }"!"</p>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        "<main><p>Hello, Ruukh!</p><span>3</span></main>"
+    );
+}
+
+#[test]
+fn should_render_the_value_of_textarea_as_its_content() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page;
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <textarea name="note" value={"\nTom & <Jerry>"}></textarea>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        "<textarea name=\"note\">\n\nTom &amp; &lt;Jerry&gt;</textarea>"
+    );
+}
+
+#[test]
+fn should_preserve_the_whitespace_in_pre() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page;
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <pre>"\nfn main() {\n    run();\n}"</pre>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        "<pre>\n\nfn main() {\n    run();\n}</pre>"
+    );
+}
+
+#[test]
+fn should_escape_the_text_in_noscript() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page;
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <noscript>"</noscript><script>alert(1)</script>"<p>"Enable JavaScript."</p></noscript>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        "<noscript>&lt;/noscript&gt;&lt;script&gt;alert(1)&lt;/script&gt;\
+         <p>Enable JavaScript.</p></noscript>"
+    );
+}
+
+#[test]
+fn should_render_a_fragment_for_every_item_without_a_wrapper() {
+    struct Term {
+        id: u32,
+        name: &'static str,
+        definition: &'static str,
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page;
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            let terms = vec![
+                Term {
+                    id: 1,
+                    name: "VDOM",
+                    definition: "A tree of the nodes to render.",
+                },
+                Term {
+                    id: 2,
+                    name: "Patch",
+                    definition: "Applies the changes onto the DOM.",
+                },
+            ];
+            html! {
+                <dl>
+                    <Fragment ruukh:for={term in terms.iter()} key={term.id}>
+                        <dt>{ term.name }</dt>
+                        <dd>{ term.definition }</dd>
+                    </Fragment>
+                </dl>
+            }
+        }
+    }
+
+    assert_eq!(
+        render_to_string::<Page>(),
+        "<dl><dt>VDOM</dt><dd>A tree of the nodes to render.</dd>\
+         <dt>Patch</dt><dd>Applies the changes onto the DOM.</dd></dl>"
+    );
+}
+
+#[test]
+fn should_render_the_value_bound_to_an_input() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page {
+        #[state(default = "Ruukh".to_string())]
+        name: String,
+    }
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <input @bind={self.name}>
+            }
+        }
+    }
+
+    assert_eq!(render_to_string::<Page>(), r#"<input value="Ruukh">"#);
+}
@@ -0,0 +1,9 @@
+#![feature(proc_macro_hygiene)]
+
+use ruukh::prelude::*;
+
+fn main() {
+    let _: Markup<()> = html! {
+        <div class={ "big" "red" }></div>
+    };
+}